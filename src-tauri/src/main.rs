@@ -24,9 +24,14 @@ use commands::{
 };
 use shortcuts::register_shortcuts;
 use specs::{
-    get_all_tags, get_dependency_graph, get_project_stats, get_spec_dependencies_cmd,
-    get_spec_detail, get_specs, get_specs_by_status, search_specs, update_spec_status,
-    validate_all_specs_cmd, validate_spec_cmd,
+    export_dependency_graph, export_validation_report, fix_spec_issues, get_all_tags,
+    get_assignee_stats, get_completion_forecast, get_dependency_graph, get_flow_metrics,
+    get_frontmatter_schema, get_load_diagnostics, get_portfolio_stats, get_project_stats,
+    get_spec_activity, get_spec_dependencies_cmd, get_spec_detail, get_spec_impact_cmd,
+    get_spec_tokens, get_spec_traceability, get_specs, get_specs_by_status, get_stale_specs,
+    get_stats_history, get_tag_analytics, get_validation_rules, merge_tags, record_stats_snapshot,
+    rename_tag, search_specs, toggle_spec_task, update_spec_status, validate_all_specs_cmd,
+    validate_spec_cmd,
 };
 use state::DesktopState;
 
@@ -97,13 +102,34 @@ fn main() {
             get_specs,
             get_spec_detail,
             get_project_stats,
+            record_stats_snapshot,
+            get_stats_history,
+            get_portfolio_stats,
+            get_flow_metrics,
+            get_completion_forecast,
             get_dependency_graph,
+            export_dependency_graph,
             get_spec_dependencies_cmd,
+            get_spec_impact_cmd,
             search_specs,
             get_specs_by_status,
             get_all_tags,
+            get_tag_analytics,
+            rename_tag,
+            merge_tags,
             validate_spec_cmd,
             validate_all_specs_cmd,
+            export_validation_report,
+            get_load_diagnostics,
+            get_validation_rules,
+            get_frontmatter_schema,
+            fix_spec_issues,
+            get_stale_specs,
+            get_assignee_stats,
+            get_spec_tokens,
+            get_spec_traceability,
+            get_spec_activity,
+            toggle_spec_task,
             update_spec_status
         ])
         .run(tauri::generate_context!())
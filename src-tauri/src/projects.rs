@@ -69,6 +69,18 @@ impl ProjectStore {
             .cloned()
     }
 
+    /// Find a project by id, or by name (case-insensitive)
+    pub fn find_by_name_or_id(&self, key: &str) -> Option<DesktopProject> {
+        self.find(key).or_else(|| {
+            self.data
+                .read()
+                .projects
+                .iter()
+                .find(|project| project.name.eq_ignore_ascii_case(key))
+                .cloned()
+        })
+    }
+
     pub fn refresh(&self) -> Vec<DesktopProject> {
         if let Ok(latest) = read_projects(&self.path_json, &self.path_yaml) {
             let mut guard = self.data.write();
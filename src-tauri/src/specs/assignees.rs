@@ -0,0 +1,329 @@
+//! Assignee workload and ownership analytics
+//!
+//! Aggregates open work, estimated tokens and completions per assignee, and
+//! lists high and critical specs nobody owns. Assignee spellings are
+//! normalized (`@alice` and `Alice` are the same person) and can be mapped
+//! further through `<project>/.lean-spec/assignees.json`:
+//!
+//! ```json
+//! { "alice": ["Alice Smith", "alice@example.com"] }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::specs::flow::SpecTimeline;
+use crate::specs::reader::Spec;
+use crate::specs::stats::{PriorityCount, StatusCount};
+use crate::specs::tokens::TokenBudget;
+
+const ALIASES_DIR: &str = ".lean-spec";
+const ALIASES_FILE: &str = "assignees.json";
+
+/// Lowercase an assignee and strip a leading `@`
+pub fn normalize_assignee(assignee: &str) -> String {
+    assignee.trim().trim_start_matches('@').to_lowercase()
+}
+
+/// Maps assignee spellings to a canonical name
+#[derive(Debug, Clone, Default)]
+pub struct AssigneeAliases {
+    aliases: HashMap<String, String>,
+}
+
+impl AssigneeAliases {
+    /// Build from canonical name → aliases
+    pub fn from_map(map: HashMap<String, Vec<String>>) -> Self {
+        let mut aliases = HashMap::new();
+        for (canonical, names) in map {
+            aliases.insert(normalize_assignee(&canonical), canonical.clone());
+            for name in names {
+                aliases.insert(normalize_assignee(&name), canonical.clone());
+            }
+        }
+        Self { aliases }
+    }
+
+    /// Load the project's alias file, or no aliases when there is none
+    ///
+    /// A file that can't be read or parsed is an error naming the file, so
+    /// the UI can point at it instead of silently ignoring the aliases.
+    pub fn load(project_root: impl AsRef<Path>) -> Result<Self, String> {
+        let path = project_root.as_ref().join(ALIASES_DIR).join(ALIASES_FILE);
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+
+        serde_json::from_str(&raw)
+            .map(Self::from_map)
+            .map_err(|e| format!("Invalid assignee aliases in {}: {}", path.display(), e))
+    }
+
+    /// Canonical name for an assignee spelling
+    pub fn resolve(&self, assignee: &str) -> String {
+        let normalized = normalize_assignee(assignee);
+        self.aliases.get(&normalized).cloned().unwrap_or(normalized)
+    }
+}
+
+/// Completions in a calendar month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyCompletions {
+    /// `YYYY-MM`
+    pub month: String,
+    pub completed: i32,
+}
+
+/// Workload for one assignee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssigneeStats {
+    pub assignee: String,
+    /// Spellings found in frontmatter that resolved to this assignee
+    pub aliases: Vec<String>,
+    pub open_specs: i32,
+    pub open_by_status: Vec<StatusCount>,
+    pub open_by_priority: Vec<PriorityCount>,
+    /// Tokens across all assigned specs, for the project's model
+    pub estimated_tokens: usize,
+    pub completed_specs: i32,
+    pub completed_by_month: Vec<MonthlyCompletions>,
+}
+
+/// A high or critical spec without an assignee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnassignedSpec {
+    pub spec_name: String,
+    pub title: Option<String>,
+    pub status: String,
+    pub priority: String,
+}
+
+/// Assignee analytics for a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssigneeReport {
+    pub assignees: Vec<AssigneeStats>,
+    /// Open high and critical priority specs nobody is assigned to
+    pub unassigned_high_priority: Vec<UnassignedSpec>,
+}
+
+#[derive(Default)]
+struct Workload {
+    aliases: Vec<String>,
+    open_by_status: BTreeMap<String, i32>,
+    open_by_priority: BTreeMap<String, i32>,
+    estimated_tokens: usize,
+    completed_by_month: BTreeMap<String, i32>,
+    completed: i32,
+}
+
+/// Aggregate workload per assignee
+pub fn analyze_assignees(
+    specs: &[Spec],
+    aliases: &AssigneeAliases,
+    budget: &TokenBudget,
+) -> AssigneeReport {
+    let mut workloads: BTreeMap<String, Workload> = BTreeMap::new();
+    let mut unassigned_high_priority = Vec::new();
+
+    for spec in specs {
+        let is_open = matches!(spec.status.as_str(), "draft" | "planned" | "in-progress");
+        let assignee = spec.assignee.as_deref().filter(|a| !a.trim().is_empty());
+
+        let Some(assignee) = assignee else {
+            let priority = spec.priority.as_deref().unwrap_or("medium");
+            if is_open && matches!(priority, "high" | "critical") {
+                unassigned_high_priority.push(UnassignedSpec {
+                    spec_name: spec.spec_name.clone(),
+                    title: spec.title.clone(),
+                    status: spec.status.clone(),
+                    priority: priority.to_string(),
+                });
+            }
+            continue;
+        };
+
+        let workload = workloads.entry(aliases.resolve(assignee)).or_default();
+        if !workload.aliases.iter().any(|a| a == assignee) {
+            workload.aliases.push(assignee.to_string());
+        }
+        workload.estimated_tokens += budget.count(&spec.content_md);
+
+        if is_open {
+            *workload
+                .open_by_status
+                .entry(spec.status.clone())
+                .or_insert(0) += 1;
+            if let Some(priority) = &spec.priority {
+                *workload
+                    .open_by_priority
+                    .entry(priority.clone())
+                    .or_insert(0) += 1;
+            }
+        } else if spec.status == "complete" {
+            workload.completed += 1;
+            let completed_at = spec
+                .completed_at
+                .or_else(|| SpecTimeline::for_spec(spec, Vec::new).first_reached("complete"));
+            if let Some(at) = completed_at {
+                *workload
+                    .completed_by_month
+                    .entry(at.format("%Y-%m").to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut assignees: Vec<AssigneeStats> = workloads
+        .into_iter()
+        .map(|(assignee, workload)| {
+            let mut aliases = workload.aliases;
+            aliases.sort();
+            AssigneeStats {
+                assignee,
+                aliases,
+                open_specs: workload.open_by_status.values().sum(),
+                open_by_status: workload
+                    .open_by_status
+                    .into_iter()
+                    .map(|(status, count)| StatusCount { status, count })
+                    .collect(),
+                open_by_priority: workload
+                    .open_by_priority
+                    .into_iter()
+                    .map(|(priority, count)| PriorityCount { priority, count })
+                    .collect(),
+                estimated_tokens: workload.estimated_tokens,
+                completed_specs: workload.completed,
+                completed_by_month: workload
+                    .completed_by_month
+                    .into_iter()
+                    .map(|(month, completed)| MonthlyCompletions { month, completed })
+                    .collect(),
+            }
+        })
+        .collect();
+    assignees.sort_by(|a, b| {
+        b.open_specs
+            .cmp(&a.open_specs)
+            .then(a.assignee.cmp(&b.assignee))
+    });
+
+    unassigned_high_priority.sort_by(|a, b| {
+        let rank = |p: &str| if p == "critical" { 0 } else { 1 };
+        rank(&a.priority)
+            .cmp(&rank(&b.priority))
+            .then(a.spec_name.cmp(&b.spec_name))
+    });
+
+    AssigneeReport {
+        assignees,
+        unassigned_high_priority,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::reader::SpecBuilder;
+    use tempfile::TempDir;
+
+    fn spec(name: &str, status: &str, priority: &str, assignee: Option<&str>) -> Spec {
+        SpecBuilder::new(name)
+            .status(status)
+            .priority(priority)
+            .assignee(assignee)
+            .content("---\nstatus: planned\n---\n# Spec\n\nSome words here.")
+            .completed_at("2024-03-05T00:00:00Z".parse().unwrap())
+            .build()
+    }
+
+    #[test]
+    fn test_aliases_resolve() {
+        let aliases = AssigneeAliases::from_map(HashMap::from([(
+            "alice".to_string(),
+            vec!["Alice Smith".to_string()],
+        )]));
+
+        assert_eq!(aliases.resolve("@Alice"), "alice");
+        assert_eq!(aliases.resolve("alice smith"), "alice");
+        assert_eq!(aliases.resolve("@bob"), "bob");
+    }
+
+    #[test]
+    fn test_load_aliases() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(
+            AssigneeAliases::load(temp.path()).unwrap().resolve("Alice"),
+            "alice"
+        );
+
+        let dir = temp.path().join(ALIASES_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(ALIASES_FILE), "{ not json").unwrap();
+        let error = AssigneeAliases::load(temp.path()).unwrap_err();
+        assert!(error.starts_with("Invalid assignee aliases in"));
+    }
+
+    #[test]
+    fn test_analyze_assignees() {
+        let specs = vec![
+            spec("001-a", "in-progress", "high", Some("@alice")),
+            spec("002-b", "planned", "low", Some("Alice Smith")),
+            spec("003-c", "complete", "medium", Some("alice")),
+            spec("004-d", "planned", "medium", Some("bob")),
+            spec("005-e", "planned", "critical", None),
+            spec("006-f", "in-progress", "high", None),
+            spec("007-g", "complete", "critical", None),
+        ];
+        let aliases = AssigneeAliases::from_map(HashMap::from([(
+            "alice".to_string(),
+            vec!["Alice Smith".to_string()],
+        )]));
+
+        let report = analyze_assignees(&specs, &aliases, &TokenBudget::default());
+
+        assert_eq!(report.assignees.len(), 2);
+        let alice = &report.assignees[0];
+        assert_eq!(alice.assignee, "alice");
+        assert_eq!(alice.aliases, vec!["@alice", "Alice Smith", "alice"]);
+        assert_eq!(alice.open_specs, 2);
+        assert_eq!(alice.completed_specs, 1);
+        assert_eq!(alice.completed_by_month[0].month, "2024-03");
+        assert!(alice.estimated_tokens > report.assignees[1].estimated_tokens);
+
+        let unassigned: Vec<&str> = report
+            .unassigned_high_priority
+            .iter()
+            .map(|s| s.spec_name.as_str())
+            .collect();
+        assert_eq!(unassigned, vec!["005-e", "006-f"]);
+    }
+
+    #[test]
+    fn test_completed_by_month_uses_completion_date() {
+        let mut done = spec("001-a", "complete", "medium", Some("alice"));
+        done.created_at = "2024-01-20T00:00:00Z".parse().ok();
+
+        let report = analyze_assignees(
+            &[done],
+            &AssigneeAliases::default(),
+            &TokenBudget::default(),
+        );
+
+        let months: Vec<&str> = report.assignees[0]
+            .completed_by_month
+            .iter()
+            .map(|m| m.month.as_str())
+            .collect();
+        assert_eq!(months, vec!["2024-03"]);
+    }
+}
@@ -0,0 +1,318 @@
+//! Code activity per spec
+//!
+//! Links local git commits and branches to the specs they mention. A commit
+//! mentions a spec when its message contains the spec's name or number
+//! (`spec 042`, `LS-042`); a branch when its name contains the spec's name or
+//! its last segment starts with the number (`feature/042-auth`).
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::specs::git::{BranchHead, CommitMessage, FileCommit};
+use crate::specs::reader::Spec;
+use crate::specs::traceability::mentioned_numbers;
+
+/// What happened in an activity event
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ActivityKind {
+    Commit,
+    Branch,
+}
+
+/// A commit or branch mentioning a spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityEvent {
+    pub kind: ActivityKind,
+    /// Commit date, or the date of the branch's tip
+    pub at: DateTime<Utc>,
+    /// Commit hash, or the hash of the branch's tip
+    pub hash: String,
+    /// Commit subject, or branch name
+    pub summary: String,
+    /// Whether a branch is merged into the default branch; `None` for commits
+    pub merged: Option<bool>,
+}
+
+/// Commits and branches mentioning a spec, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeActivity {
+    pub spec_name: String,
+    pub events: Vec<ActivityEvent>,
+    pub last_code_activity: Option<DateTime<Utc>>,
+}
+
+/// Commit messages, local branches and spec file commits of a project's
+/// repository
+#[derive(Debug, Clone, Default)]
+pub struct GitActivity {
+    pub commits: Vec<CommitMessage>,
+    pub branches: Vec<BranchHead>,
+    /// Commits touching the specs directory, for staleness
+    pub spec_commits: Vec<FileCommit>,
+}
+
+/// A project's git activity, prepared for matching against specs
+///
+/// Each spec is matched against the log once; later lookups reuse the result.
+#[derive(Default)]
+pub struct ProjectActivity {
+    pub git: GitActivity,
+    /// Lowercased message and mentioned numbers of each commit
+    messages: Vec<(String, Vec<i32>)>,
+    /// Lowercased name and mentioned numbers of each branch
+    branch_names: Vec<(String, Vec<i32>)>,
+    matched: Mutex<HashMap<String, CodeActivity>>,
+}
+
+impl ProjectActivity {
+    pub fn new(git: GitActivity) -> Self {
+        let messages = git
+            .commits
+            .iter()
+            .map(|c| {
+                let message = format!("{}\n{}", c.subject, c.body);
+                (message.to_lowercase(), mentioned_numbers(&message))
+            })
+            .collect();
+        let branch_names = git
+            .branches
+            .iter()
+            .map(|b| (b.name.to_lowercase(), mentioned_numbers(&b.name)))
+            .collect();
+
+        Self {
+            git,
+            messages,
+            branch_names,
+            matched: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build the activity timeline of each spec, in the order of `specs`
+    pub fn code_activity(&self, specs: &[Spec]) -> Vec<CodeActivity> {
+        let mut matched = self.matched.lock();
+        specs
+            .iter()
+            .map(|spec| {
+                matched
+                    .entry(spec.spec_name.clone())
+                    .or_insert_with(|| self.match_spec(spec))
+                    .clone()
+            })
+            .collect()
+    }
+
+    fn match_spec(&self, spec: &Spec) -> CodeActivity {
+        let name = spec.spec_name.to_lowercase();
+        let mentions_number = |numbers: &[i32]| {
+            spec.spec_number
+                .map(|n| numbers.contains(&n))
+                .unwrap_or(false)
+        };
+
+        let mut events: Vec<ActivityEvent> = self
+            .git
+            .commits
+            .iter()
+            .zip(&self.messages)
+            .filter(|(_, (message, numbers))| mentions_number(numbers) || message.contains(&name))
+            .map(|(commit, _)| ActivityEvent {
+                kind: ActivityKind::Commit,
+                at: commit.at,
+                hash: commit.hash.clone(),
+                summary: commit.subject.clone(),
+                merged: None,
+            })
+            .collect();
+
+        events.extend(
+            self.git
+                .branches
+                .iter()
+                .zip(&self.branch_names)
+                .filter(|(branch, (lower, numbers))| {
+                    lower.contains(&name)
+                        || mentions_number(numbers)
+                        || (spec.spec_number.is_some()
+                            && branch_number(&branch.name) == spec.spec_number)
+                })
+                .map(|(branch, _)| ActivityEvent {
+                    kind: ActivityKind::Branch,
+                    at: branch.at,
+                    hash: branch.hash.clone(),
+                    summary: branch.name.clone(),
+                    merged: Some(branch.merged),
+                }),
+        );
+        events.sort_by_key(|e| Reverse(e.at));
+
+        CodeActivity {
+            spec_name: spec.spec_name.clone(),
+            last_code_activity: events.first().map(|e| e.at),
+            events,
+        }
+    }
+}
+
+/// Git activity keyed by project id, tagged with a fingerprint of HEAD and
+/// the local branches
+///
+/// Spec lists show each spec's last code activity; the log is only reread
+/// when a commit is made or a branch moves. Clones share the same entries, so
+/// the cache can be handed to blocking tasks.
+#[derive(Clone, Default)]
+pub struct GitActivityCache {
+    entries: Arc<Mutex<HashMap<String, FingerprintedActivity>>>,
+}
+
+/// Activity and the refs fingerprint it was read at
+type FingerprintedActivity = (u64, Arc<ProjectActivity>);
+
+impl GitActivityCache {
+    /// Return the cached activity when the fingerprint matches, else load it
+    pub fn get_or_load<F>(
+        &self,
+        project_id: &str,
+        fingerprint: u64,
+        load: F,
+    ) -> Arc<ProjectActivity>
+    where
+        F: FnOnce() -> GitActivity,
+    {
+        if let Some((cached, activity)) = self.entries.lock().get(project_id) {
+            if *cached == fingerprint {
+                return Arc::clone(activity);
+            }
+        }
+
+        let activity = Arc::new(ProjectActivity::new(load()));
+        self.entries
+            .lock()
+            .insert(project_id.to_string(), (fingerprint, Arc::clone(&activity)));
+        activity
+    }
+}
+
+/// Number a branch's last segment starts with (`feature/042-auth` → 42)
+fn branch_number(branch: &str) -> Option<i32> {
+    let segment = branch.rsplit('/').next()?;
+    let digits: String = segment.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let rest = &segment[digits.len()..];
+    if rest.is_empty() || rest.starts_with('-') {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::reader::SpecBuilder;
+
+    fn at(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn spec(name: &str) -> Spec {
+        SpecBuilder::new(name).status("in-progress").build()
+    }
+
+    fn commit(hash: &str, date: &str, subject: &str, body: &str) -> CommitMessage {
+        CommitMessage {
+            hash: hash.to_string(),
+            at: at(date),
+            subject: subject.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    fn branch(name: &str, date: &str, merged: bool) -> BranchHead {
+        BranchHead {
+            name: name.to_string(),
+            hash: format!("tip-{}", name),
+            at: at(date),
+            merged,
+        }
+    }
+
+    #[test]
+    fn test_code_activity() {
+        let specs = vec![spec("042-auth"), spec("043-search")];
+        let commits = vec![
+            commit(
+                "c3",
+                "2024-03-05T09:00:00Z",
+                "Wire up login",
+                "Part of LS-042",
+            ),
+            commit("c2", "2024-03-03T09:00:00Z", "Update 042-auth README", ""),
+            commit("c1", "2024-03-01T09:00:00Z", "Fix 42 flaky tests", ""),
+        ];
+        let branches = vec![
+            branch("feature/042-auth", "2024-03-06T09:00:00Z", false),
+            branch("43-search-spike", "2024-02-01T09:00:00Z", true),
+            branch("release/1.42", "2024-03-07T09:00:00Z", false),
+        ];
+
+        let activity = ProjectActivity::new(GitActivity {
+            commits,
+            branches,
+            spec_commits: Vec::new(),
+        })
+        .code_activity(&specs);
+
+        let auth: Vec<(&str, Option<bool>)> = activity[0]
+            .events
+            .iter()
+            .map(|e| (e.hash.as_str(), e.merged))
+            .collect();
+        assert_eq!(
+            auth,
+            vec![
+                ("tip-feature/042-auth", Some(false)),
+                ("c3", None),
+                ("c2", None)
+            ]
+        );
+        assert_eq!(
+            activity[0].last_code_activity,
+            Some(at("2024-03-06T09:00:00Z"))
+        );
+
+        assert_eq!(activity[1].events.len(), 1);
+        assert_eq!(activity[1].events[0].kind, ActivityKind::Branch);
+        assert_eq!(activity[1].events[0].merged, Some(true));
+    }
+
+    #[test]
+    fn test_git_activity_cache() {
+        let cache = GitActivityCache::default();
+        let load = |hash: &str| GitActivity {
+            commits: vec![commit(hash, "2024-03-01T09:00:00Z", "Start spec 042", "")],
+            branches: Vec::new(),
+            spec_commits: Vec::new(),
+        };
+
+        let first = cache.get_or_load("project", 1, || load("c1"));
+        let cached = cache.get_or_load("project", 1, || panic!("reloaded unchanged refs"));
+        assert!(Arc::ptr_eq(&first, &cached));
+
+        let moved = cache.get_or_load("project", 2, || load("c2"));
+        assert_eq!(moved.git.commits[0].hash, "c2");
+
+        let specs = vec![spec("042-auth")];
+        let matched = moved.code_activity(&specs);
+        assert_eq!(matched[0].events[0].hash, "c2");
+        assert_eq!(moved.code_activity(&specs)[0].events.len(), 1);
+    }
+}
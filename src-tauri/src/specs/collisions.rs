@@ -0,0 +1,186 @@
+//! Spec number and slug collisions
+//!
+//! Two folders can end up with the same number (`042-auth` and `042-billing`
+//! after merging branches), which makes lookups by number ambiguous. Near
+//! identical slugs under different numbers usually mean a spec was created
+//! twice.
+
+use std::collections::BTreeMap;
+
+use crate::specs::reader::Spec;
+
+/// Minimum normalized Levenshtein similarity for two slugs to be flagged
+const SLUG_SIMILARITY_THRESHOLD: f64 = 0.9;
+
+/// A suggested new number for a spec whose number is taken
+#[derive(Debug, Clone, PartialEq)]
+pub struct Renumbering {
+    pub spec_name: String,
+    pub number: i32,
+    /// Folder name with the new number
+    pub new_name: String,
+}
+
+/// Specs sharing a number
+#[derive(Debug, Clone)]
+pub struct NumberCollision {
+    pub number: i32,
+    /// In name order; the first keeps the number
+    pub spec_names: Vec<String>,
+    /// New numbers for every spec but the first
+    pub renumber: Vec<Renumbering>,
+}
+
+/// Specs with nearly identical slugs
+#[derive(Debug, Clone)]
+pub struct SlugCollision {
+    pub first: String,
+    pub second: String,
+}
+
+/// Spec name without its number prefix (`012-user-auth` → `user-auth`)
+pub fn spec_slug(name: &str) -> &str {
+    match name.split_once('-') {
+        Some((number, slug)) if number.chars().all(|c| c.is_ascii_digit()) => slug,
+        _ => name,
+    }
+}
+
+/// Lowest number above every number in use
+pub fn next_free_number(specs: &[Spec]) -> i32 {
+    specs
+        .iter()
+        .filter_map(|s| s.spec_number)
+        .max()
+        .map(|n| n + 1)
+        .unwrap_or(1)
+}
+
+/// Numbers used by more than one spec, with renumbering suggestions
+///
+/// Suggested numbers are handed out from the next free number, so applying
+/// all of them never creates a new collision.
+pub fn find_duplicate_numbers(specs: &[Spec]) -> Vec<NumberCollision> {
+    let mut by_number: BTreeMap<i32, Vec<&str>> = BTreeMap::new();
+    for spec in specs {
+        if let Some(number) = spec.spec_number {
+            by_number
+                .entry(number)
+                .or_default()
+                .push(spec.spec_name.as_str());
+        }
+    }
+
+    let mut next = next_free_number(specs);
+    by_number
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .map(|(number, mut names)| {
+            names.sort_unstable();
+            let renumber = names[1..]
+                .iter()
+                .map(|name| {
+                    let renumbering = Renumbering {
+                        spec_name: name.to_string(),
+                        number: next,
+                        new_name: format!("{:03}-{}", next, spec_slug(name)),
+                    };
+                    next += 1;
+                    renumbering
+                })
+                .collect();
+            NumberCollision {
+                number,
+                spec_names: names.into_iter().map(String::from).collect(),
+                renumber,
+            }
+        })
+        .collect()
+}
+
+/// Pairs of specs whose slugs are identical or nearly so
+pub fn find_similar_slugs(specs: &[Spec]) -> Vec<SlugCollision> {
+    let mut collisions = Vec::new();
+    for (i, a) in specs.iter().enumerate() {
+        for b in &specs[i + 1..] {
+            let (slug_a, slug_b) = (spec_slug(&a.spec_name), spec_slug(&b.spec_name));
+            if slug_a.is_empty() || slug_b.is_empty() || a.spec_name == b.spec_name {
+                continue;
+            }
+            if strsim::normalized_levenshtein(slug_a, slug_b) >= SLUG_SIMILARITY_THRESHOLD {
+                collisions.push(SlugCollision {
+                    first: a.spec_name.clone(),
+                    second: b.spec_name.clone(),
+                });
+            }
+        }
+    }
+    collisions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::reader::SpecBuilder;
+
+    fn spec(name: &str) -> Spec {
+        SpecBuilder::new(name).build()
+    }
+
+    #[test]
+    fn test_duplicate_numbers() {
+        let specs: Vec<Spec> = [
+            "042-billing",
+            "042-auth",
+            "043-search",
+            "043-search-ui",
+            "056-export",
+        ]
+        .iter()
+        .map(|n| spec(n))
+        .collect();
+
+        assert_eq!(next_free_number(&specs), 57);
+        let collisions = find_duplicate_numbers(&specs);
+        assert_eq!(collisions.len(), 2);
+        assert_eq!(collisions[0].spec_names, vec!["042-auth", "042-billing"]);
+        assert_eq!(
+            collisions[0].renumber,
+            vec![Renumbering {
+                spec_name: "042-billing".to_string(),
+                number: 57,
+                new_name: "057-billing".to_string(),
+            }]
+        );
+        assert_eq!(collisions[1].renumber[0].new_name, "058-search-ui");
+    }
+
+    #[test]
+    fn test_similar_slugs() {
+        let specs: Vec<Spec> = [
+            "012-user-auth",
+            "051-user-auth",
+            "052-user-authn",
+            "060-export",
+        ]
+        .iter()
+        .map(|n| spec(n))
+        .collect();
+
+        let collisions = find_similar_slugs(&specs);
+        let pairs: Vec<(&str, &str)> = collisions
+            .iter()
+            .map(|c| (c.first.as_str(), c.second.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("012-user-auth", "051-user-auth"),
+                ("012-user-auth", "052-user-authn"),
+                ("051-user-auth", "052-user-authn"),
+            ]
+        );
+        assert_eq!(spec_slug("012-user-auth"), "user-auth");
+        assert_eq!(spec_slug("auth"), "auth");
+    }
+}
@@ -3,18 +3,56 @@
 //! These commands expose the Rust spec operations library to the UI frontend,
 //! replacing legacy HTTP API routes.
 
-use std::path::Path;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tauri::State;
 
 use crate::specs::{
     constants::VALID_STATUSES,
-    reader::{LightweightSpec, Spec, SpecReader},
+    git,
+    reader::{LightweightSpec, LoadDiagnostic, Spec, SpecReader},
     stats::{calculate_stats, StatsResult},
-    dependencies::{build_dependency_graph, get_spec_dependencies, DependencyGraph, SpecDependencies},
-    validation::{validate_all_specs, validate_spec, ValidationResult},
+    flow::{calculate_flow_metrics, FlowMetrics, SpecTimeline},
+    forecast::{forecast_completion, CompletionForecast, DEFAULT_HISTORY_WEEKS},
+    history::{backfill_snapshots, filter_range, StatsHistoryStore, StatsSnapshot},
+    portfolio::{aggregate_portfolio, cache_fingerprint, summarize_project, PortfolioStats},
+    dependencies::{
+        layout::{compute_layout, LayoutGrouping, LayoutOptions},
+        build_dependency_graph, find_external_dependents, get_spec_dependencies, get_spec_impact,
+        resolve_external_specs, DependencyGraph, ExternalProject, ExternalSpecs, ImpactDirection,
+        SpecDependencies, SpecImpact,
+    },
+    settings::ProjectSettings,
+    staleness::{detect_stale_specs, ActivityIndex, StalenessReport},
+    assignees::{analyze_assignees, AssigneeAliases, AssigneeReport},
+    frontmatter::{
+        parse_frontmatter, parse_source_map, set_frontmatter_field, set_frontmatter_list,
+    },
+    tags::{analyze_tags, replace_tags, TagAnalytics},
+    fixes::{fix_spec, FixReport},
+    schema::FrontmatterSchema,
+    graph_export::{export_graph, filter_graph, GraphExportFilter, GraphExportFormat},
+    tokens::{SpecTokenCount, TokenBudget},
+    traceability::{trace_specs, TraceabilityReport},
+    code_activity::{CodeActivity, GitActivity, GitActivityCache, ProjectActivity},
+    tasks::toggle_task,
+    validation::{
+        apply_rule_settings, list_rules, load_failure_result,
+        rules::{missing_sections, RuleInfo},
+        report::{export_report, write_report, ReportEntry, ValidationReportFormat},
+        validate_all_specs, validate_spec, ValidationOptions, ValidationResult,
+    },
 };
+use leanspec_core::sessions::runner::RunnerRegistry;
+use crate::projects::DesktopProject;
 use crate::state::DesktopState;
 
+/// Commits read from a project's log for code activity, staleness and
+/// traceability; older commits are not considered
+const GIT_LOG_LIMIT: usize = 5_000;
+
 /// Get all specs for a project
 #[tauri::command]
 pub async fn get_specs(
@@ -29,7 +67,7 @@ pub async fn get_specs(
     let reader = SpecReader::new(&project.specs_dir, &project_id);
     let specs = reader.load_all();
     
-    Ok(specs.iter().map(LightweightSpec::from).collect())
+    lightweight_specs(&state, project, specs).await
 }
 
 /// Get a single spec by ID or number
@@ -45,9 +83,7 @@ pub async fn get_spec_detail(
         .ok_or_else(|| "Project not found".to_string())?;
 
     let reader = SpecReader::new(&project.specs_dir, &project_id);
-    reader
-        .load_spec(&spec_id)
-        .ok_or_else(|| format!("Spec '{}' not found", spec_id))
+    reader.load_spec(&spec_id)
 }
 
 /// Get project statistics
@@ -63,15 +99,219 @@ pub async fn get_project_stats(
 
     let reader = SpecReader::new(&project.specs_dir, &project_id);
     let specs = reader.load_all();
-    
     Ok(calculate_stats(&specs))
 }
 
+/// Record today's project stats for trend charts
+///
+/// Only the first snapshot of each day is kept, so this can be called on
+/// every stats refresh. Returns whether a snapshot was written.
+#[tauri::command]
+pub async fn record_stats_snapshot(
+    state: State<'_, DesktopState>,
+    project_id: String,
+) -> Result<bool, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+    let store = StatsHistoryStore::open_default()
+        .ok_or_else(|| "Unable to locate config directory".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    let stats = calculate_stats(&reader.load_all());
+    let snapshot = StatsSnapshot::from_stats(Utc::now().date_naive(), &stats);
+    store.record_daily(&project_id, snapshot)
+}
+
+/// Get daily stats snapshots for burnup and completion-rate charts
+///
+/// Live snapshots come from `record_stats_snapshot`. With `backfill`, days
+/// missing from the history are first reconstructed from the git history of
+/// the specs directory.
+#[tauri::command]
+pub async fn get_stats_history(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    from: Option<String>,
+    to: Option<String>,
+    backfill: Option<bool>,
+) -> Result<Vec<StatsSnapshot>, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let from = from.as_deref().map(|v| parse_date_param(v, false)).transpose()?;
+    let to = to.as_deref().map(|v| parse_date_param(v, true)).transpose()?;
+    let store = StatsHistoryStore::open_default()
+        .ok_or_else(|| "Unable to locate config directory".to_string())?;
+
+    // Reading the whole specs history with patches is slow on large repositories
+    tauri::async_runtime::spawn_blocking(move || {
+        if backfill.unwrap_or(false) {
+            let changes = git::frontmatter_history(Path::new(&project.specs_dir));
+            store.merge_backfill(&project_id, backfill_snapshots(&changes))?;
+        }
+
+        Ok(filter_range(store.load(&project_id)?, from, to))
+    })
+    .await
+    .map_err(|e| format!("Reading stats history failed: {}", e))?
+}
+
+/// Forecast when the specs matching a query and/or tag will all be complete
+///
+/// Runs a Monte Carlo simulation over the project's weekly throughput for the
+/// last `history_weeks` weeks. Without a query or tag, all specs are included.
+#[tauri::command]
+pub async fn get_completion_forecast(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    query: Option<String>,
+    tag: Option<String>,
+    history_weeks: Option<usize>,
+    seed: Option<u64>,
+) -> Result<CompletionForecast, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    let specs = reader.load_all();
+
+    let selected: Vec<&Spec> = specs
+        .iter()
+        .filter(|s| query.as_deref().map(|q| s.matches_query(q)).unwrap_or(true))
+        .filter(|s| tag.as_ref().map(|t| s.tags.contains(t)).unwrap_or(true))
+        .collect();
+    let remaining = selected
+        .iter()
+        .filter(|s| s.status != "complete" && s.status != "archived")
+        .count();
+
+    // Completion dates from `completed_at`, falling back to transitions
+    let completions: Vec<DateTime<Utc>> = specs
+        .iter()
+        .filter_map(|spec| {
+            spec.completed_at
+                .or_else(|| SpecTimeline::for_spec(spec, Vec::new).first_reached("complete"))
+        })
+        .collect();
+
+    Ok(forecast_completion(
+        selected.len(),
+        remaining,
+        &completions,
+        history_weeks.unwrap_or(DEFAULT_HISTORY_WEEKS),
+        Utc::now(),
+        seed,
+    ))
+}
+
+/// Get combined stats across all registered projects, or a subset
+///
+/// Projects whose spec files and git refs are unchanged since the last call
+/// are served from the portfolio cache instead of being rescanned. Stale
+/// counts use the same detection as `get_stale_specs`.
+#[tauri::command]
+pub async fn get_portfolio_stats(
+    state: State<'_, DesktopState>,
+    project_ids: Option<Vec<String>>,
+) -> Result<PortfolioStats, String> {
+    let projects: Vec<_> = match &project_ids {
+        Some(ids) => ids
+            .iter()
+            .map(|id| {
+                state
+                    .project_store
+                    .find(id)
+                    .ok_or_else(|| format!("Project '{}' not found", id))
+            })
+            .collect::<Result<_, _>>()?,
+        None => state.project_store.all(),
+    };
+
+    let portfolio_cache = state.portfolio_cache.clone();
+    let git_cache = state.git_activity_cache.clone();
+    let entries = tauri::async_runtime::spawn_blocking(move || {
+        let today = Utc::now().date_naive();
+        projects
+            .iter()
+            .map(|project| {
+                let reader = SpecReader::new(&project.specs_dir, &project.id);
+                let stale_after_days = ProjectSettings::load(&project.path)?
+                    .staleness
+                    .in_progress_days;
+                let fingerprint = cache_fingerprint(
+                    reader.fingerprint(),
+                    git::refs_fingerprint(Path::new(&project.path)),
+                    stale_after_days,
+                    today,
+                );
+                portfolio_cache.get_or_compute(&project.id, fingerprint, || {
+                    let specs = reader.load_all();
+                    let staleness = project_staleness(&git_cache, project, &specs)?;
+                    Ok(summarize_project(&project.id, &project.name, &specs, &staleness))
+                })
+            })
+            .collect::<Result<_, String>>()
+    })
+    .await
+    .map_err(|e| format!("Portfolio stats failed: {}", e))??;
+
+    Ok(aggregate_portfolio(entries))
+}
+
+/// Get lead time, cycle time, throughput and time-in-status metrics
+///
+/// `from` and `to` accept RFC 3339 timestamps or `YYYY-MM-DD` dates.
+#[tauri::command]
+pub async fn get_flow_metrics(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<FlowMetrics, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let from = from.as_deref().map(|v| parse_date_param(v, false)).transpose()?;
+    let to = to.as_deref().map(|v| parse_date_param(v, true)).transpose()?;
+
+    // One git log pass covers every spec without recorded transitions
+    tauri::async_runtime::spawn_blocking(move || {
+        let reader = SpecReader::new(&project.specs_dir, &project_id);
+        let specs = reader.load_all();
+        let histories = git::status_histories(Path::new(&project.specs_dir));
+
+        let timelines: Vec<SpecTimeline> = specs
+            .iter()
+            .map(|spec| {
+                SpecTimeline::for_spec(spec, || {
+                    histories.get(&spec.spec_name).cloned().unwrap_or_default()
+                })
+            })
+            .collect();
+
+        calculate_flow_metrics(&timelines, from, to, Utc::now())
+    })
+    .await
+    .map_err(|e| format!("Flow metrics failed: {}", e))
+}
+
 /// Get dependency graph for visualization
+///
+/// The layered layout is only computed when `layout` asks for it, grouped as
+/// given.
 #[tauri::command]
 pub async fn get_dependency_graph(
     state: State<'_, DesktopState>,
     project_id: String,
+    layout: Option<LayoutGrouping>,
 ) -> Result<DependencyGraph, String> {
     let project = state
         .project_store
@@ -80,8 +320,54 @@ pub async fn get_dependency_graph(
 
     let reader = SpecReader::new(&project.specs_dir, &project_id);
     let specs = reader.load_all();
+    let external = load_external_specs(&state, &specs);
     
-    Ok(build_dependency_graph(&specs))
+    let mut graph = build_dependency_graph(&specs, &external);
+    if let Some(group_by) = layout {
+        let options = LayoutOptions {
+            group_by,
+            ..Default::default()
+        };
+        graph.layout = Some(compute_layout(&graph, &options));
+    }
+    Ok(graph)
+}
+
+/// Export the dependency graph as DOT, Mermaid or GraphML
+#[tauri::command]
+pub async fn export_dependency_graph(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    format: GraphExportFormat,
+    status: Option<Vec<String>>,
+    tag: Option<String>,
+    root: Option<String>,
+    with_positions: Option<bool>,
+) -> Result<String, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    let specs = reader.load_all();
+
+    let filter = GraphExportFilter {
+        status: status.unwrap_or_default(),
+        tag,
+        root,
+    };
+    let external = load_external_specs(&state, &specs);
+    let mut graph = filter_graph(
+        &build_dependency_graph(&specs, &external),
+        &filter,
+        &specs,
+    )?;
+    if with_positions.unwrap_or(false) {
+        graph.layout = Some(compute_layout(&graph, &LayoutOptions::default()));
+    }
+
+    Ok(export_graph(&graph, format))
 }
 
 /// Get dependencies for a specific spec
@@ -103,8 +389,62 @@ pub async fn get_spec_dependencies_cmd(
         .iter()
         .find(|s| s.spec_name == spec_id || s.id == spec_id || s.id == format!("fs-{}", spec_id))
         .ok_or_else(|| format!("Spec '{}' not found", spec_id))?;
-    
-    Ok(get_spec_dependencies(spec, &specs))
+
+    let external = load_external_specs(&state, &specs);
+    let mut dependencies = get_spec_dependencies(spec, &specs, &external);
+
+    // Specs in other projects that depend on this one via `project:spec`;
+    // projects are only rescanned when their spec files change
+    let other_projects: Vec<(ExternalProject, Vec<Spec>)> = state
+        .project_store
+        .all()
+        .into_iter()
+        .filter(|p| p.id != project.id)
+        .map(|p| {
+            let reader = SpecReader::new(&p.specs_dir, &p.id);
+            let specs = state
+                .external_reference_cache
+                .get_or_load(&p.id, reader.fingerprint(), || reader.load_all());
+            (ExternalProject { id: p.id, name: p.name }, specs)
+        })
+        .collect();
+    dependencies.required_by.extend(find_external_dependents(
+        spec,
+        &[&project.id, &project.name],
+        &other_projects,
+    ));
+
+    Ok(dependencies)
+}
+
+/// Get the transitive upstream or downstream impact of a spec
+#[tauri::command]
+pub async fn get_spec_impact_cmd(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    spec_id: String,
+    direction: Option<ImpactDirection>,
+    max_depth: Option<usize>,
+) -> Result<SpecImpact, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    let specs = reader.load_all();
+
+    let spec = specs
+        .iter()
+        .find(|s| s.spec_name == spec_id || s.id == spec_id || s.id == format!("fs-{}", spec_id))
+        .ok_or_else(|| format!("Spec '{}' not found", spec_id))?;
+
+    Ok(get_spec_impact(
+        spec,
+        &specs,
+        direction.unwrap_or_default(),
+        max_depth,
+    ))
 }
 
 /// Search specs by query
@@ -122,7 +462,7 @@ pub async fn search_specs(
     let reader = SpecReader::new(&project.specs_dir, &project_id);
     let specs = reader.search(&query);
     
-    Ok(specs.iter().map(LightweightSpec::from).collect())
+    lightweight_specs(&state, project, specs).await
 }
 
 /// Get specs by status
@@ -140,7 +480,7 @@ pub async fn get_specs_by_status(
     let reader = SpecReader::new(&project.specs_dir, &project_id);
     let specs = reader.get_by_status(&status);
     
-    Ok(specs.iter().map(LightweightSpec::from).collect())
+    lightweight_specs(&state, project, specs).await
 }
 
 /// Get all unique tags
@@ -158,6 +498,64 @@ pub async fn get_all_tags(
     Ok(reader.get_all_tags())
 }
 
+/// Get per-tag counts, co-occurrence, near-duplicates and orphan tags
+#[tauri::command]
+pub async fn get_tag_analytics(
+    state: State<'_, DesktopState>,
+    project_id: String,
+) -> Result<TagAnalytics, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    Ok(analyze_tags(&reader.load_all()))
+}
+
+/// Rename a tag on every spec that uses it
+///
+/// Returns the names of the specs that were rewritten.
+#[tauri::command]
+pub async fn rename_tag(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    from: String,
+    to: String,
+) -> Result<Vec<String>, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    if from == to {
+        return Err("New tag name is the same as the old one".to_string());
+    }
+    rewrite_project_tags(&project, &[from], &to)
+}
+
+/// Merge several tags into one on every affected spec
+///
+/// Returns the names of the specs that were rewritten.
+#[tauri::command]
+pub async fn merge_tags(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    sources: Vec<String>,
+    target: String,
+) -> Result<Vec<String>, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let sources: Vec<String> = sources.into_iter().filter(|s| *s != target).collect();
+    if sources.is_empty() {
+        return Err("No tags to merge".to_string());
+    }
+    rewrite_project_tags(&project, &sources, &target)
+}
+
 /// Validate a single spec
 #[tauri::command]
 pub async fn validate_spec_cmd(
@@ -171,11 +569,10 @@ pub async fn validate_spec_cmd(
         .ok_or_else(|| "Project not found".to_string())?;
 
     let reader = SpecReader::new(&project.specs_dir, &project_id);
-    let spec = reader
-        .load_spec(&spec_id)
-        .ok_or_else(|| format!("Spec '{}' not found", spec_id))?;
+    let spec = reader.load_spec(&spec_id)?;
     
-    Ok(validate_spec(&spec))
+    let options = project_validation_options(&state, &project).await?;
+    Ok(validate_spec(&spec, &options))
 }
 
 /// Validate all specs in a project
@@ -189,10 +586,321 @@ pub async fn validate_all_specs_cmd(
         .find(&project_id)
         .ok_or_else(|| "Project not found".to_string())?;
 
+    let (entries, _) = validate_project(&state, &project).await?;
+    Ok(entries.into_iter().map(|entry| entry.result).collect())
+}
+
+/// Export project validation results as SARIF 2.1.0 or JUnit XML
+///
+/// Returns the report; when `output_path` is given it is also written there,
+/// relative to the project root. Paths outside the project are refused.
+#[tauri::command]
+pub async fn export_validation_report(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    format: ValidationReportFormat,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let (entries, options) = validate_project(&state, &project).await?;
+    let report = export_report(&entries, &options.settings, format);
+
+    if let Some(output_path) = output_path {
+        write_report(Path::new(&project.path), &output_path, &report)?;
+    }
+
+    Ok(report)
+}
+
+/// List spec directories that failed to load, with the reason and YAML error position
+#[tauri::command]
+pub async fn get_load_diagnostics(
+    state: State<'_, DesktopState>,
+    project_id: String,
+) -> Result<Vec<LoadDiagnostic>, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
     let reader = SpecReader::new(&project.specs_dir, &project_id);
+    Ok(reader.load_all_with_diagnostics().1)
+}
+
+/// List validation rules with the project's enable/severity/params overrides
+#[tauri::command]
+pub async fn get_validation_rules(
+    state: State<'_, DesktopState>,
+    project_id: String,
+) -> Result<Vec<RuleInfo>, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let settings = ProjectSettings::load(&project.path)?;
+    Ok(list_rules(&settings.validation))
+}
+
+/// Fix validation issues that have a safe automatic fix
+///
+/// Fixes one spec, or every spec when `spec_id` is omitted. Returns a diff and
+/// a content hash per spec; files are only written when `apply` is set.
+/// Applying takes the previewed hashes by spec name and writes only those
+/// specs, refusing when none are given or any of them would no longer get the
+/// previewed change.
+#[tauri::command]
+pub async fn fix_spec_issues(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    spec_id: Option<String>,
+    apply: Option<bool>,
+    expected_hashes: Option<HashMap<String, String>>,
+) -> Result<FixReport, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    let target = match &spec_id {
+        Some(spec_id) => Some(reader.load_spec(spec_id)?.spec_name),
+        None => None,
+    };
+
     let specs = reader.load_all();
-    
-    Ok(validate_all_specs(&specs))
+    let external = load_external_specs(&state, &specs);
+    let options = project_validation_options(&state, &project).await?;
+    let results = validate_all_specs(&specs, &external, &options);
+
+    let mut fixed = Vec::new();
+    let mut rewrites = Vec::new();
+    for (spec, result) in specs.iter().zip(&results) {
+        if target.as_ref().is_some_and(|name| *name != spec.spec_name) {
+            continue;
+        }
+        let path = spec_file_path(&project, spec);
+        let original = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", spec.spec_name, e))?;
+        if let Some(fix) = fix_spec(spec, &original, result, &specs, &options.settings) {
+            rewrites.push((spec.spec_name.clone(), path, original, fix.content.clone()));
+            fixed.push(fix);
+        }
+    }
+
+    let apply = apply.unwrap_or(false);
+    if apply {
+        let expected = expected_hashes
+            .filter(|hashes| !hashes.is_empty())
+            .ok_or_else(|| {
+                "Preview the fixes and pass their content hashes to apply them".to_string()
+            })?;
+        for (spec_name, hash) in &expected {
+            if !fixed
+                .iter()
+                .any(|f| f.spec_name == *spec_name && f.content_hash == *hash)
+            {
+                return Err(format!(
+                    "Spec '{}' changed since the fixes were previewed. Preview them again.",
+                    spec_name
+                ));
+            }
+        }
+        fixed.retain(|f| expected.contains_key(&f.spec_name));
+        rewrites.retain(|(spec_name, ..)| expected.contains_key(spec_name));
+        write_all_or_restore(&rewrites)?;
+    }
+
+    Ok(FixReport {
+        applied: apply,
+        specs: fixed,
+    })
+}
+
+/// Get the project's frontmatter JSON Schema, if it has one
+///
+/// Lets the UI render typed editors for custom frontmatter fields. Fails with
+/// the file path when the schema is invalid.
+#[tauri::command]
+pub async fn get_frontmatter_schema(
+    state: State<'_, DesktopState>,
+    project_id: String,
+) -> Result<Option<serde_json::Value>, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    Ok(FrontmatterSchema::load(&project.path)?.map(|schema| schema.schema))
+}
+
+/// Find stale in-progress, neglected planned, untouched draft specs and
+/// specs with inactive assignees, using the project's thresholds
+#[tauri::command]
+pub async fn get_stale_specs(
+    state: State<'_, DesktopState>,
+    project_id: String,
+) -> Result<StalenessReport, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    let specs = reader.load_all();
+
+    detect_project_staleness(&state, &project, specs).await
+}
+
+/// Get workload per assignee and unassigned high-priority specs
+#[tauri::command]
+pub async fn get_assignee_stats(
+    state: State<'_, DesktopState>,
+    project_id: String,
+) -> Result<AssigneeReport, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    let specs = reader.load_all();
+    let aliases = AssigneeAliases::load(&project.path)?;
+    let settings = ProjectSettings::load(&project.path)?;
+    let budget = project_token_budget(&state, &project, &settings).await;
+
+    Ok(analyze_assignees(&specs, &aliases, &budget))
+}
+
+/// Count a spec's tokens for the model the project uses
+#[tauri::command]
+pub async fn get_spec_tokens(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    spec_id: String,
+) -> Result<SpecTokenCount, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    let spec = reader.load_spec(&spec_id)?;
+    let settings = ProjectSettings::load(&project.path)?;
+    let budget = project_token_budget(&state, &project, &settings).await;
+
+    Ok(SpecTokenCount {
+        spec_name: spec.spec_name,
+        tokens: budget.count(&spec.content_md),
+        budget,
+    })
+}
+
+/// Find the source files, lines and commits referencing each spec
+///
+/// Complete specs that no source file references are flagged as untraced.
+/// With `spec_id`, only that spec is reported.
+#[tauri::command]
+pub async fn get_spec_traceability(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    spec_id: Option<String>,
+) -> Result<TraceabilityReport, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    // Walking the whole tree and the git log is slow on large repositories
+    let cache = state.git_activity_cache.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let reader = SpecReader::new(&project.specs_dir, &project_id);
+        let specs = match spec_id {
+            Some(spec_id) => vec![reader.load_spec(&spec_id)?],
+            None => reader.load_all(),
+        };
+        let activity = project_git_activity(&cache, &project);
+
+        Ok(trace_specs(
+            &specs,
+            Path::new(&project.path),
+            Path::new(&project.specs_dir),
+            &activity.git.commits,
+        ))
+    })
+    .await
+    .map_err(|e| format!("Traceability scan failed: {}", e))?
+}
+
+/// Get the commits and branches mentioning a spec, newest first
+///
+/// Branches carry whether they are merged into the default branch.
+#[tauri::command]
+pub async fn get_spec_activity(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    spec_id: String,
+) -> Result<CodeActivity, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    let spec = reader.load_spec(&spec_id)?;
+
+    let cache = state.git_activity_cache.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        project_git_activity(&cache, &project)
+            .code_activity(std::slice::from_ref(&spec))
+            .pop()
+    })
+    .await
+    .map_err(|e| format!("Reading git activity failed: {}", e))?
+    .ok_or_else(|| format!("Spec '{}' not found", spec_id))
+}
+
+/// Check or uncheck the task list item on a 1-based line of a spec
+///
+/// `expected_content` is the spec content the UI shows; the toggle is refused
+/// when the file no longer matches it, so edits made elsewhere are not lost.
+#[tauri::command]
+pub async fn toggle_spec_task(
+    state: State<'_, DesktopState>,
+    project_id: String,
+    spec_id: String,
+    line: usize,
+    expected_content: String,
+) -> Result<Spec, String> {
+    let project = state
+        .project_store
+        .find(&project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let reader = SpecReader::new(&project.specs_dir, &project_id);
+    let spec = reader.load_spec(&spec_id)?;
+    let spec_path = spec_file_path(&project, &spec);
+
+    let content = std::fs::read_to_string(&spec_path)
+        .map_err(|e| format!("Failed to read spec file: {}", e))?;
+    if content != expected_content {
+        return Err(format!(
+            "Spec '{}' changed since it was read. Reload it and try again.",
+            spec.spec_name
+        ));
+    }
+
+    let updated = toggle_task(&content, line)?;
+    std::fs::write(&spec_path, &updated)
+        .map_err(|e| format!("Failed to write spec file: {}", e))?;
+
+    reader
+        .load_spec(&spec_id)
+        .map_err(|_| "Failed to reload spec".to_string())
 }
 
 /// Update spec status (writes to filesystem)
@@ -222,9 +930,7 @@ pub async fn update_spec_status(
         .ok_or_else(|| "Project not found".to_string())?;
 
     let reader = SpecReader::new(&project.specs_dir, &project_id);
-    let spec = reader
-        .load_spec(&spec_id)
-        .ok_or_else(|| format!("Spec '{}' not found", spec_id))?;
+    let spec = reader.load_spec(&spec_id)?;
 
     let skip_force = force.unwrap_or(false);
     if spec.status == "draft"
@@ -234,6 +940,21 @@ pub async fn update_spec_status(
         return Err("Cannot skip 'planned' stage. Use force to override.".to_string());
     }
 
+    // Sections the project requires before a spec can enter the new status
+    let validation = ProjectSettings::load(&project.path)?.validation;
+    if validation.is_enabled("missing-required-section") && !skip_force {
+        let source = parse_source_map(&spec.content_md);
+        let missing = missing_sections(&source, validation.required_sections_for(&new_status));
+        if !missing.is_empty() {
+            let sections: Vec<String> = missing.iter().map(|s| format!("## {}", s)).collect();
+            return Err(format!(
+                "Specs that are {} must have {}. Use force to override.",
+                new_status,
+                sections.join(", ")
+            ));
+        }
+    }
+
     // Read the spec file - construct proper path from specs_dir and spec_name
     // The file_path is relative like "specs/169-name/README.md"
     // We need to join specs_dir with the spec directory and README.md
@@ -245,11 +966,11 @@ pub async fn update_spec_status(
         .map_err(|e| format!("Failed to read spec file: {}", e))?;
 
     // Update status in frontmatter
-    let updated_content = update_frontmatter_field(&content, "status", &new_status)?;
+    let updated_content = set_frontmatter_field(&content, "status", &new_status)?;
     
     // Add transition record and update updated_at
     let now = Utc::now().to_rfc3339();
-    let updated_content = update_frontmatter_field(&updated_content, "updated_at", &format!("'{}'", now))?;
+    let updated_content = set_frontmatter_field(&updated_content, "updated_at", &format!("'{}'", now))?;
 
     // Write back
     fs::write(&spec_path, &updated_content)
@@ -258,45 +979,267 @@ pub async fn update_spec_status(
     // Reload and return updated spec
     reader
         .load_spec(&spec_id)
-        .ok_or_else(|| "Failed to reload spec after update".to_string())
+        .map_err(|_| "Failed to reload spec after update".to_string())
 }
 
-/// Helper to update a field in YAML frontmatter
-fn update_frontmatter_field(content: &str, field: &str, value: &str) -> Result<String, String> {
-    if !content.starts_with("---") {
-        return Err("No frontmatter found".to_string());
+/// Replace `sources` with `target` in the tags of every spec of a project
+///
+/// All files are rewritten in memory first so the project is never left
+/// half-renamed.
+fn rewrite_project_tags(
+    project: &DesktopProject,
+    sources: &[String],
+    target: &str,
+) -> Result<Vec<String>, String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err("Tag name cannot be empty".to_string());
     }
 
-    let rest = &content[3..];
-    let rest = rest.strip_prefix('\n').unwrap_or(rest);
-    
-    if let Some(end_pos) = rest.find("\n---") {
-        let yaml_content = &rest[..end_pos];
-        let markdown_content = &rest[end_pos + 4..];
-        
-        // Simple field replacement (works for simple values)
-        let field_pattern = format!("{}:", field);
-        let new_line = format!("{}: {}", field, value);
-        let mut lines: Vec<String> = yaml_content.lines().map(String::from).collect();
-        let mut found = false;
-        
-        for line in lines.iter_mut() {
-            if line.trim_start().starts_with(&field_pattern) {
-                *line = new_line.clone();
-                found = true;
-                break;
+    let specs = SpecReader::new(&project.specs_dir, &project.id).load_all();
+    let mut rewrites = Vec::new();
+    for spec in &specs {
+        let Some(tags) = replace_tags(&spec.tags, sources, target) else {
+            continue;
+        };
+        let path = spec_file_path(project, spec);
+        let original = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", spec.spec_name, e))?;
+        let updated = set_frontmatter_list(&original, "tags", &tags)
+            .map_err(|e| format!("Failed to update {}: {}", spec.spec_name, e))?;
+        if parse_frontmatter(&updated).0.tags != tags {
+            return Err(format!("Failed to update tags of {}", spec.spec_name));
+        }
+        rewrites.push((spec.spec_name.clone(), path, original, updated));
+    }
+
+    write_all_or_restore(&rewrites)?;
+    Ok(rewrites.into_iter().map(|(spec_name, ..)| spec_name).collect())
+}
+
+/// Absolute path of a spec's README.md
+fn spec_file_path(project: &DesktopProject, spec: &Spec) -> PathBuf {
+    let relative = spec.file_path.strip_prefix("specs/").unwrap_or(&spec.file_path);
+    Path::new(&project.specs_dir).join(relative)
+}
+
+/// Write `(spec name, path, original, updated)` rewrites, restoring the files
+/// already written if one fails
+fn write_all_or_restore(rewrites: &[(String, PathBuf, String, String)]) -> Result<(), String> {
+    for (index, (spec_name, path, _, updated)) in rewrites.iter().enumerate() {
+        if let Err(error) = std::fs::write(path, updated) {
+            for (_, path, original, _) in &rewrites[..index] {
+                let _ = std::fs::write(path, original);
             }
+            return Err(format!("Failed to write {}: {}", spec_name, error));
+        }
+    }
+    Ok(())
+}
+
+/// Validate every spec of a project, including staleness findings and spec
+/// directories that failed to load
+async fn validate_project(
+    state: &DesktopState,
+    project: &DesktopProject,
+) -> Result<(Vec<ReportEntry>, ValidationOptions), String> {
+    let reader = SpecReader::new(&project.specs_dir, &project.id);
+    let (specs, diagnostics) = reader.load_all_with_diagnostics();
+    let external = load_external_specs(state, &specs);
+    let options = project_validation_options(state, project).await?;
+    let mut results = validate_all_specs(&specs, &external, &options);
+
+    // Stale and neglected specs surface as warnings and info issues
+    for stale in detect_project_staleness(state, project, specs.clone()).await?.specs {
+        if let Some(result) = results.iter_mut().find(|r| r.spec_name == stale.spec_name) {
+            result.issues.push(stale.to_issue());
+            apply_rule_settings(result, &options.settings);
+        }
+    }
+
+    let mut entries: Vec<ReportEntry> = specs
+        .iter()
+        .zip(results)
+        .map(|(spec, result)| ReportEntry {
+            path: project_relative_path(project, &spec.file_path),
+            result,
+        })
+        .collect();
+
+    // Spec directories that failed to load fail validation
+    for diagnostic in &diagnostics {
+        let mut result = load_failure_result(diagnostic);
+        apply_rule_settings(&mut result, &options.settings);
+        entries.push(ReportEntry {
+            path: project_relative_path(project, &diagnostic.file_path),
+            result,
+        });
+    }
+
+    Ok((entries, options))
+}
+
+/// A spec `file_path` (`specs/<name>/README.md`) relative to the project root
+fn project_relative_path(project: &DesktopProject, file_path: &str) -> String {
+    let relative = file_path.strip_prefix("specs/").unwrap_or(file_path);
+    let absolute = Path::new(&project.specs_dir).join(relative);
+    absolute
+        .strip_prefix(&project.path)
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| file_path.to_string())
+}
+
+/// Resolve `project:spec` dependencies against the registered projects
+fn load_external_specs(state: &DesktopState, specs: &[Spec]) -> ExternalSpecs {
+    resolve_external_specs(specs, |key| {
+        let project = state.project_store.find_by_name_or_id(key)?;
+        let specs = SpecReader::new(&project.specs_dir, &project.id).load_all();
+        Some((
+            ExternalProject {
+                id: project.id,
+                name: project.name,
+            },
+            specs,
+        ))
+    })
+}
+
+/// Tokenizer and thresholds for a project
+///
+/// Project settings can pin them; otherwise the default chat model decides,
+/// then the project's default session runner.
+async fn project_token_budget(
+    state: &DesktopState,
+    project: &DesktopProject,
+    settings: &ProjectSettings,
+) -> TokenBudget {
+    let config = state.chat_config.read().await.config();
+    let chat_model = config
+        .providers
+        .iter()
+        .flat_map(|provider| provider.models.iter())
+        .find(|model| model.default == Some(true))
+        .map(|model| model.id.clone());
+    let runner = RunnerRegistry::load(Path::new(&project.path))
+        .ok()
+        .and_then(|registry| registry.default().map(String::from));
+
+    TokenBudget::resolve(&settings.tokens, chat_model, runner)
+}
+
+/// Validation options from the project's settings and frontmatter schema
+async fn project_validation_options(
+    state: &DesktopState,
+    project: &DesktopProject,
+) -> Result<ValidationOptions, String> {
+    let settings = ProjectSettings::load(&project.path)?;
+    Ok(ValidationOptions {
+        tokens: project_token_budget(state, project, &settings).await,
+        settings: settings.validation,
+        specs_dir: Some(PathBuf::from(&project.specs_dir)),
+        schema: FrontmatterSchema::load(&project.path)?.map(Arc::new),
+    })
+}
+
+/// Git activity of the project's repository
+///
+/// The logs are cached per project until HEAD or a local branch moves. This
+/// runs git, so call it on the blocking pool.
+fn project_git_activity(cache: &GitActivityCache, project: &DesktopProject) -> Arc<ProjectActivity> {
+    let root = Path::new(&project.path);
+    let Some(fingerprint) = git::refs_fingerprint(root) else {
+        return Arc::new(ProjectActivity::default());
+    };
+    cache.get_or_load(&project.id, fingerprint, || GitActivity {
+        commits: git::commit_messages(root, GIT_LOG_LIMIT),
+        branches: git::branch_heads(root),
+        spec_commits: git::file_commits(Path::new(&project.specs_dir), GIT_LOG_LIMIT),
+    })
+}
+
+/// Lightweight specs with their last code activity
+async fn lightweight_specs(
+    state: &DesktopState,
+    project: DesktopProject,
+    specs: Vec<Spec>,
+) -> Result<Vec<LightweightSpec>, String> {
+    let cache = state.git_activity_cache.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let activity = project_git_activity(&cache, &project).code_activity(&specs);
+        specs
+            .iter()
+            .zip(activity)
+            .map(|(spec, activity)| LightweightSpec {
+                last_code_activity: activity.last_code_activity,
+                ..LightweightSpec::from(spec)
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Reading git activity failed: {}", e))
+}
+
+/// Run stale spec detection with git activity, falling back to file mtimes
+async fn detect_project_staleness(
+    state: &DesktopState,
+    project: &DesktopProject,
+    specs: Vec<Spec>,
+) -> Result<StalenessReport, String> {
+    let cache = state.git_activity_cache.clone();
+    let project = project.clone();
+    tauri::async_runtime::spawn_blocking(move || project_staleness(&cache, &project, &specs))
+        .await
+        .map_err(|e| format!("Staleness check failed: {}", e))?
+}
+
+/// Stale spec detection from git, file and code activity (blocking)
+fn project_staleness(
+    cache: &GitActivityCache,
+    project: &DesktopProject,
+    specs: &[Spec],
+) -> Result<StalenessReport, String> {
+    let settings = ProjectSettings::load(&project.path)?;
+    let git_activity = project_git_activity(cache, project);
+
+    let mut activity = ActivityIndex::from_commits(&git_activity.git.spec_commits)
+        .with_aliases(AssigneeAliases::load(&project.path)?);
+    for spec in specs {
+        if activity.contains(&spec.spec_name) {
+            continue;
+        }
+        let modified = std::fs::metadata(spec_file_path(project, spec)).and_then(|m| m.modified());
+        if let Ok(modified) = modified {
+            activity.record_fallback(&spec.spec_name, modified.into());
         }
-        
-        // If field not found, add it at the end
-        let new_yaml = if found {
-            lines.join("\n")
-        } else {
-            format!("{}\n{}: {}", yaml_content, field, value)
-        };
-        
-        Ok(format!("---\n{}\n---{}", new_yaml, markdown_content))
-    } else {
-        Err("Malformed frontmatter".to_string())
     }
+    for coded in git_activity.code_activity(specs) {
+        if let Some(at) = coded.last_code_activity {
+            activity.record_code_activity(&coded.spec_name, at);
+        }
+    }
+
+    Ok(detect_stale_specs(
+        specs,
+        &activity,
+        &settings.staleness,
+        Utc::now(),
+    ))
+}
+
+/// Parse a date parameter given as RFC 3339 or `YYYY-MM-DD`
+///
+/// Plain dates resolve to the start of the day, or its end when `end_of_day`.
+fn parse_date_param(value: &str, end_of_day: bool) -> Result<DateTime<Utc>, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}'. Use YYYY-MM-DD or RFC 3339", value))?;
+    let time = if end_of_day {
+        date.and_hms_opt(23, 59, 59)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+    Ok(time.expect("valid time of day").and_utc())
 }
@@ -2,10 +2,16 @@
 //!
 //! Builds a directed graph of spec dependencies for visualization.
 
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use crate::specs::reader::Spec;
+use crate::specs::relations::RelationKind;
+
+pub mod layout;
+
+use layout::GraphLayout;
 
 /// A node in the dependency graph
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +23,11 @@ pub struct DependencyNode {
     pub status: String,
     pub priority: String,
     pub tags: Vec<String>,
+    /// Owning project, set only for specs from another registered project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_name: Option<String>,
 }
 
 /// An edge in the dependency graph
@@ -35,95 +46,341 @@ pub struct DependencyEdge {
 pub struct DependencyGraph {
     pub nodes: Vec<DependencyNode>,
     pub edges: Vec<DependencyEdge>,
+    /// Precomputed layered layout, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<GraphLayout>,
 }
 
 /// Build a dependency graph from a list of specs
-pub fn build_dependency_graph(specs: &[Spec]) -> DependencyGraph {
-    // Build lookup maps
-    let mut spec_id_by_name: HashMap<String, String> = HashMap::new();
-    let mut spec_id_by_number: HashMap<i32, String> = HashMap::new();
-
+///
+/// Resolved cross-project dependencies are included: specs from other
+/// projects are added as nodes carrying their project and live status, with
+/// an edge to each local spec that depends on them.
+pub fn build_dependency_graph(
+    specs: &[Spec],
+    external: &ExternalSpecs,
+) -> DependencyGraph {
     // Only include specs with numbers
     let numbered_specs: Vec<&Spec> = specs
         .iter()
         .filter(|s| s.spec_number.is_some())
         .collect();
+    let lookup = SpecLookup::new(numbered_specs.iter().copied());
+
+    // Build nodes
+    let mut nodes: Vec<DependencyNode> = numbered_specs
+        .iter()
+        .map(|spec| DependencyNode::from_spec(spec, None))
+        .collect();
+
+    // Build edges
+    let mut edges: Vec<DependencyEdge> = Vec::new();
+    let mut seen_related: HashSet<(String, String)> = HashSet::new();
+    let mut external_nodes: HashSet<String> = HashSet::new();
 
-    // Build lookup maps
     for spec in &numbered_specs {
-        spec_id_by_name.insert(spec.spec_name.clone(), spec.id.clone());
-        if let Some(num) = spec.spec_number {
-            spec_id_by_number.insert(num, spec.id.clone());
-            // Also index by padded number
-            spec_id_by_name.insert(format!("{:03}", num), spec.id.clone());
-            spec_id_by_name.insert(num.to_string(), spec.id.clone());
+        for kind in RelationKind::ALL {
+            for dep in spec.references(kind) {
+                // Try to resolve the reference
+                let Some(target_id) = lookup.find(dep).map(|s| s.id.clone()) else {
+                    continue;
+                };
+
+                // Only add edge if target exists and is different from source
+                if target_id == spec.id {
+                    continue;
+                }
+
+                // Related is symmetric, so keep a single edge per pair
+                if kind == RelationKind::Related {
+                    let mut pair = [spec.id.clone(), target_id.clone()];
+                    pair.sort();
+                    let [a, b] = pair;
+                    if !seen_related.insert((a, b)) {
+                        continue;
+                    }
+                }
+
+                // "A depends on B" means edge from B to A; see RelationKind::edge_endpoints
+                let (source, target) = kind.edge_endpoints(&spec.id, &target_id);
+                edges.push(DependencyEdge {
+                    source: source.to_string(),
+                    target: target.to_string(),
+                    edge_type: kind.edge_type().to_string(),
+                });
+            }
+        }
+
+        // Cross-project dependencies
+        for dep in &spec.depends_on {
+            let Some(ExternalResolution::Resolved { project, spec: ext }) = external.get(dep)
+            else {
+                continue;
+            };
+            let node_id = format!("{}:{}", project.id, ext.id);
+            if external_nodes.insert(node_id.clone()) {
+                let mut node = DependencyNode::from_spec(ext, Some(project));
+                node.id = node_id.clone();
+                nodes.push(node);
+            }
+            edges.push(DependencyEdge {
+                source: node_id,
+                target: spec.id.clone(),
+                edge_type: RelationKind::DependsOn.edge_type().to_string(),
+            });
         }
     }
 
-    // Build nodes
-    let nodes: Vec<DependencyNode> = numbered_specs
-        .iter()
-        .map(|spec| DependencyNode {
+    DependencyGraph {
+        nodes,
+        edges,
+        layout: None,
+    }
+}
+
+impl DependencyNode {
+    fn from_spec(spec: &Spec, project: Option<&ExternalProject>) -> Self {
+        DependencyNode {
             id: spec.id.clone(),
-            name: spec.title.clone().unwrap_or_else(|| {
-                format!("Spec {}", spec.spec_number.unwrap_or(0))
-            }),
+            name: spec
+                .title
+                .clone()
+                .unwrap_or_else(|| format!("Spec {}", spec.spec_number.unwrap_or(0))),
             number: spec.spec_number.unwrap_or(0),
             status: spec.status.clone(),
             priority: spec.priority.clone().unwrap_or_else(|| "medium".to_string()),
             tags: spec.tags.clone(),
+            project_id: project.map(|p| p.id.clone()),
+            project_name: project.map(|p| p.name.clone()),
+        }
+    }
+}
+
+impl DependencyInfo {
+    fn from_spec(spec: &Spec, project: Option<&ExternalProject>) -> Self {
+        DependencyInfo {
+            spec_name: spec.spec_name.clone(),
+            title: spec.title.clone(),
+            status: spec.status.clone(),
+            project_id: project.map(|p| p.id.clone()),
+            project_name: project.map(|p| p.name.clone()),
+        }
+    }
+}
+
+/// A registered project referenced from another project's specs
+#[derive(Debug, Clone)]
+pub struct ExternalProject {
+    pub id: String,
+    pub name: String,
+}
+
+/// Outcome of resolving a `project:spec` reference
+#[derive(Debug, Clone)]
+pub enum ExternalResolution {
+    Resolved {
+        project: ExternalProject,
+        spec: Box<Spec>,
+    },
+    /// The project exists but has no matching spec
+    UnknownSpec { project: ExternalProject },
+    /// No registered project matches by name or id
+    UnknownProject,
+}
+
+/// Resolved cross-project references, keyed by the raw reference
+#[derive(Debug, Clone, Default)]
+pub struct ExternalSpecs {
+    resolutions: HashMap<String, ExternalResolution>,
+}
+
+impl ExternalSpecs {
+    pub fn get(&self, reference: &str) -> Option<&ExternalResolution> {
+        self.resolutions.get(reference.trim())
+    }
+
+    pub fn insert(&mut self, reference: &str, resolution: ExternalResolution) {
+        self.resolutions.insert(reference.trim().to_string(), resolution);
+    }
+}
+
+/// Split a `project:spec` reference into its project key and spec reference
+pub fn parse_external_reference(dep: &str) -> Option<(&str, &str)> {
+    let (project, spec) = dep.trim().split_once(':')?;
+    let (project, spec) = (project.trim(), spec.trim());
+    if project.is_empty() || spec.is_empty() {
+        return None;
+    }
+    Some((project, spec))
+}
+
+/// Resolve every cross-project `depends_on` entry in a set of specs
+///
+/// `load_project` looks a project up by name or id and returns it with its
+/// specs; it is called at most once per distinct project key.
+pub fn resolve_external_specs<F>(specs: &[Spec], mut load_project: F) -> ExternalSpecs
+where
+    F: FnMut(&str) -> Option<(ExternalProject, Vec<Spec>)>,
+{
+    let references: Vec<(&String, &str, &str)> = specs
+        .iter()
+        .flat_map(|s| s.depends_on.iter())
+        .filter_map(|dep| {
+            parse_external_reference(dep)
+                .map(|(project_key, spec_ref)| (dep, project_key, spec_ref))
         })
         .collect();
 
-    // Build edges
-    let mut edges: Vec<DependencyEdge> = Vec::new();
+    let mut loaded: HashMap<String, Option<(ExternalProject, Vec<Spec>)>> = HashMap::new();
+    for (_, project_key, _) in &references {
+        if !loaded.contains_key(*project_key) {
+            loaded.insert(project_key.to_string(), load_project(project_key));
+        }
+    }
+    let lookups: HashMap<&str, (&ExternalProject, SpecLookup)> = loaded
+        .iter()
+        .filter_map(|(key, entry)| {
+            let (project, project_specs) = entry.as_ref()?;
+            Some((key.as_str(), (project, SpecLookup::new(project_specs))))
+        })
+        .collect();
 
-    for spec in &numbered_specs {
-        for dep in &spec.depends_on {
-            // Try to resolve the dependency
-            let target_id = resolve_dependency(dep, &spec_id_by_name, &spec_id_by_number);
+    let mut external = ExternalSpecs::default();
+    for (dep, project_key, spec_ref) in references {
+        let resolution = match lookups.get(project_key) {
+            None => ExternalResolution::UnknownProject,
+            Some((project, lookup)) => match lookup.find(spec_ref) {
+                Some(spec) => ExternalResolution::Resolved {
+                    project: (*project).clone(),
+                    spec: Box::new(spec.clone()),
+                },
+                None => ExternalResolution::UnknownSpec {
+                    project: (*project).clone(),
+                },
+            },
+        };
+        external.insert(dep, resolution);
+    }
 
-            if let Some(target_id) = target_id {
-                // Only add edge if target exists and is different from source
-                if target_id != spec.id {
-                    edges.push(DependencyEdge {
-                        // Edge goes from dependency (source) to dependent (target)
-                        // i.e., "A depends on B" means edge from B to A
-                        source: target_id,
-                        target: spec.id.clone(),
-                        edge_type: "dependsOn".to_string(),
-                    });
-                }
+    external
+}
+
+/// Find specs in other projects that depend on `spec` through a `project:spec` reference
+///
+/// `project_keys` are the id and name the current project can be referenced by.
+pub fn find_external_dependents(
+    spec: &Spec,
+    project_keys: &[&str],
+    other_projects: &[(ExternalProject, Vec<Spec>)],
+) -> Vec<DependencyInfo> {
+    let lookup = SpecLookup::new([spec]);
+    let mut dependents = Vec::new();
+    for (project, specs) in other_projects {
+        for other in specs {
+            let depends = other.depends_on.iter().any(|dep| {
+                parse_external_reference(dep)
+                    .map(|(key, spec_ref)| {
+                        project_keys.iter().any(|k| k.eq_ignore_ascii_case(key))
+                            && lookup.find(spec_ref).is_some()
+                    })
+                    .unwrap_or(false)
+            });
+            if depends {
+                dependents.push(DependencyInfo::from_spec(other, Some(project)));
             }
         }
     }
+    dependents
+}
 
-    DependencyGraph { nodes, edges }
+/// Specs with cross-project references, keyed by project id and tagged with a
+/// spec fingerprint
+///
+/// Finding external dependents needs the specs of every other project; only
+/// the ones referencing another project are kept, and a project is reloaded
+/// only when its spec files change.
+#[derive(Default)]
+pub struct ExternalReferenceCache {
+    entries: Mutex<HashMap<String, (u64, Vec<Spec>)>>,
 }
 
-/// Resolve a dependency string to a spec ID
-fn resolve_dependency(
-    dep: &str,
-    by_name: &HashMap<String, String>,
-    by_number: &HashMap<i32, String>,
-) -> Option<String> {
-    let trimmed = dep.trim();
-
-    // Direct name match
-    if let Some(id) = by_name.get(trimmed) {
-        return Some(id.clone());
+impl ExternalReferenceCache {
+    /// Return the cached specs when the fingerprint matches, else load them
+    pub fn get_or_load<F>(&self, project_id: &str, fingerprint: u64, load: F) -> Vec<Spec>
+    where
+        F: FnOnce() -> Vec<Spec>,
+    {
+        if let Some((cached, specs)) = self.entries.lock().get(project_id) {
+            if *cached == fingerprint {
+                return specs.clone();
+            }
+        }
+
+        let specs: Vec<Spec> = load()
+            .into_iter()
+            .filter(|s| {
+                s.depends_on
+                    .iter()
+                    .any(|dep| parse_external_reference(dep).is_some())
+            })
+            .collect();
+        self.entries
+            .lock()
+            .insert(project_id.to_string(), (fingerprint, specs.clone()));
+        specs
     }
+}
+
+/// Resolves dependency strings to specs by name or number prefix
+///
+/// Build it once per spec list; lookups are map accesses.
+pub struct SpecLookup<'a> {
+    by_name: HashMap<&'a str, &'a Spec>,
+    by_number: HashMap<i32, Vec<&'a Spec>>,
+}
 
-    // Try to extract number from dependency
-    if let Some(num_str) = trimmed.split('-').next() {
-        if let Ok(num) = num_str.parse::<i32>() {
-            if let Some(id) = by_number.get(&num) {
-                return Some(id.clone());
+impl<'a> SpecLookup<'a> {
+    pub fn new(specs: impl IntoIterator<Item = &'a Spec>) -> Self {
+        let mut by_name = HashMap::new();
+        let mut by_number: HashMap<i32, Vec<&'a Spec>> = HashMap::new();
+        for spec in specs {
+            by_name.insert(spec.spec_name.as_str(), spec);
+            if let Some(num) = spec.spec_number {
+                by_number.entry(num).or_default().push(spec);
             }
         }
+        Self { by_name, by_number }
+    }
+
+    /// The spec a dependency string refers to
+    ///
+    /// Returns `None` when the number is shared by several specs; see
+    /// [`SpecLookup::candidates`].
+    pub fn find(&self, dep: &str) -> Option<&'a Spec> {
+        match self.candidates(dep).as_slice() {
+            [spec] => Some(spec),
+            _ => None,
+        }
     }
 
-    None
+    /// Every spec a dependency string could refer to
+    ///
+    /// An exact name match wins; otherwise all specs with the same number are
+    /// returned, so more than one candidate means the reference is ambiguous.
+    pub fn candidates(&self, dep: &str) -> Vec<&'a Spec> {
+        let trimmed = dep.trim();
+        if let Some(spec) = self.by_name.get(trimmed) {
+            return vec![spec];
+        }
+
+        trimmed
+            .split('-')
+            .next()
+            .and_then(|n| n.parse::<i32>().ok())
+            .and_then(|num| self.by_number.get(&num))
+            .cloned()
+            .unwrap_or_default()
+    }
 }
 
 /// Get dependency chain for a specific spec (for visualization)
@@ -132,6 +389,19 @@ fn resolve_dependency(
 pub struct SpecDependencies {
     pub depends_on: Vec<DependencyInfo>,
     pub required_by: Vec<DependencyInfo>,
+    #[serde(default)]
+    pub related: Vec<DependencyInfo>,
+    #[serde(default)]
+    pub blocks: Vec<DependencyInfo>,
+    #[serde(default)]
+    pub blocked_by: Vec<DependencyInfo>,
+    #[serde(default)]
+    pub supersedes: Vec<DependencyInfo>,
+    #[serde(default)]
+    pub superseded_by: Vec<DependencyInfo>,
+    pub parent: Option<DependencyInfo>,
+    #[serde(default)]
+    pub children: Vec<DependencyInfo>,
 }
 
 /// Information about a dependency
@@ -141,101 +411,213 @@ pub struct DependencyInfo {
     pub spec_name: String,
     pub title: Option<String>,
     pub status: String,
+    /// Owning project, set only for specs from another registered project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_name: Option<String>,
 }
 
-/// Get dependency info for a specific spec
-pub fn get_spec_dependencies(spec: &Spec, all_specs: &[Spec]) -> SpecDependencies {
-    let spec_map: HashMap<String, &Spec> = all_specs
-        .iter()
-        .flat_map(|s| {
-            let mut entries = vec![(s.spec_name.clone(), s)];
-            if let Some(num) = s.spec_number {
-                entries.push((format!("{:03}", num), s));
-                entries.push((num.to_string(), s));
+/// Get dependency info for a specific spec, including cross-project dependencies
+pub fn get_spec_dependencies(
+    spec: &Spec,
+    all_specs: &[Spec],
+    external: &ExternalSpecs,
+) -> SpecDependencies {
+    let lookup = SpecLookup::new(all_specs);
+    let info = |s: &Spec| DependencyInfo::from_spec(s, None);
+
+    // Resolve declared references by direct match, then by number prefix
+    let resolve_all = |kind: RelationKind| -> Vec<DependencyInfo> {
+        spec.references(kind)
+            .into_iter()
+            .filter_map(|dep| lookup.find(dep).map(info))
+            .collect()
+    };
+
+    // Computed inverses are exact spec names
+    let lookup_all = |names: &[String]| -> Vec<DependencyInfo> {
+        names
+            .iter()
+            .filter_map(|name| lookup.find(name).map(info))
+            .collect()
+    };
+
+    // Related is symmetric: merge declared and inverse without duplicates
+    let mut related = resolve_all(RelationKind::Related);
+    for other in lookup_all(&spec.relations.related_by) {
+        if !related.iter().any(|r| r.spec_name == other.spec_name) {
+            related.push(other);
+        }
+    }
+
+    let mut depends_on = resolve_all(RelationKind::DependsOn);
+    for dep in &spec.depends_on {
+        if let Some(ExternalResolution::Resolved { project, spec: ext }) = external.get(dep) {
+            depends_on.push(DependencyInfo::from_spec(ext, Some(project)));
+        }
+    }
+
+    SpecDependencies {
+        depends_on,
+        required_by: lookup_all(&spec.required_by),
+        related,
+        blocks: resolve_all(RelationKind::Blocks),
+        blocked_by: lookup_all(&spec.relations.blocked_by),
+        supersedes: resolve_all(RelationKind::Supersedes),
+        superseded_by: lookup_all(&spec.relations.superseded_by),
+        parent: resolve_all(RelationKind::Parent).into_iter().next(),
+        children: lookup_all(&spec.relations.children),
+    }
+}
+
+/// Direction to walk the dependency graph in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ImpactDirection {
+    /// Specs this spec depends on, transitively
+    Upstream,
+    /// Specs that depend on this spec, transitively
+    #[default]
+    Downstream,
+}
+
+/// A spec reached while walking the dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpactedSpec {
+    pub spec_name: String,
+    pub title: Option<String>,
+    pub status: String,
+    /// Number of hops from the starting spec
+    pub distance: usize,
+    /// Spec names from the starting spec to this one (inclusive)
+    pub path: Vec<String>,
+}
+
+/// Status summary of the impacted specs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpactSummary {
+    pub total: usize,
+    /// Specs in draft, planned or in-progress
+    pub open: usize,
+    pub complete: usize,
+    pub archived: usize,
+}
+
+/// Transitive impact of a spec in one direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecImpact {
+    pub spec_name: String,
+    pub direction: ImpactDirection,
+    pub max_depth: Option<usize>,
+    pub specs: Vec<ImpactedSpec>,
+    pub summary: ImpactSummary,
+}
+
+/// Walk the dependency graph transitively from a spec
+///
+/// Uses a breadth-first search so each reached spec is reported with its
+/// shortest distance. `max_depth` limits the number of hops; `None` walks
+/// the whole graph.
+pub fn get_spec_impact(
+    spec: &Spec,
+    all_specs: &[Spec],
+    direction: ImpactDirection,
+    max_depth: Option<usize>,
+) -> SpecImpact {
+    let lookup = SpecLookup::new(all_specs);
+
+    // Edges are expressed by spec name: upstream follows depends_on,
+    // downstream follows the computed required_by list.
+    let mut upstream: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut downstream: HashMap<&str, Vec<&str>> = HashMap::new();
+    for s in all_specs {
+        for dep in &s.depends_on {
+            if let Some(target) = lookup.find(dep) {
+                if target.spec_name != s.spec_name {
+                    upstream
+                        .entry(s.spec_name.as_str())
+                        .or_default()
+                        .push(target.spec_name.as_str());
+                    downstream
+                        .entry(target.spec_name.as_str())
+                        .or_default()
+                        .push(s.spec_name.as_str());
+                }
             }
-            entries
-        })
-        .collect();
+        }
+    }
+    let adjacency = match direction {
+        ImpactDirection::Upstream => &upstream,
+        ImpactDirection::Downstream => &downstream,
+    };
+
+    let mut visited: HashSet<&str> = HashSet::new();
+    visited.insert(spec.spec_name.as_str());
+    let mut queue: VecDeque<(&str, Vec<String>)> = VecDeque::new();
+    queue.push_back((spec.spec_name.as_str(), vec![spec.spec_name.clone()]));
+
+    let mut specs = Vec::new();
+    let mut summary = ImpactSummary::default();
+
+    while let Some((name, path)) = queue.pop_front() {
+        let distance = path.len() - 1;
+        if max_depth.map(|max| distance >= max).unwrap_or(false) {
+            continue;
+        }
 
-    // Resolve depends_on
-    let depends_on: Vec<DependencyInfo> = spec
-        .depends_on
-        .iter()
-        .filter_map(|dep| {
-            let trimmed = dep.trim();
-            
-            // Try direct match first
-            if let Some(s) = spec_map.get(trimmed) {
-                return Some(DependencyInfo {
-                    spec_name: s.spec_name.clone(),
-                    title: s.title.clone(),
-                    status: s.status.clone(),
-                });
+        for next in adjacency.get(name).into_iter().flatten() {
+            if !visited.insert(next) {
+                continue;
             }
-            
-            // Try number prefix
-            if let Some(num_str) = trimmed.split('-').next() {
-                if let Ok(num) = num_str.parse::<i32>() {
-                    if let Some(s) = spec_map.get(&num.to_string()) {
-                        return Some(DependencyInfo {
-                            spec_name: s.spec_name.clone(),
-                            title: s.title.clone(),
-                            status: s.status.clone(),
-                        });
-                    }
-                }
+            let Some(reached) = lookup.find(next) else {
+                continue;
+            };
+
+            let mut next_path = path.clone();
+            next_path.push(reached.spec_name.clone());
+
+            summary.total += 1;
+            match reached.status.as_str() {
+                "complete" => summary.complete += 1,
+                "archived" => summary.archived += 1,
+                _ => summary.open += 1,
             }
-            
-            None
-        })
-        .collect();
 
-    // Resolve required_by
-    let required_by: Vec<DependencyInfo> = spec
-        .required_by
-        .iter()
-        .filter_map(|name| {
-            spec_map.get(name).map(|s| DependencyInfo {
-                spec_name: s.spec_name.clone(),
-                title: s.title.clone(),
-                status: s.status.clone(),
-            })
-        })
-        .collect();
+            specs.push(ImpactedSpec {
+                spec_name: reached.spec_name.clone(),
+                title: reached.title.clone(),
+                status: reached.status.clone(),
+                distance: distance + 1,
+                path: next_path.clone(),
+            });
+            queue.push_back((next, next_path));
+        }
+    }
 
-    SpecDependencies {
-        depends_on,
-        required_by,
+    SpecImpact {
+        spec_name: spec.spec_name.clone(),
+        direction,
+        max_depth,
+        specs,
+        summary,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use crate::specs::reader::SpecBuilder;
 
     fn create_test_spec(num: i32, name: &str, deps: Vec<&str>) -> Spec {
-        Spec {
-            id: format!("fs-{}", name),
-            project_id: "test".to_string(),
-            spec_number: Some(num),
-            spec_name: name.to_string(),
-            title: Some(format!("Spec {}", num)),
-            status: "planned".to_string(),
-            priority: Some("medium".to_string()),
-            tags: vec![],
-            assignee: None,
-            content_md: String::new(),
-            content_html: None,
-            created_at: None,
-            updated_at: None,
-            completed_at: None,
-            file_path: format!("specs/{}/README.md", name),
-            github_url: None,
-            synced_at: Utc::now(),
-            depends_on: deps.into_iter().map(String::from).collect(),
-            required_by: Vec::new(),
-        }
+        SpecBuilder::new(name)
+            .title(&format!("Spec {}", num))
+            .priority("medium")
+            .depends_on(deps.into_iter().map(String::from).collect())
+            .build()
     }
 
     #[test]
@@ -246,7 +628,7 @@ mod tests {
             create_test_spec(3, "003-extension", vec!["002-feature", "001"]),
         ];
 
-        let graph = build_dependency_graph(&specs);
+        let graph = build_dependency_graph(&specs, &ExternalSpecs::default());
 
         assert_eq!(graph.nodes.len(), 3);
         assert_eq!(graph.edges.len(), 3);
@@ -263,19 +645,166 @@ mod tests {
         assert!(edge_pairs.contains(&("fs-001-base", "fs-003-extension")));
     }
 
+    #[test]
+    fn test_build_dependency_graph_typed_edges() {
+        let mut specs = vec![
+            create_test_spec(1, "001-epic", vec![]),
+            create_test_spec(2, "002-old", vec![]),
+            create_test_spec(3, "003-new", vec![]),
+            create_test_spec(4, "004-next", vec![]),
+        ];
+        specs[2].relations.parent = Some("001".to_string());
+        specs[2].relations.supersedes = vec!["002-old".to_string()];
+        specs[2].relations.blocks = vec!["004-next".to_string()];
+        specs[2].relations.related = vec!["004".to_string()];
+        specs[3].relations.related = vec!["003-new".to_string()];
+
+        let graph = build_dependency_graph(&specs, &ExternalSpecs::default());
+        let edges: Vec<(&str, &str, &str)> = graph
+            .edges
+            .iter()
+            .map(|e| (e.source.as_str(), e.target.as_str(), e.edge_type.as_str()))
+            .collect();
+
+        assert_eq!(edges.len(), 4);
+        assert!(edges.contains(&("fs-001-epic", "fs-003-new", "parent")));
+        assert!(edges.contains(&("fs-003-new", "fs-002-old", "supersedes")));
+        assert!(edges.contains(&("fs-003-new", "fs-004-next", "blocks")));
+        assert!(edges.contains(&("fs-003-new", "fs-004-next", "related")));
+    }
+
+    #[test]
+    fn test_cross_project_dependencies() {
+        let specs = vec![
+            create_test_spec(1, "001-login-ui", vec!["backend:042", "missing:001", "backend:999"]),
+        ];
+        let backend_specs = vec![create_test_spec(42, "042-auth-tokens", vec![])];
+
+        let external = resolve_external_specs(&specs, |key| {
+            (key == "backend").then(|| {
+                (
+                    ExternalProject {
+                        id: "abc123".to_string(),
+                        name: "backend".to_string(),
+                    },
+                    backend_specs.clone(),
+                )
+            })
+        });
+
+        assert!(matches!(
+            external.get("backend:042"),
+            Some(ExternalResolution::Resolved { .. })
+        ));
+        assert!(matches!(
+            external.get("missing:001"),
+            Some(ExternalResolution::UnknownProject)
+        ));
+        assert!(matches!(
+            external.get("backend:999"),
+            Some(ExternalResolution::UnknownSpec { .. })
+        ));
+
+        let graph = build_dependency_graph(&specs, &external);
+        assert_eq!(graph.nodes.len(), 2);
+        let ext_node = graph.nodes.iter().find(|n| n.project_id.is_some()).unwrap();
+        assert_eq!(ext_node.id, "abc123:fs-042-auth-tokens");
+        assert_eq!(graph.edges[0].source, "abc123:fs-042-auth-tokens");
+        assert_eq!(graph.edges[0].target, "fs-001-login-ui");
+
+        let deps = get_spec_dependencies(&specs[0], &specs, &external);
+        assert_eq!(deps.depends_on.len(), 1);
+        assert_eq!(deps.depends_on[0].project_name.as_deref(), Some("backend"));
+    }
+
+    #[test]
+    fn test_external_reference_cache() {
+        let cache = ExternalReferenceCache::default();
+        let load = || {
+            vec![
+                create_test_spec(1, "001-ui", vec!["backend:042"]),
+                create_test_spec(2, "002-local", vec!["001-ui"]),
+            ]
+        };
+
+        let specs = cache.get_or_load("web", 1, load);
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].spec_name, "001-ui");
+        cache.get_or_load("web", 1, || panic!("should be cached"));
+        assert!(cache.get_or_load("web", 2, Vec::new).is_empty());
+    }
+
     #[test]
     fn test_resolve_dependency() {
-        let mut by_name = HashMap::new();
-        let mut by_number = HashMap::new();
-        
-        by_name.insert("001-base".to_string(), "id-1".to_string());
-        by_name.insert("001".to_string(), "id-1".to_string());
-        by_name.insert("1".to_string(), "id-1".to_string());
-        by_number.insert(1, "id-1".to_string());
-
-        assert_eq!(resolve_dependency("001-base", &by_name, &by_number), Some("id-1".to_string()));
-        assert_eq!(resolve_dependency("001", &by_name, &by_number), Some("id-1".to_string()));
-        assert_eq!(resolve_dependency("1", &by_name, &by_number), Some("id-1".to_string()));
-        assert_eq!(resolve_dependency("999", &by_name, &by_number), None);
+        let specs = vec![create_test_spec(1, "001-base", vec![])];
+        let lookup = SpecLookup::new(&specs);
+        let resolve = |dep: &str| lookup.find(dep).map(|s| s.id.clone());
+
+        assert_eq!(resolve("001-base"), Some("fs-001-base".to_string()));
+        assert_eq!(resolve("001"), Some("fs-001-base".to_string()));
+        assert_eq!(resolve("1"), Some("fs-001-base".to_string()));
+        assert_eq!(resolve("999"), None);
+    }
+
+    #[test]
+    fn test_ambiguous_dependency_number() {
+        let specs = vec![
+            create_test_spec(42, "042-auth", vec![]),
+            create_test_spec(42, "042-billing", vec![]),
+            create_test_spec(43, "043-ui", vec!["042", "042-billing"]),
+        ];
+
+        let lookup = SpecLookup::new(&specs);
+        assert_eq!(lookup.candidates("042").len(), 2);
+        assert!(lookup.find("042").is_none());
+        assert_eq!(
+            lookup.find("042-billing").map(|s| s.spec_name.as_str()),
+            Some("042-billing")
+        );
+
+        // Only the full name resolves to an edge
+        let graph = build_dependency_graph(&specs, &ExternalSpecs::default());
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].source, "fs-042-billing");
+    }
+
+    #[test]
+    fn test_get_spec_impact_downstream() {
+        let mut specs = vec![
+            create_test_spec(1, "001-base", vec![]),
+            create_test_spec(2, "002-feature", vec!["001-base"]),
+            create_test_spec(3, "003-extension", vec!["002"]),
+            create_test_spec(4, "004-other", vec![]),
+        ];
+        specs[2].status = "complete".to_string();
+
+        let impact = get_spec_impact(&specs[0], &specs, ImpactDirection::Downstream, None);
+
+        assert_eq!(impact.specs.len(), 2);
+        assert_eq!(impact.specs[0].spec_name, "002-feature");
+        assert_eq!(impact.specs[0].distance, 1);
+        assert_eq!(impact.specs[1].spec_name, "003-extension");
+        assert_eq!(impact.specs[1].distance, 2);
+        assert_eq!(
+            impact.specs[1].path,
+            vec!["001-base", "002-feature", "003-extension"]
+        );
+        assert_eq!(impact.summary.open, 1);
+        assert_eq!(impact.summary.complete, 1);
+    }
+
+    #[test]
+    fn test_get_spec_impact_upstream_with_depth() {
+        let specs = vec![
+            create_test_spec(1, "001-base", vec![]),
+            create_test_spec(2, "002-feature", vec!["001-base"]),
+            create_test_spec(3, "003-extension", vec!["002-feature"]),
+        ];
+
+        let impact = get_spec_impact(&specs[2], &specs, ImpactDirection::Upstream, Some(1));
+
+        assert_eq!(impact.specs.len(), 1);
+        assert_eq!(impact.specs[0].spec_name, "002-feature");
+        assert_eq!(impact.summary.total, 1);
     }
 }
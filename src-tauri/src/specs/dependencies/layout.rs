@@ -0,0 +1,742 @@
+//! Layered (Sugiyama-style) layout for the dependency graph
+//!
+//! Large graphs are unreadable with a force layout, so the backend computes a
+//! top-to-bottom layered layout: cycles are broken, nodes are assigned to
+//! layers by longest path, long edges are split with virtual nodes, crossings
+//! are reduced with barycenter sweeps and finally coordinates are assigned.
+//! Only `dependsOn` and `blocks` edges decide the layers; other relationships
+//! are routed straight between the placed nodes.
+//! Nodes can be grouped by tag or status into clusters, each occupying its
+//! own horizontal band.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use super::{DependencyGraph, DependencyNode};
+
+/// Edge types that order nodes into layers
+const LAYERED_EDGE_TYPES: [&str; 2] = ["dependsOn", "blocks"];
+
+/// How to group nodes into clusters
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutGrouping {
+    #[default]
+    None,
+    /// Group by the node's first tag
+    Tag,
+    Status,
+}
+
+/// Layout tuning parameters
+#[derive(Debug, Clone)]
+pub struct LayoutOptions {
+    pub group_by: LayoutGrouping,
+    pub node_width: f64,
+    pub node_height: f64,
+    /// Horizontal gap between nodes in the same layer
+    pub node_gap: f64,
+    /// Vertical gap between layers
+    pub layer_gap: f64,
+    /// Extra horizontal gap between clusters
+    pub cluster_gap: f64,
+    /// Number of barycenter sweeps for crossing reduction
+    pub sweeps: usize,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        Self {
+            group_by: LayoutGrouping::None,
+            node_width: 180.0,
+            node_height: 48.0,
+            node_gap: 24.0,
+            layer_gap: 72.0,
+            cluster_gap: 48.0,
+            sweeps: 8,
+        }
+    }
+}
+
+/// A point in layout coordinates
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Position of a node (center coordinates)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodePosition {
+    pub id: String,
+    pub x: f64,
+    pub y: f64,
+    pub layer: usize,
+    /// Index within the layer, left to right
+    pub order: usize,
+}
+
+/// Route of an edge from source to target, including bend points
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeRoute {
+    pub source: String,
+    pub target: String,
+    pub points: Vec<Point>,
+    /// Set when the edge was reversed to break a cycle
+    pub reversed: bool,
+}
+
+/// Bounding box of a cluster of nodes sharing a tag or status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutCluster {
+    pub key: String,
+    pub node_ids: Vec<String>,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Computed layout for a dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphLayout {
+    pub nodes: Vec<NodePosition>,
+    pub edges: Vec<EdgeRoute>,
+    #[serde(default)]
+    pub clusters: Vec<LayoutCluster>,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Compute a layered layout for a graph
+pub fn compute_layout(graph: &DependencyGraph, options: &LayoutOptions) -> GraphLayout {
+    let node_count = graph.nodes.len();
+    let index: HashMap<&str, usize> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.id.as_str(), i))
+        .collect();
+
+    // Edges by node index, skipping self loops and unknown endpoints
+    let (edges, direct_edges): (Vec<_>, Vec<_>) = graph
+        .edges
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| {
+            let source = *index.get(e.source.as_str())?;
+            let target = *index.get(e.target.as_str())?;
+            (source != target).then_some((i, source, target))
+        })
+        .partition(|&(i, _, _)| LAYERED_EDGE_TYPES.contains(&graph.edges[i].edge_type.as_str()));
+
+    let reversed = break_cycles(node_count, &edges);
+    let dag: Vec<(usize, usize)> = edges
+        .iter()
+        .zip(&reversed)
+        .map(|(&(_, s, t), &rev)| if rev { (t, s) } else { (s, t) })
+        .collect();
+    let layers = assign_layers(node_count, &dag);
+
+    // Group ranks keep clusters contiguous within each layer
+    let keys: Vec<String> = graph
+        .nodes
+        .iter()
+        .map(|n| group_key(n, options.group_by))
+        .collect();
+    let group_names: Vec<String> = keys
+        .iter()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let group_rank: HashMap<&str, usize> = group_names
+        .iter()
+        .enumerate()
+        .map(|(i, k)| (k.as_str(), i))
+        .collect();
+
+    // Virtual graph: real nodes followed by dummy nodes on long edges
+    let mut layer_of = layers.clone();
+    let mut group_of: Vec<usize> = keys.iter().map(|k| group_rank[k.as_str()]).collect();
+    let mut succ: Vec<Vec<usize>> = vec![Vec::new(); node_count];
+    let mut pred: Vec<Vec<usize>> = vec![Vec::new(); node_count];
+    let mut chains: Vec<Vec<usize>> = Vec::with_capacity(dag.len());
+
+    for &(source, target) in &dag {
+        let mut chain = vec![source];
+        let mut previous = source;
+        for layer in layers[source] + 1..layers[target] {
+            let dummy = layer_of.len();
+            layer_of.push(layer);
+            group_of.push(group_of[source]);
+            succ.push(Vec::new());
+            pred.push(Vec::new());
+            succ[previous].push(dummy);
+            pred[dummy].push(previous);
+            chain.push(dummy);
+            previous = dummy;
+        }
+        succ[previous].push(target);
+        pred[target].push(previous);
+        chain.push(target);
+        chains.push(chain);
+    }
+
+    let order = reduce_crossings(&layer_of, &group_of, &succ, &pred, options.sweeps);
+
+    // Each group gets a horizontal band wide enough for its fullest layer
+    let slot = options.node_width + options.node_gap;
+    let mut band_slots = vec![0usize; group_names.len().max(1)];
+    for layer in &order {
+        let mut counts = vec![0usize; band_slots.len()];
+        for &v in layer {
+            counts[group_of[v]] += 1;
+        }
+        for (band, count) in band_slots.iter_mut().zip(counts) {
+            *band = (*band).max(count);
+        }
+    }
+    let mut band_start = Vec::with_capacity(band_slots.len());
+    let mut cursor = 0.0;
+    for &slots in &band_slots {
+        band_start.push(cursor);
+        if slots > 0 {
+            cursor += slots as f64 * slot + options.cluster_gap;
+        }
+    }
+    let width = (cursor - options.cluster_gap - options.node_gap).max(0.0);
+
+    let layer_height = options.node_height + options.layer_gap;
+    let mut position = vec![Point { x: 0.0, y: 0.0 }; layer_of.len()];
+    let mut order_in_layer = vec![0usize; layer_of.len()];
+    for (layer_index, layer) in order.iter().enumerate() {
+        let mut counts = vec![0usize; band_slots.len()];
+        for &v in layer {
+            counts[group_of[v]] += 1;
+        }
+        let mut placed = vec![0usize; band_slots.len()];
+        for (i, &v) in layer.iter().enumerate() {
+            let group = group_of[v];
+            let offset = (band_slots[group] - counts[group]) as f64 * slot / 2.0;
+            position[v] = Point {
+                x: band_start[group]
+                    + offset
+                    + placed[group] as f64 * slot
+                    + options.node_width / 2.0,
+                y: layer_index as f64 * layer_height + options.node_height / 2.0,
+            };
+            placed[group] += 1;
+            order_in_layer[v] = i;
+        }
+    }
+    let height = if order.is_empty() {
+        0.0
+    } else {
+        order.len() as f64 * layer_height - options.layer_gap
+    };
+
+    let nodes = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| NodePosition {
+            id: n.id.clone(),
+            x: position[i].x,
+            y: position[i].y,
+            layer: layer_of[i],
+            order: order_in_layer[i],
+        })
+        .collect();
+
+    let half_height = options.node_height / 2.0;
+    let mut edge_routes: Vec<(usize, EdgeRoute)> = edges
+        .iter()
+        .zip(&reversed)
+        .zip(&chains)
+        .map(|((&(edge_index, _, _), &rev), chain)| {
+            let last = chain.len() - 1;
+            let mut points: Vec<Point> = chain
+                .iter()
+                .enumerate()
+                .map(|(i, &v)| {
+                    let p = position[v];
+                    match i {
+                        0 => Point {
+                            x: p.x,
+                            y: p.y + half_height,
+                        },
+                        i if i == last => Point {
+                            x: p.x,
+                            y: p.y - half_height,
+                        },
+                        _ => p,
+                    }
+                })
+                .collect();
+            if rev {
+                points.reverse();
+            }
+            let edge = &graph.edges[edge_index];
+            let route = EdgeRoute {
+                source: edge.source.clone(),
+                target: edge.target.clone(),
+                points,
+                reversed: rev,
+            };
+            (edge_index, route)
+        })
+        .collect();
+    for &(edge_index, source, target) in &direct_edges {
+        let edge = &graph.edges[edge_index];
+        let route = EdgeRoute {
+            source: edge.source.clone(),
+            target: edge.target.clone(),
+            points: direct_route(position[source], position[target], options),
+            reversed: false,
+        };
+        edge_routes.push((edge_index, route));
+    }
+    edge_routes.sort_by_key(|(edge_index, _)| *edge_index);
+
+    let clusters = if options.group_by == LayoutGrouping::None {
+        Vec::new()
+    } else {
+        build_clusters(graph, &keys, &position, options)
+    };
+
+    GraphLayout {
+        nodes,
+        edges: edge_routes.into_iter().map(|(_, route)| route).collect(),
+        clusters,
+        width,
+        height,
+    }
+}
+
+/// Straight route between two placed nodes, leaving from the facing sides
+fn direct_route(from: Point, to: Point, options: &LayoutOptions) -> Vec<Point> {
+    let half_width = options.node_width / 2.0;
+    let half_height = options.node_height / 2.0;
+    let (dx, dy) = match from.y.total_cmp(&to.y) {
+        Ordering::Less => (0.0, half_height),
+        Ordering::Greater => (0.0, -half_height),
+        Ordering::Equal if from.x < to.x => (half_width, 0.0),
+        Ordering::Equal => (-half_width, 0.0),
+    };
+    vec![
+        Point {
+            x: from.x + dx,
+            y: from.y + dy,
+        },
+        Point {
+            x: to.x - dx,
+            y: to.y - dy,
+        },
+    ]
+}
+
+fn group_key(node: &DependencyNode, group_by: LayoutGrouping) -> String {
+    match group_by {
+        LayoutGrouping::None => String::new(),
+        LayoutGrouping::Tag => node
+            .tags
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "untagged".to_string()),
+        LayoutGrouping::Status => node.status.clone(),
+    }
+}
+
+/// Find edges to reverse so the graph becomes acyclic (back edges of a DFS)
+fn break_cycles(node_count: usize, edges: &[(usize, usize, usize)]) -> Vec<bool> {
+    let mut outgoing: Vec<Vec<(usize, usize)>> = vec![Vec::new(); node_count];
+    for (i, &(_, source, target)) in edges.iter().enumerate() {
+        outgoing[source].push((i, target));
+    }
+
+    // 0 = unvisited, 1 = on the DFS stack, 2 = done
+    let mut state = vec![0u8; node_count];
+    let mut reversed = vec![false; edges.len()];
+
+    for start in 0..node_count {
+        if state[start] != 0 {
+            continue;
+        }
+        state[start] = 1;
+        let mut stack = vec![(start, 0usize)];
+        while let Some((node, next)) = stack.last_mut() {
+            if let Some(&(edge, target)) = outgoing[*node].get(*next) {
+                *next += 1;
+                match state[target] {
+                    0 => {
+                        state[target] = 1;
+                        stack.push((target, 0));
+                    }
+                    1 => reversed[edge] = true,
+                    _ => {}
+                }
+            } else {
+                state[*node] = 2;
+                stack.pop();
+            }
+        }
+    }
+
+    reversed
+}
+
+/// Assign each node the length of the longest path reaching it
+fn assign_layers(node_count: usize, dag: &[(usize, usize)]) -> Vec<usize> {
+    let mut in_degree = vec![0usize; node_count];
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); node_count];
+    for &(source, target) in dag {
+        outgoing[source].push(target);
+        in_degree[target] += 1;
+    }
+
+    let mut layers = vec![0usize; node_count];
+    let mut queue: VecDeque<usize> = (0..node_count).filter(|&v| in_degree[v] == 0).collect();
+    while let Some(node) = queue.pop_front() {
+        for &target in &outgoing[node] {
+            layers[target] = layers[target].max(layers[node] + 1);
+            in_degree[target] -= 1;
+            if in_degree[target] == 0 {
+                queue.push_back(target);
+            }
+        }
+    }
+
+    layers
+}
+
+/// Order nodes within layers using alternating barycenter sweeps
+///
+/// Returns the ordering with the fewest crossings seen. Nodes stay sorted by
+/// group first so clusters remain contiguous.
+fn reduce_crossings(
+    layer_of: &[usize],
+    group_of: &[usize],
+    succ: &[Vec<usize>],
+    pred: &[Vec<usize>],
+    sweeps: usize,
+) -> Vec<Vec<usize>> {
+    let layer_count = layer_of.iter().max().map(|m| m + 1).unwrap_or(0);
+    let mut order: Vec<Vec<usize>> = vec![Vec::new(); layer_count];
+    for (v, &layer) in layer_of.iter().enumerate() {
+        order[layer].push(v);
+    }
+    for layer in &mut order {
+        layer.sort_by_key(|&v| group_of[v]);
+    }
+
+    let mut best = order.clone();
+    let mut best_crossings = count_crossings(&order, succ, layer_of.len());
+
+    for sweep in 0..sweeps {
+        if sweep % 2 == 0 {
+            for layer in 1..layer_count {
+                reorder_layer(&mut order, layer, layer - 1, pred, group_of);
+            }
+        } else {
+            for layer in (0..layer_count.saturating_sub(1)).rev() {
+                reorder_layer(&mut order, layer, layer + 1, succ, group_of);
+            }
+        }
+
+        let crossings = count_crossings(&order, succ, layer_of.len());
+        if crossings < best_crossings {
+            best = order.clone();
+            best_crossings = crossings;
+        }
+    }
+
+    best
+}
+
+/// Sort one layer by the barycenter of each node's neighbors in a fixed layer
+fn reorder_layer(
+    order: &mut [Vec<usize>],
+    layer: usize,
+    fixed: usize,
+    neighbors: &[Vec<usize>],
+    group_of: &[usize],
+) {
+    let fixed_position: HashMap<usize, usize> = order[fixed]
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (v, i))
+        .collect();
+
+    let mut keyed: Vec<(usize, f64, usize)> = order[layer]
+        .iter()
+        .enumerate()
+        .map(|(current, &v)| {
+            let positions: Vec<usize> = neighbors[v]
+                .iter()
+                .filter_map(|w| fixed_position.get(w).copied())
+                .collect();
+            let barycenter = if positions.is_empty() {
+                current as f64
+            } else {
+                positions.iter().sum::<usize>() as f64 / positions.len() as f64
+            };
+            (group_of[v], barycenter, v)
+        })
+        .collect();
+
+    keyed.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    order[layer] = keyed.into_iter().map(|(_, _, v)| v).collect();
+}
+
+/// Count edge crossings between every pair of adjacent layers
+///
+/// Segments are sorted by their upper end, so two segments cross exactly when
+/// their lower ends are inverted. Inversions are counted with a Fenwick tree
+/// over lower positions, keeping this at O(E log V) per sweep.
+fn count_crossings(order: &[Vec<usize>], succ: &[Vec<usize>], node_count: usize) -> usize {
+    let mut position = vec![0usize; node_count];
+    for layer in order {
+        for (i, &v) in layer.iter().enumerate() {
+            position[v] = i;
+        }
+    }
+
+    let mut crossings = 0;
+    for (layer_index, layer) in order.iter().enumerate() {
+        let mut segments: Vec<(usize, usize)> = layer
+            .iter()
+            .flat_map(|&v| succ[v].iter().map(move |&w| (v, w)))
+            .map(|(v, w)| (position[v], position[w]))
+            .collect();
+        segments.sort_unstable();
+
+        let next_len = order.get(layer_index + 1).map_or(0, Vec::len);
+        let mut tree = vec![0usize; next_len + 1];
+        for (seen, &(_, lower)) in segments.iter().enumerate() {
+            // Earlier segments ending at or left of this one don't cross it
+            let mut not_crossing = 0;
+            let mut i = lower + 1;
+            while i > 0 {
+                not_crossing += tree[i];
+                i &= i - 1;
+            }
+            crossings += seen - not_crossing;
+
+            let mut i = lower + 1;
+            while i < tree.len() {
+                tree[i] += 1;
+                i += i & i.wrapping_neg();
+            }
+        }
+    }
+    crossings
+}
+
+fn build_clusters(
+    graph: &DependencyGraph,
+    keys: &[String],
+    position: &[Point],
+    options: &LayoutOptions,
+) -> Vec<LayoutCluster> {
+    let padding = options.node_gap / 2.0;
+    let mut clusters: Vec<LayoutCluster> = Vec::new();
+
+    for (i, node) in graph.nodes.iter().enumerate() {
+        let left = position[i].x - options.node_width / 2.0 - padding;
+        let top = position[i].y - options.node_height / 2.0 - padding;
+        let right = position[i].x + options.node_width / 2.0 + padding;
+        let bottom = position[i].y + options.node_height / 2.0 + padding;
+
+        match clusters.iter_mut().find(|c| c.key == keys[i]) {
+            Some(cluster) => {
+                let cluster_right = (cluster.x + cluster.width).max(right);
+                let cluster_bottom = (cluster.y + cluster.height).max(bottom);
+                cluster.x = cluster.x.min(left);
+                cluster.y = cluster.y.min(top);
+                cluster.width = cluster_right - cluster.x;
+                cluster.height = cluster_bottom - cluster.y;
+                cluster.node_ids.push(node.id.clone());
+            }
+            None => clusters.push(LayoutCluster {
+                key: keys[i].clone(),
+                node_ids: vec![node.id.clone()],
+                x: left,
+                y: top,
+                width: right - left,
+                height: bottom - top,
+            }),
+        }
+    }
+
+    clusters.sort_by(|a, b| a.key.cmp(&b.key));
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::dependencies::DependencyEdge;
+
+    fn node(id: &str, status: &str, tags: Vec<&str>) -> DependencyNode {
+        DependencyNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            number: 0,
+            status: status.to_string(),
+            priority: "medium".to_string(),
+            tags: tags.into_iter().map(String::from).collect(),
+            project_id: None,
+            project_name: None,
+        }
+    }
+
+    fn edge(source: &str, target: &str) -> DependencyEdge {
+        typed_edge(source, target, "dependsOn")
+    }
+
+    fn typed_edge(source: &str, target: &str, edge_type: &str) -> DependencyEdge {
+        DependencyEdge {
+            source: source.to_string(),
+            target: target.to_string(),
+            edge_type: edge_type.to_string(),
+        }
+    }
+
+    fn position<'a>(layout: &'a GraphLayout, id: &str) -> &'a NodePosition {
+        layout.nodes.iter().find(|n| n.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_layers_and_bend_points() {
+        let graph = DependencyGraph {
+            nodes: vec![
+                node("a", "complete", vec![]),
+                node("b", "planned", vec![]),
+                node("c", "planned", vec![]),
+            ],
+            edges: vec![edge("a", "b"), edge("b", "c"), edge("a", "c")],
+            layout: None,
+        };
+
+        let layout = compute_layout(&graph, &LayoutOptions::default());
+
+        assert_eq!(position(&layout, "a").layer, 0);
+        assert_eq!(position(&layout, "b").layer, 1);
+        assert_eq!(position(&layout, "c").layer, 2);
+
+        // a -> c spans two layers, so it bends through one virtual node
+        let long_edge = layout
+            .edges
+            .iter()
+            .find(|e| e.source == "a" && e.target == "c")
+            .unwrap();
+        assert_eq!(long_edge.points.len(), 3);
+        assert!(long_edge.points[0].y < long_edge.points[2].y);
+    }
+
+    #[test]
+    fn test_cycle_is_broken() {
+        let graph = DependencyGraph {
+            nodes: vec![node("a", "planned", vec![]), node("b", "planned", vec![])],
+            edges: vec![edge("a", "b"), edge("b", "a")],
+            layout: None,
+        };
+
+        let layout = compute_layout(&graph, &LayoutOptions::default());
+
+        assert_eq!(layout.edges.iter().filter(|e| e.reversed).count(), 1);
+        assert_ne!(position(&layout, "a").layer, position(&layout, "b").layer);
+    }
+
+    #[test]
+    fn test_only_dependency_edges_assign_layers() {
+        let graph = DependencyGraph {
+            nodes: vec![
+                node("a", "planned", vec![]),
+                node("b", "planned", vec![]),
+                node("c", "planned", vec![]),
+            ],
+            edges: vec![
+                typed_edge("a", "b", "related"),
+                typed_edge("a", "c", "blocks"),
+            ],
+            layout: None,
+        };
+
+        let layout = compute_layout(&graph, &LayoutOptions::default());
+
+        assert_eq!(position(&layout, "a").layer, 0);
+        assert_eq!(position(&layout, "b").layer, 0);
+        assert_eq!(position(&layout, "c").layer, 1);
+
+        // The related edge is still routed, in the original edge order
+        assert_eq!(layout.edges.len(), 2);
+        assert_eq!(layout.edges[0].target, "b");
+        assert_eq!(layout.edges[0].points.len(), 2);
+    }
+
+    #[test]
+    fn test_count_crossings() {
+        // 0 -> 3 and 1 -> 2 cross; 0 -> 2 crosses 1 -> 2 only at the shared end
+        let order = vec![vec![0, 1], vec![2, 3]];
+        let succ = vec![vec![3, 2], vec![2], vec![], vec![]];
+
+        assert_eq!(count_crossings(&order, &succ, 4), 1);
+    }
+
+    #[test]
+    fn test_crossing_reduction() {
+        // Two parallel chains declared in crossing order
+        let graph = DependencyGraph {
+            nodes: vec![
+                node("a1", "planned", vec![]),
+                node("b1", "planned", vec![]),
+                node("b2", "planned", vec![]),
+                node("a2", "planned", vec![]),
+            ],
+            edges: vec![edge("a1", "a2"), edge("b1", "b2")],
+            layout: None,
+        };
+
+        let layout = compute_layout(&graph, &LayoutOptions::default());
+
+        let a1 = position(&layout, "a1").order;
+        let a2 = position(&layout, "a2").order;
+        let b1 = position(&layout, "b1").order;
+        let b2 = position(&layout, "b2").order;
+        assert_eq!(a1 < b1, a2 < b2);
+    }
+
+    #[test]
+    fn test_grouping_clusters_do_not_overlap() {
+        let graph = DependencyGraph {
+            nodes: vec![
+                node("a", "planned", vec!["ui"]),
+                node("b", "planned", vec!["api"]),
+                node("c", "planned", vec!["ui"]),
+                node("d", "planned", vec!["api"]),
+            ],
+            edges: vec![edge("a", "d"), edge("b", "c")],
+            layout: None,
+        };
+        let options = LayoutOptions {
+            group_by: LayoutGrouping::Tag,
+            ..Default::default()
+        };
+
+        let layout = compute_layout(&graph, &options);
+
+        assert_eq!(layout.clusters.len(), 2);
+        let api = &layout.clusters[0];
+        let ui = &layout.clusters[1];
+        assert_eq!(api.key, "api");
+        assert_eq!(ui.node_ids, vec!["a", "c"]);
+        assert!(api.x + api.width <= ui.x);
+    }
+}
@@ -0,0 +1,393 @@
+//! Automatic fixes for validation issues
+//!
+//! Only fixes whose outcome is unambiguous are applied: a missing status
+//! becomes `planned`, mis-cased status and priority values are normalized,
+//! empty `depends_on` entries are dropped, references to a renumbered spec
+//! follow its slug, and a missing overview section gets a stub. Every fix
+//! comes with a unified diff so it can be previewed before writing, and a
+//! hash so exactly the previewed change is applied.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use similar::TextDiff;
+
+use crate::specs::collisions::spec_slug;
+use crate::specs::constants::{VALID_PRIORITIES, VALID_STATUSES};
+use crate::specs::dependencies::{parse_external_reference, SpecLookup};
+use crate::specs::frontmatter::{parse_source_map, set_frontmatter_field, set_frontmatter_list};
+use crate::specs::reader::Spec;
+use crate::specs::relations::RelationKind;
+use crate::specs::settings::ValidationSettings;
+use crate::specs::validation::ValidationResult;
+
+/// A fix applied to a spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedFix {
+    /// Code of the issue the fix resolves
+    pub code: String,
+    pub description: String,
+}
+
+/// Fixes for one spec and the resulting change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecFix {
+    pub spec_name: String,
+    pub fixes: Vec<AppliedFix>,
+    /// Unified diff of the spec's README.md
+    pub diff: String,
+    /// Hash of the README and its fixed content; pass it back to apply this
+    /// exact change
+    pub content_hash: String,
+    #[serde(skip)]
+    pub content: String,
+}
+
+/// Fixes for a spec or a whole project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixReport {
+    /// Whether the fixes were written, or only previewed
+    pub applied: bool,
+    pub specs: Vec<SpecFix>,
+}
+
+/// Fix the issues found in a spec
+///
+/// `content` is the spec's README.md and `specs` every spec of the project,
+/// used to find renamed dependencies. A fix that cannot be written is
+/// skipped. Returns `None` when nothing can be fixed safely.
+pub fn fix_spec(
+    spec: &Spec,
+    content: &str,
+    result: &ValidationResult,
+    specs: &[Spec],
+    settings: &ValidationSettings,
+) -> Option<SpecFix> {
+    let has_issue = |code: &str| result.issues.iter().any(|issue| issue.code == code);
+    let mut fixes = Vec::new();
+    let mut updated = content.to_string();
+    let mut depends_on = spec.depends_on.clone();
+
+    if has_issue("missing-status") {
+        let next = if parse_source_map(&updated).frontmatter.is_some() {
+            set_frontmatter_field(&updated, "status", "planned").ok()
+        } else {
+            Some(format!("---\nstatus: planned\n---\n\n{}", updated))
+        };
+        if let Some(next) = next {
+            updated = next;
+            fixes.push(fix("missing-status", "Set status to planned"));
+        }
+    }
+
+    if has_issue("invalid-status") {
+        let allowed = VALID_STATUSES.to_vec();
+        let normalized = normalized_value(&updated, "status", &allowed);
+        if let Some((from, to, next)) = normalized.and_then(|(from, to)| {
+            let next = set_frontmatter_field(&updated, "status", &to).ok()?;
+            Some((from, to, next))
+        }) {
+            updated = next;
+            fixes.push(fix(
+                "invalid-status",
+                format!("Changed status '{}' to '{}'", from, to),
+            ));
+        }
+    }
+
+    if has_issue("invalid-priority") {
+        let allowed = settings
+            .params("invalid-priority")
+            .strings("allowed")
+            .unwrap_or_else(|| VALID_PRIORITIES.to_vec());
+        let normalized = normalized_value(&updated, "priority", &allowed);
+        if let Some((from, to, next)) = normalized.and_then(|(from, to)| {
+            let next = set_frontmatter_field(&updated, "priority", &to).ok()?;
+            Some((from, to, next))
+        }) {
+            updated = next;
+            fixes.push(fix(
+                "invalid-priority",
+                format!("Changed priority '{}' to '{}'", from, to),
+            ));
+        }
+    }
+
+    if has_issue("empty-dependency") {
+        let kept: Vec<String> = depends_on
+            .iter()
+            .filter(|dep| !dep.trim().is_empty())
+            .cloned()
+            .collect();
+        let key = field_key(&updated, RelationKind::DependsOn.field_name());
+        if let Ok(next) = set_frontmatter_list(&updated, &key, &kept) {
+            updated = next;
+            depends_on = kept;
+            fixes.push(fix("empty-dependency", "Removed empty depends_on entries"));
+        }
+    }
+
+    let lookup = SpecLookup::new(specs);
+    for kind in RelationKind::ALL {
+        if !has_issue(kind.broken_code()) {
+            continue;
+        }
+        let references: Vec<&str> = match kind {
+            RelationKind::DependsOn => depends_on.iter().map(String::as_str).collect(),
+            _ => spec.references(kind),
+        };
+        let mut renamed = Vec::new();
+        let values: Vec<String> = references
+            .iter()
+            .map(|reference| match renamed_spec(reference, specs, &lookup) {
+                Some(target) => {
+                    renamed.push((reference.to_string(), target.clone()));
+                    target
+                }
+                None => reference.to_string(),
+            })
+            .collect();
+        if renamed.is_empty() {
+            continue;
+        }
+
+        let key = field_key(&updated, kind.field_name());
+        let next = match kind {
+            RelationKind::Parent => set_frontmatter_field(&updated, &key, &values[0]),
+            _ => set_frontmatter_list(&updated, &key, &values),
+        };
+        let Ok(next) = next else {
+            continue;
+        };
+        updated = next;
+        for (from, to) in renamed {
+            fixes.push(fix(
+                kind.broken_code(),
+                format!("Changed {} '{}' to '{}'", kind.field_name(), from, to),
+            ));
+        }
+    }
+
+    if has_issue("missing-overview") {
+        let section = settings
+            .params("missing-overview")
+            .string("section")
+            .unwrap_or("Overview");
+        updated = insert_section(&updated, section);
+        fixes.push(fix(
+            "missing-overview",
+            format!("Added an ## {} section", section),
+        ));
+    }
+
+    if fixes.is_empty() || updated == content {
+        return None;
+    }
+
+    let path = format!("{}/README.md", spec.spec_name);
+    let diff = TextDiff::from_lines(content, &updated)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string();
+
+    Some(SpecFix {
+        spec_name: spec.spec_name.clone(),
+        fixes,
+        diff,
+        content_hash: fix_hash(content, &updated),
+        content: updated,
+    })
+}
+
+/// Hash identifying a change from `original` to `updated`
+fn fix_hash(original: &str, updated: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(original.as_bytes());
+    hasher.update([0]);
+    hasher.update(updated.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+fn fix(code: &str, description: impl Into<String>) -> AppliedFix {
+    AppliedFix {
+        code: code.to_string(),
+        description: description.into(),
+    }
+}
+
+/// Key as written in the file, so `dependsOn` is not duplicated as `depends_on`
+fn field_key(content: &str, field: &str) -> String {
+    parse_source_map(content)
+        .field(field)
+        .map(|f| f.key.clone())
+        .unwrap_or_else(|| field.to_string())
+}
+
+/// Current value of a field and the allowed value it differs from only in
+/// case or separators (`In Progress` → `in-progress`, `HIGH` → `high`)
+fn normalized_value(content: &str, field: &str, allowed: &[&str]) -> Option<(String, String)> {
+    let current = parse_source_map(content).field(field)?.value.clone()?.value;
+    let normalized = current.trim().to_lowercase().replace([' ', '_'], "-");
+    allowed
+        .iter()
+        .find(|value| value.to_lowercase() == normalized)
+        .filter(|value| **value != current)
+        .map(|value| (current, value.to_string()))
+}
+
+/// The spec a broken reference most likely means: the only spec with the
+/// same slug under a different number
+fn renamed_spec(reference: &str, specs: &[Spec], lookup: &SpecLookup) -> Option<String> {
+    let reference = reference.trim();
+    if reference.is_empty()
+        || parse_external_reference(reference).is_some()
+        || lookup.find(reference).is_some()
+    {
+        return None;
+    }
+
+    let slug = spec_slug(reference);
+    if slug.is_empty() {
+        return None;
+    }
+    let mut candidates = specs.iter().filter(|s| spec_slug(&s.spec_name) == slug);
+    match (candidates.next(), candidates.next()) {
+        (Some(spec), None) => Some(spec.spec_name.clone()),
+        _ => None,
+    }
+}
+
+/// Insert a stub section before the first `##` heading, or at the end
+///
+/// The stub has no TODO marker, so fixing one issue does not raise a
+/// `placeholder-marker` finding.
+fn insert_section(content: &str, section: &str) -> String {
+    let stub = format!(
+        "## {}\n\n<!-- Describe the problem and the goal -->\n",
+        section
+    );
+    let source = parse_source_map(content);
+    let first_section = source.headings.iter().find(|h| h.level >= 2);
+
+    match first_section {
+        Some(heading) => {
+            let mut lines: Vec<&str> = content.split_inclusive('\n').collect();
+            let index = (heading.span.line - 1) as usize;
+            let stub = format!("{}\n", stub);
+            lines.insert(index, &stub);
+            lines.concat()
+        }
+        None => {
+            let body = content.trim_end_matches('\n');
+            format!("{}\n\n{}", body, stub)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::reader::SpecBuilder;
+    use crate::specs::validation::{validate_all_specs, ValidationOptions};
+
+    fn spec(name: &str, content: &str) -> Spec {
+        let (frontmatter, _) = crate::specs::frontmatter::parse_frontmatter(content);
+        Spec {
+            title: crate::specs::frontmatter::extract_title(content),
+            priority: frontmatter.priority.clone(),
+            ..SpecBuilder::new(name)
+                .status(frontmatter.status_or_default())
+                .content(content)
+                .depends_on(frontmatter.depends_on.clone())
+                .build()
+        }
+    }
+
+    fn fix_all(specs: &[Spec]) -> Vec<SpecFix> {
+        let options = ValidationOptions::default();
+        validate_all_specs(specs, &Default::default(), &options)
+            .iter()
+            .zip(specs)
+            .filter_map(|(result, spec)| {
+                fix_spec(spec, &spec.content_md, result, specs, &options.settings)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fix_frontmatter_issues() {
+        let specs = vec![
+            spec(
+                "001-first",
+                "---\nstatus: In Progress\npriority: HIGH\ndepends_on:\n  - ''\n  - 012-user-auth\n---\n\n# First\n\n## Overview\n\nText\n",
+            ),
+            spec(
+                "045-user-auth",
+                "---\nstatus: planned\n---\n\n# Auth\n\n## Overview\n",
+            ),
+        ];
+
+        let fixes = fix_all(&specs);
+        assert_eq!(fixes.len(), 1);
+        let fixed = &fixes[0];
+        let codes: Vec<&str> = fixed.fixes.iter().map(|f| f.code.as_str()).collect();
+        assert_eq!(
+            codes,
+            vec![
+                "invalid-status",
+                "invalid-priority",
+                "empty-dependency",
+                "broken-dependency"
+            ]
+        );
+        assert_eq!(
+            fixed.content,
+            "---\nstatus: in-progress\npriority: high\ndepends_on:\n  - 045-user-auth\n---\n\n# First\n\n## Overview\n\nText\n"
+        );
+        assert!(fixed
+            .diff
+            .starts_with("--- a/001-first/README.md\n+++ b/001-first/README.md\n"));
+        assert!(fixed.diff.contains("\n-status: In Progress\n"));
+        assert!(fixed.diff.contains("\n+status: in-progress\n"));
+
+        // The hash changes with the file, so a stale preview is detectable
+        assert_eq!(fix_all(&specs)[0].content_hash, fixed.content_hash);
+        let mut edited = specs.clone();
+        edited[0].content_md.push_str("More text\n");
+        assert_ne!(fix_all(&edited)[0].content_hash, fixed.content_hash);
+    }
+
+    #[test]
+    fn test_fix_missing_status_and_overview() {
+        let specs = vec![spec("001-bare", "# Bare\n\n## Design\n\nText\n")];
+
+        let fixes = fix_all(&specs);
+        assert_eq!(
+            fixes[0].content,
+            "---\nstatus: planned\n---\n\n# Bare\n\n## Overview\n\n<!-- Describe the problem and the goal -->\n\n## Design\n\nText\n"
+        );
+
+        // The fixed spec has no issues left
+        let fixed = vec![spec("001-bare", &fixes[0].content)];
+        let results = validate_all_specs(&fixed, &Default::default(), &Default::default());
+        assert!(results[0].issues.is_empty(), "{:?}", results[0].issues);
+    }
+
+    #[test]
+    fn test_no_fix_for_ambiguous_issues() {
+        let specs =
+            vec![
+            spec(
+                "001-first",
+                "---\nstatus: finished\ndepends_on: [099-auth]\n---\n\n# First\n\n## Overview\n",
+            ),
+            spec("002-auth", "---\nstatus: planned\n---\n\n# A\n\n## Overview\n"),
+            spec("003-auth", "---\nstatus: planned\n---\n\n# B\n\n## Overview\n"),
+        ];
+
+        assert!(fix_all(&specs).is_empty());
+    }
+}
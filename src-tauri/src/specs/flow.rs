@@ -0,0 +1,495 @@
+//! Flow metrics from status transitions
+//!
+//! Derives lead time (created → complete), cycle time (first in-progress →
+//! complete), weekly throughput and time spent in each status. Timelines come
+//! from the `transitions` recorded in frontmatter, falling back to the spec
+//! file's git history when none are recorded.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::specs::frontmatter::parse_frontmatter;
+use crate::specs::git::StatusChange;
+use crate::specs::reader::Spec;
+
+/// Where a spec's status timeline was reconstructed from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimelineSource {
+    Transitions,
+    Git,
+    /// Only the creation date and current status are known
+    None,
+}
+
+/// A spec's status history, oldest first
+#[derive(Debug, Clone)]
+pub struct SpecTimeline {
+    pub spec_name: String,
+    pub title: Option<String>,
+    pub status: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub events: Vec<StatusChange>,
+    pub source: TimelineSource,
+}
+
+impl SpecTimeline {
+    /// Build a timeline from frontmatter transitions, or from git history
+    ///
+    /// `git_history` is only invoked when no transitions are recorded.
+    pub fn for_spec<F>(spec: &Spec, git_history: F) -> Self
+    where
+        F: FnOnce() -> Vec<StatusChange>,
+    {
+        let (frontmatter, _) = parse_frontmatter(&spec.content_md);
+        let mut events = Vec::new();
+        let mut source = TimelineSource::Transitions;
+
+        if !frontmatter.transitions.is_empty() {
+            if let (Some(first), Some(created)) = (frontmatter.transitions.first(), spec.created_at)
+            {
+                events.push(StatusChange {
+                    at: created,
+                    status: first.from.clone(),
+                });
+            }
+            for transition in &frontmatter.transitions {
+                if let Ok(at) = DateTime::parse_from_rfc3339(&transition.at) {
+                    events.push(StatusChange {
+                        at: at.with_timezone(&Utc),
+                        status: transition.to.clone(),
+                    });
+                }
+            }
+            events.sort_by_key(|e| e.at);
+        } else {
+            events = git_history();
+            source = TimelineSource::Git;
+        }
+
+        // Without a history only the creation and completion dates are known;
+        // a finished spec was not finished when it was created
+        if events.is_empty() {
+            source = TimelineSource::None;
+            let finished = matches!(spec.status.as_str(), "complete" | "archived");
+            if let Some(created) = spec.created_at {
+                events.push(StatusChange {
+                    at: created,
+                    status: if finished {
+                        "planned".to_string()
+                    } else {
+                        spec.status.clone()
+                    },
+                });
+            }
+            if let Some(completed) = spec.completed_at.filter(|_| finished) {
+                events.push(StatusChange {
+                    at: completed,
+                    status: "complete".to_string(),
+                });
+            }
+            events.sort_by_key(|e| e.at);
+        }
+
+        SpecTimeline {
+            spec_name: spec.spec_name.clone(),
+            title: spec.title.clone(),
+            status: spec.status.clone(),
+            created_at: spec.created_at,
+            events,
+            source,
+        }
+    }
+
+    /// Creation date, falling back to the first recorded event
+    fn started(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+            .or_else(|| self.events.first().map(|e| e.at))
+    }
+
+    /// First time the spec reached `status`
+    pub fn first_reached(&self, status: &str) -> Option<DateTime<Utc>> {
+        self.events
+            .iter()
+            .find(|e| e.status == status)
+            .map(|e| e.at)
+    }
+}
+
+/// Flow times for a single spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecFlow {
+    pub spec_name: String,
+    pub title: Option<String>,
+    pub status: String,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub lead_time_days: Option<f64>,
+    pub cycle_time_days: Option<f64>,
+    pub source: TimelineSource,
+}
+
+/// Distribution of durations, in days
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DurationSummary {
+    pub samples: usize,
+    pub mean_days: f64,
+    pub p50_days: f64,
+    pub p85_days: f64,
+    pub p95_days: f64,
+}
+
+/// Time spent in one status across specs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusDuration {
+    pub status: String,
+    #[serde(flatten)]
+    pub summary: DurationSummary,
+}
+
+/// Specs completed in a week (weeks start on Monday)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyThroughput {
+    pub week_start: NaiveDate,
+    pub completed: usize,
+}
+
+/// Flow metrics for a project over a date range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowMetrics {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Specs completed within the range
+    pub specs: Vec<SpecFlow>,
+    pub lead_time: DurationSummary,
+    pub cycle_time: DurationSummary,
+    pub throughput: Vec<WeeklyThroughput>,
+    pub time_in_status: Vec<StatusDuration>,
+}
+
+/// Calculate flow metrics over an optional date range
+///
+/// Lead and cycle times and throughput count specs completed within the
+/// range. Time in status clips each interval to the range; a spec's current
+/// status runs until `now` unless it is complete or archived.
+pub fn calculate_flow_metrics(
+    timelines: &[SpecTimeline],
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> FlowMetrics {
+    let in_range = |at: DateTime<Utc>| {
+        from.map(|f| at >= f).unwrap_or(true) && to.map(|t| at <= t).unwrap_or(true)
+    };
+
+    let mut specs = Vec::new();
+    let mut lead_times = Vec::new();
+    let mut cycle_times = Vec::new();
+    let mut completions: Vec<DateTime<Utc>> = Vec::new();
+
+    for timeline in timelines {
+        let Some(completed_at) = timeline.first_reached("complete") else {
+            continue;
+        };
+        if !in_range(completed_at) {
+            continue;
+        }
+
+        let lead_time = timeline
+            .started()
+            .filter(|s| *s <= completed_at)
+            .map(|s| days(completed_at - s));
+        let cycle_time = timeline
+            .first_reached("in-progress")
+            .filter(|s| *s <= completed_at)
+            .map(|s| days(completed_at - s));
+        lead_times.extend(lead_time);
+        cycle_times.extend(cycle_time);
+        completions.push(completed_at);
+
+        specs.push(SpecFlow {
+            spec_name: timeline.spec_name.clone(),
+            title: timeline.title.clone(),
+            status: timeline.status.clone(),
+            completed_at: Some(completed_at),
+            lead_time_days: lead_time.map(round),
+            cycle_time_days: cycle_time.map(round),
+            source: timeline.source,
+        });
+    }
+    specs.sort_by_key(|s| s.completed_at);
+
+    FlowMetrics {
+        from,
+        to,
+        specs,
+        lead_time: summarize(lead_times),
+        cycle_time: summarize(cycle_times),
+        throughput: weekly_throughput(&completions, from, to),
+        time_in_status: time_in_status(timelines, from, to.unwrap_or(now).min(now)),
+    }
+}
+
+fn time_in_status(
+    timelines: &[SpecTimeline],
+    from: Option<DateTime<Utc>>,
+    end: DateTime<Utc>,
+) -> Vec<StatusDuration> {
+    let mut durations: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+
+    for timeline in timelines {
+        for (i, event) in timeline.events.iter().enumerate() {
+            let until = match timeline.events.get(i + 1) {
+                Some(next) => next.at,
+                None if matches!(event.status.as_str(), "complete" | "archived") => continue,
+                None => end,
+            };
+            let start = from.map(|f| event.at.max(f)).unwrap_or(event.at);
+            let until = until.min(end);
+            if until > start {
+                durations
+                    .entry(event.status.clone())
+                    .or_default()
+                    .push(days(until - start));
+            }
+        }
+    }
+
+    durations
+        .into_iter()
+        .map(|(status, values)| StatusDuration {
+            status,
+            summary: summarize(values),
+        })
+        .collect()
+}
+
+fn weekly_throughput(
+    completions: &[DateTime<Utc>],
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Vec<WeeklyThroughput> {
+    let first = from.or_else(|| completions.iter().min().copied());
+    let last = to.or_else(|| completions.iter().max().copied());
+    let (Some(first), Some(last)) = (first, last) else {
+        return Vec::new();
+    };
+
+    let mut counts: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    let mut week = week_start(first);
+    while week <= week_start(last) {
+        counts.insert(week, 0);
+        week += Duration::weeks(1);
+    }
+    for completed in completions {
+        *counts.entry(week_start(*completed)).or_insert(0) += 1;
+    }
+
+    counts
+        .into_iter()
+        .map(|(week_start, completed)| WeeklyThroughput {
+            week_start,
+            completed,
+        })
+        .collect()
+}
+
+fn week_start(at: DateTime<Utc>) -> NaiveDate {
+    let date = at.date_naive();
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn days(duration: Duration) -> f64 {
+    duration.num_seconds() as f64 / 86_400.0
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Summarize durations with nearest-rank percentiles
+fn summarize(mut values: Vec<f64>) -> DurationSummary {
+    if values.is_empty() {
+        return DurationSummary::default();
+    }
+    values.sort_by(f64::total_cmp);
+
+    let percentile = |p: f64| {
+        let rank = ((p / 100.0) * values.len() as f64).ceil() as usize;
+        round(values[rank.clamp(1, values.len()) - 1])
+    };
+
+    DurationSummary {
+        samples: values.len(),
+        mean_days: round(values.iter().sum::<f64>() / values.len() as f64),
+        p50_days: percentile(50.0),
+        p85_days: percentile(85.0),
+        p95_days: percentile(95.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::reader::SpecBuilder;
+
+    fn at(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&format!("{}T00:00:00Z", date))
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn spec(name: &str, status: &str, created: &str, content: &str) -> Spec {
+        SpecBuilder::new(name)
+            .status(status)
+            .content(content)
+            .created_at(at(created))
+            .build()
+    }
+
+    fn timeline(name: &str, events: &[(&str, &str)]) -> SpecTimeline {
+        SpecTimeline {
+            spec_name: name.to_string(),
+            title: None,
+            status: events.last().unwrap().1.to_string(),
+            created_at: Some(at(events[0].0)),
+            events: events
+                .iter()
+                .map(|(date, status)| StatusChange {
+                    at: at(date),
+                    status: status.to_string(),
+                })
+                .collect(),
+            source: TimelineSource::Transitions,
+        }
+    }
+
+    #[test]
+    fn test_timeline_from_transitions() {
+        let content = "---\nstatus: complete\ntransitions:\n  - from: planned\n    to: in-progress\n    at: '2024-01-03T00:00:00Z'\n  - from: in-progress\n    to: complete\n    at: '2024-01-08T00:00:00Z'\n---\n# Spec\n";
+        let spec = spec("001-a", "complete", "2024-01-01", content);
+
+        let timeline = SpecTimeline::for_spec(&spec, || panic!("git should not be used"));
+
+        assert_eq!(timeline.source, TimelineSource::Transitions);
+        let statuses: Vec<&str> = timeline.events.iter().map(|e| e.status.as_str()).collect();
+        assert_eq!(statuses, vec!["planned", "in-progress", "complete"]);
+    }
+
+    #[test]
+    fn test_timeline_falls_back_to_git() {
+        let spec = spec(
+            "001-a",
+            "planned",
+            "2024-01-01",
+            "---\nstatus: planned\n---\n",
+        );
+
+        let timeline = SpecTimeline::for_spec(&spec, Vec::new);
+        assert_eq!(timeline.source, TimelineSource::None);
+        assert_eq!(timeline.events.len(), 1);
+
+        let mut done = spec.clone();
+        done.status = "complete".to_string();
+        done.completed_at = Some(at("2024-01-09"));
+        let timeline = SpecTimeline::for_spec(&done, Vec::new);
+        assert_eq!(timeline.first_reached("planned"), Some(at("2024-01-01")));
+        assert_eq!(timeline.first_reached("complete"), Some(at("2024-01-09")));
+        done.completed_at = None;
+        let timeline = SpecTimeline::for_spec(&done, Vec::new);
+        assert_eq!(timeline.first_reached("complete"), None);
+
+        let timeline = SpecTimeline::for_spec(&spec, || {
+            vec![StatusChange {
+                at: at("2024-01-02"),
+                status: "planned".to_string(),
+            }]
+        });
+        assert_eq!(timeline.source, TimelineSource::Git);
+    }
+
+    #[test]
+    fn test_lead_and_cycle_time() {
+        let timelines = vec![
+            timeline(
+                "001-a",
+                &[
+                    ("2024-01-01", "planned"),
+                    ("2024-01-03", "in-progress"),
+                    ("2024-01-08", "complete"),
+                ],
+            ),
+            timeline(
+                "002-b",
+                &[
+                    ("2024-01-02", "planned"),
+                    ("2024-01-04", "in-progress"),
+                    ("2024-01-16", "complete"),
+                ],
+            ),
+            timeline(
+                "003-c",
+                &[("2024-01-05", "planned"), ("2024-01-06", "in-progress")],
+            ),
+        ];
+
+        let metrics = calculate_flow_metrics(&timelines, None, None, at("2024-01-20"));
+
+        assert_eq!(metrics.specs.len(), 2);
+        assert_eq!(metrics.specs[0].lead_time_days, Some(7.0));
+        assert_eq!(metrics.specs[0].cycle_time_days, Some(5.0));
+        assert_eq!(metrics.lead_time.samples, 2);
+        assert_eq!(metrics.lead_time.p50_days, 7.0);
+        assert_eq!(metrics.lead_time.p95_days, 14.0);
+        assert_eq!(metrics.cycle_time.mean_days, 8.5);
+
+        // Weeks of 2024-01-08 and 2024-01-15 each saw one completion
+        assert_eq!(metrics.throughput.len(), 2);
+        assert!(metrics.throughput.iter().all(|w| w.completed == 1));
+
+        // 003-c is still in progress, counted until now
+        let in_progress = metrics
+            .time_in_status
+            .iter()
+            .find(|s| s.status == "in-progress")
+            .unwrap();
+        assert_eq!(in_progress.summary.samples, 3);
+        assert_eq!(in_progress.summary.p95_days, 14.0);
+    }
+
+    #[test]
+    fn test_date_range() {
+        let timelines = vec![
+            timeline(
+                "001-a",
+                &[("2024-01-01", "planned"), ("2024-01-08", "complete")],
+            ),
+            timeline(
+                "002-b",
+                &[("2024-02-01", "planned"), ("2024-02-10", "complete")],
+            ),
+        ];
+
+        let metrics = calculate_flow_metrics(
+            &timelines,
+            Some(at("2024-02-01")),
+            Some(at("2024-02-29")),
+            at("2024-03-15"),
+        );
+
+        assert_eq!(metrics.specs.len(), 1);
+        assert_eq!(metrics.specs[0].spec_name, "002-b");
+        assert_eq!(
+            metrics.throughput.first().unwrap().week_start.to_string(),
+            "2024-01-29"
+        );
+        let planned = &metrics.time_in_status[0];
+        assert_eq!(planned.status, "planned");
+        assert_eq!(planned.summary.samples, 1);
+    }
+}
@@ -0,0 +1,219 @@
+//! Completion forecasting
+//!
+//! Forecasts when a set of specs will be complete with a Monte Carlo
+//! simulation: each trial draws weekly throughput samples from the
+//! project's recent history until the remaining specs are done.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Weeks of history sampled when not specified
+pub const DEFAULT_HISTORY_WEEKS: usize = 12;
+
+/// Number of simulated futures
+const TRIALS: usize = 10_000;
+
+/// Give up on a trial after this many weeks (about ten years)
+const MAX_WEEKS: u32 = 520;
+
+/// Seed used when the caller doesn't provide one, so results are stable
+const DEFAULT_SEED: u64 = 0x5eed_1ea5_5bec;
+
+/// Forecast completion after a number of weeks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastPoint {
+    pub weeks: u32,
+    pub date: NaiveDate,
+}
+
+/// Forecast for a set of specs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionForecast {
+    pub total_specs: usize,
+    /// Selected specs that are not yet complete or archived
+    pub remaining_specs: usize,
+    pub history_weeks: usize,
+    /// Completed specs per week in the sampled history
+    pub weekly_throughput: Vec<usize>,
+    pub average_weekly_throughput: f64,
+    pub trials: usize,
+    /// 50%, 85% and 95% confidence completion, when forecastable
+    pub p50: Option<ForecastPoint>,
+    pub p85: Option<ForecastPoint>,
+    pub p95: Option<ForecastPoint>,
+    /// Why no forecast could be made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+/// Forecast completion of `remaining` specs out of `total`
+///
+/// `completions` are the completion dates of every spec in the project; the
+/// last `history_weeks` full weeks before `now` are used as throughput samples.
+pub fn forecast_completion(
+    total: usize,
+    remaining: usize,
+    completions: &[DateTime<Utc>],
+    history_weeks: usize,
+    now: DateTime<Utc>,
+    seed: Option<u64>,
+) -> CompletionForecast {
+    let history_weeks = history_weeks.max(1);
+    let weekly_throughput = weekly_samples(completions, history_weeks, now);
+    let average = weekly_throughput.iter().sum::<usize>() as f64 / history_weeks as f64;
+
+    let mut forecast = CompletionForecast {
+        total_specs: total,
+        remaining_specs: remaining,
+        history_weeks,
+        weekly_throughput,
+        average_weekly_throughput: (average * 100.0).round() / 100.0,
+        trials: 0,
+        p50: None,
+        p85: None,
+        p95: None,
+        warning: None,
+    };
+
+    if remaining == 0 {
+        let done = ForecastPoint {
+            weeks: 0,
+            date: now.date_naive(),
+        };
+        forecast.p50 = Some(done.clone());
+        forecast.p85 = Some(done.clone());
+        forecast.p95 = Some(done);
+        return forecast;
+    }
+
+    if forecast.weekly_throughput.iter().all(|&n| n == 0) {
+        forecast.warning = Some(format!(
+            "No specs were completed in the last {} weeks",
+            history_weeks
+        ));
+        return forecast;
+    }
+
+    let mut rng = SplitMix64::new(seed.unwrap_or(DEFAULT_SEED));
+    let samples = &forecast.weekly_throughput;
+    let mut outcomes: Vec<u32> = (0..TRIALS)
+        .map(|_| {
+            let mut done = 0;
+            let mut weeks = 0;
+            while done < remaining && weeks < MAX_WEEKS {
+                done += samples[rng.next_below(samples.len())];
+                weeks += 1;
+            }
+            weeks
+        })
+        .collect();
+    outcomes.sort_unstable();
+
+    let point = |p: f64| {
+        let rank = ((p / 100.0) * outcomes.len() as f64).ceil() as usize;
+        let weeks = outcomes[rank.clamp(1, outcomes.len()) - 1];
+        ForecastPoint {
+            weeks,
+            date: (now + Duration::weeks(weeks as i64)).date_naive(),
+        }
+    };
+    forecast.trials = TRIALS;
+    forecast.p50 = Some(point(50.0));
+    forecast.p85 = Some(point(85.0));
+    forecast.p95 = Some(point(95.0));
+    forecast
+}
+
+/// Completions per week for the `weeks` weeks ending at `now`, oldest first
+fn weekly_samples(completions: &[DateTime<Utc>], weeks: usize, now: DateTime<Utc>) -> Vec<usize> {
+    let start = now - Duration::weeks(weeks as i64);
+    let mut samples = vec![0; weeks];
+    for completed in completions {
+        if *completed < start || *completed >= now {
+            continue;
+        }
+        let index = ((*completed - start).num_days() / 7) as usize;
+        samples[index.min(weeks - 1)] += 1;
+    }
+    samples
+}
+
+/// Small deterministic PRNG (SplitMix64)
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-06-03T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_weekly_samples() {
+        let completions = vec![
+            now() - Duration::days(1),
+            now() - Duration::days(2),
+            now() - Duration::days(10),
+            now() - Duration::days(100),
+        ];
+
+        assert_eq!(weekly_samples(&completions, 4, now()), vec![0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_forecast_is_ordered_and_deterministic() {
+        let completions: Vec<DateTime<Utc>> = (0..12)
+            .flat_map(|week| {
+                // Alternate between one and three completions per week
+                let count = if week % 2 == 0 { 1 } else { 3 };
+                (0..count).map(move |_| now() - Duration::weeks(week) - Duration::days(1))
+            })
+            .collect();
+
+        let forecast = forecast_completion(20, 10, &completions, 12, now(), None);
+        let again = forecast_completion(20, 10, &completions, 12, now(), None);
+
+        assert_eq!(forecast.average_weekly_throughput, 2.0);
+        let (p50, p85, p95) = (
+            forecast.p50.unwrap().weeks,
+            forecast.p85.unwrap().weeks,
+            forecast.p95.unwrap().weeks,
+        );
+        assert!((4..=10).contains(&p50));
+        assert!(p50 <= p85 && p85 <= p95);
+        assert_eq!(again.p95.unwrap().weeks, p95);
+    }
+
+    #[test]
+    fn test_forecast_without_history() {
+        let forecast = forecast_completion(3, 3, &[], 12, now(), None);
+        assert!(forecast.p50.is_none());
+        assert!(forecast.warning.is_some());
+
+        let done = forecast_completion(3, 0, &[], 12, now(), None);
+        assert_eq!(done.p95.unwrap().weeks, 0);
+    }
+}
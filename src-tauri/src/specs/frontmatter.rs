@@ -16,11 +16,25 @@ pub struct Frontmatter {
     pub tags: Vec<String>,
     pub assignee: Option<String>,
     pub created: Option<String>,
+    #[serde(alias = "created_at")]
     pub created_at: Option<String>,
+    #[serde(alias = "updated_at")]
     pub updated_at: Option<String>,
+    #[serde(alias = "completed_at")]
     pub completed_at: Option<String>,
-    #[serde(default)]
+    #[serde(default, alias = "depends_on")]
     pub depends_on: Vec<String>,
+    /// Specs this one is related to (symmetric, informational)
+    #[serde(default)]
+    pub related: Vec<String>,
+    /// Specs that cannot proceed until this one is done
+    #[serde(default)]
+    pub blocks: Vec<String>,
+    /// Specs this one replaces
+    #[serde(default)]
+    pub supersedes: Vec<String>,
+    /// Parent spec (umbrella/epic)
+    pub parent: Option<String>,
     #[serde(default)]
     pub transitions: Vec<StatusTransition>,
     /// Catch-all for unknown fields
@@ -70,13 +84,32 @@ impl Frontmatter {
     }
 }
 
+/// Why frontmatter could not be parsed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontmatterError {
+    pub message: String,
+    /// 1-based position in the file, when the YAML parser reports one
+    pub line: Option<i32>,
+    pub column: Option<i32>,
+}
+
 /// Parse frontmatter from markdown content
 /// 
-/// Returns (frontmatter, content_without_frontmatter)
+/// Returns (frontmatter, content_without_frontmatter). Invalid frontmatter is
+/// treated as missing; use `parse_frontmatter_checked` to get the error.
 pub fn parse_frontmatter(content: &str) -> (Frontmatter, String) {
+    parse_frontmatter_checked(content)
+        .unwrap_or_else(|_| (Frontmatter::default(), content.to_string()))
+}
+
+/// Parse frontmatter, reporting unclosed frontmatter and YAML errors
+///
+/// Content without frontmatter is not an error and yields defaults.
+pub fn parse_frontmatter_checked(content: &str) -> Result<(Frontmatter, String), FrontmatterError> {
     // Check if content starts with frontmatter delimiter
     if !content.starts_with("---") {
-        return (Frontmatter::default(), content.to_string());
+        return Ok((Frontmatter::default(), content.to_string()));
     }
 
     // Find the closing delimiter
@@ -92,18 +125,41 @@ pub fn parse_frontmatter(content: &str) -> (Frontmatter, String) {
         let markdown_content = rest[markdown_start..].trim_start_matches(['\n', '\r']);
         
         match serde_yaml::from_str::<Frontmatter>(yaml_content) {
-            Ok(frontmatter) => (frontmatter, markdown_content.to_string()),
+            Ok(frontmatter) => Ok((frontmatter, markdown_content.to_string())),
             Err(e) => {
-                eprintln!("Failed to parse frontmatter YAML: {}", e);
-                (Frontmatter::default(), content.to_string())
+                // The YAML starts on the line after the opening "---", so
+                // drop the parser's YAML-relative position from the message
+                let location = e.location();
+                let mut message = e.to_string();
+                if let Some(l) = &location {
+                    let position = format!(" at line {} column {}", l.line(), l.column());
+                    message = message.replacen(&position, "", 1);
+                }
+                Err(FrontmatterError {
+                    message,
+                    line: location.as_ref().map(|l| l.line() as i32 + 1),
+                    column: location.as_ref().map(|l| l.column() as i32),
+                })
             }
         }
     } else {
-        // No closing delimiter found
-        (Frontmatter::default(), content.to_string())
+        Err(FrontmatterError {
+            message: "Frontmatter has no closing '---'".to_string(),
+            line: Some(1),
+            column: Some(1),
+        })
     }
 }
 
+/// Frontmatter as JSON, including custom fields, for schema validation
+pub fn frontmatter_value(content: &str) -> Option<serde_json::Value> {
+    let rest = content.strip_prefix("---")?;
+    let rest = rest.strip_prefix('\n').unwrap_or(rest);
+    let rest = rest.strip_prefix("\r\n").unwrap_or(rest);
+    let end_pos = rest.find("\n---")?;
+    serde_yaml::from_str(&rest[..end_pos]).ok()
+}
+
 /// Extract the title from markdown content (first H1 heading)
 pub fn extract_title(content: &str) -> Option<String> {
     for line in content.lines() {
@@ -115,6 +171,332 @@ pub fn extract_title(content: &str) -> Option<String> {
     None
 }
 
+/// A range in a spec file
+///
+/// Lines and columns are 1-based and count characters; `end_column` is
+/// exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceSpan {
+    pub line: i32,
+    pub column: i32,
+    pub end_line: i32,
+    pub end_column: i32,
+}
+
+impl SourceSpan {
+    /// Span within a single line, from 0-based line index and char offsets
+    pub fn on_line(line: usize, start: usize, end: usize) -> Self {
+        Self {
+            line: line as i32 + 1,
+            column: start as i32 + 1,
+            end_line: line as i32 + 1,
+            end_column: end as i32 + 1,
+        }
+    }
+}
+
+/// A scalar in the frontmatter and where it is written
+#[derive(Debug, Clone)]
+pub struct ValueSpan {
+    /// Unquoted value
+    pub value: String,
+    pub span: SourceSpan,
+}
+
+/// A top-level frontmatter key with its inline value or list items
+#[derive(Debug, Clone)]
+pub struct FieldSpan {
+    pub key: String,
+    pub span: SourceSpan,
+    /// Value on the key's line, unless it is a flow list
+    pub value: Option<ValueSpan>,
+    /// Items of a block or flow list
+    pub items: Vec<ValueSpan>,
+}
+
+/// A Markdown heading outside code blocks
+#[derive(Debug, Clone)]
+pub struct HeadingSpan {
+    pub level: usize,
+    pub text: String,
+    pub span: SourceSpan,
+}
+
+/// Positions of frontmatter fields and Markdown headings in a spec file
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// Opening `---` line, when the file has frontmatter
+    pub frontmatter: Option<SourceSpan>,
+    pub fields: Vec<FieldSpan>,
+    pub headings: Vec<HeadingSpan>,
+}
+
+impl SourceMap {
+    /// Field by key; `depends_on` also matches `dependsOn`
+    pub fn field(&self, key: &str) -> Option<&FieldSpan> {
+        let normalize = |k: &str| k.replace('_', "").to_lowercase();
+        let key = normalize(key);
+        self.fields.iter().find(|f| normalize(&f.key) == key)
+    }
+
+    /// Span of a field's inline value, or of the key when there is none
+    pub fn value_span(&self, key: &str) -> Option<SourceSpan> {
+        let field = self.field(key)?;
+        Some(field.value.as_ref().map(|v| v.span).unwrap_or(field.span))
+    }
+
+    /// Span of a list item (or scalar value) equal to `value`
+    pub fn item_span(&self, key: &str, value: &str) -> Option<SourceSpan> {
+        let field = self.field(key)?;
+        field
+            .items
+            .iter()
+            .chain(field.value.as_ref())
+            .find(|item| item.value == value)
+            .map(|item| item.span)
+    }
+
+    /// First `##`-`######` heading whose text matches (case-insensitive)
+    pub fn section(&self, text: &str) -> Option<&HeadingSpan> {
+        self.headings
+            .iter()
+            .find(|h| h.level >= 2 && h.text.eq_ignore_ascii_case(text.trim()))
+    }
+}
+
+/// Map the positions of frontmatter keys, values, list items and headings
+///
+/// A line scanner over the simple YAML used in spec frontmatter; nested
+/// mappings are skipped rather than mapped.
+pub fn parse_source_map(content: &str) -> SourceMap {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut map = SourceMap::default();
+
+    let mut body_start = 0;
+    if lines.first().map(|l| l.trim_end() == "---").unwrap_or(false) {
+        if let Some(close) = lines.iter().skip(1).position(|l| l.trim_end() == "---") {
+            let close = close + 1;
+            map.frontmatter = Some(SourceSpan::on_line(0, 0, 3));
+            map.fields = scan_fields(&lines, 1, close);
+            body_start = close + 1;
+        }
+    }
+
+    let mut fence: Option<&str> = None;
+    for (index, line) in lines.iter().enumerate().skip(body_start) {
+        let trimmed = line.trim_start();
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            match fence {
+                Some(open) if open == marker => fence = None,
+                None => fence = Some(marker),
+                _ => {}
+            }
+            continue;
+        }
+        if fence.is_some() {
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|&c| c == '#').count();
+        if !(1..=6).contains(&level) || !trimmed[level..].starts_with(' ') {
+            continue;
+        }
+        let indent = line.chars().count() - trimmed.chars().count();
+        map.headings.push(HeadingSpan {
+            level,
+            text: trimmed[level..].trim().to_string(),
+            span: SourceSpan::on_line(index, indent, line.trim_end().chars().count()),
+        });
+    }
+
+    map
+}
+
+/// Scan top-level keys between the frontmatter delimiters
+fn scan_fields(lines: &[&str], start: usize, end: usize) -> Vec<FieldSpan> {
+    let mut fields: Vec<FieldSpan> = Vec::new();
+
+    for (index, line) in lines.iter().enumerate().take(end).skip(start) {
+        let trimmed = line.trim_start();
+        let indent = line.chars().count() - trimmed.chars().count();
+
+        // Block list item of the previous key
+        if let Some(item) = trimmed.strip_prefix('-') {
+            if item.is_empty() || item.starts_with(' ') {
+                if let Some(field) = fields.last_mut() {
+                    let offset = indent + 1;
+                    field.items.push(value_span(index, offset, item));
+                }
+                continue;
+            }
+        }
+
+        if indent > 0 || trimmed.starts_with('#') {
+            continue;
+        }
+        let Some(colon) = line.find(':') else {
+            continue;
+        };
+
+        let key = &line[..colon];
+        let key_width = key.chars().count();
+        let rest = &line[colon + 1..];
+        let mut field = FieldSpan {
+            key: key.trim().to_string(),
+            span: SourceSpan::on_line(index, 0, key_width),
+            value: None,
+            items: Vec::new(),
+        };
+
+        let value_offset = key_width + 1;
+        let inline = rest.trim();
+        if let Some(list) = inline.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let mut offset = value_offset + (rest.chars().count() - rest.trim_start().chars().count()) + 1;
+            for item in list.split(',') {
+                if !item.trim().is_empty() {
+                    field.items.push(value_span(index, offset, item));
+                }
+                offset += item.chars().count() + 1;
+            }
+        } else if !inline.is_empty() && !inline.starts_with('#') {
+            field.value = Some(value_span(index, value_offset, rest));
+        }
+        fields.push(field);
+    }
+
+    fields
+}
+
+/// Span of a scalar written at `offset` (in chars) on a line, quotes removed from the value
+fn value_span(line: usize, offset: usize, raw: &str) -> ValueSpan {
+    let leading = raw.chars().count() - raw.trim_start().chars().count();
+    let text = raw.trim();
+    let start = offset + leading;
+    ValueSpan {
+        value: text.trim_matches(|c| c == '"' || c == '\'').to_string(),
+        span: SourceSpan::on_line(line, start, start + text.chars().count()),
+    }
+}
+
+/// Set a scalar field in the frontmatter, appending it when missing
+pub fn set_frontmatter_field(content: &str, field: &str, value: &str) -> Result<String, String> {
+    if !content.starts_with("---") {
+        return Err("No frontmatter found".to_string());
+    }
+
+    let rest = &content[3..];
+    let rest = rest.strip_prefix('\n').unwrap_or(rest);
+    
+    if let Some(end_pos) = rest.find("\n---") {
+        let yaml_content = &rest[..end_pos];
+        let markdown_content = &rest[end_pos + 4..];
+        
+        // Simple field replacement (works for simple values)
+        let field_pattern = format!("{}:", field);
+        let new_line = format!("{}: {}", field, value);
+        let mut lines: Vec<String> = yaml_content.lines().map(String::from).collect();
+        let mut found = false;
+        
+        for line in lines.iter_mut() {
+            if line.trim_start().starts_with(&field_pattern) {
+                *line = new_line.clone();
+                found = true;
+                break;
+            }
+        }
+        
+        // If field not found, add it at the end
+        let new_yaml = if found {
+            lines.join("\n")
+        } else {
+            format!("{}\n{}: {}", yaml_content, field, value)
+        };
+        
+        Ok(format!("---\n{}\n---{}", new_yaml, markdown_content))
+    } else {
+        Err("Malformed frontmatter".to_string())
+    }
+}
+
+/// Replace a list field in the frontmatter, keeping the rest of the file as is
+///
+/// Flow lists (`tags: [a, b]`) stay flow lists; anything else is written as a
+/// block list. The field is appended when missing.
+pub fn set_frontmatter_list(
+    content: &str,
+    field: &str,
+    values: &[String],
+) -> Result<String, String> {
+    if !content.starts_with("---") {
+        return Err("No frontmatter found".to_string());
+    }
+
+    let rest = &content[3..];
+    let rest = rest.strip_prefix('\n').unwrap_or(rest);
+    let end_pos = rest.find("\n---").ok_or_else(|| "Malformed frontmatter".to_string())?;
+    let yaml_content = &rest[..end_pos];
+    let markdown_content = &rest[end_pos + 4..];
+
+    let lines: Vec<&str> = yaml_content.lines().collect();
+    let field_pattern = format!("{}:", field);
+    let start = lines.iter().position(|line| line.starts_with(&field_pattern));
+
+    let (before, after, flow) = match start {
+        Some(start) => {
+            let inline = lines[start][field_pattern.len()..].trim();
+            // A block list continues over indented lines and `- ` items
+            let mut end = start + 1;
+            if inline.is_empty() {
+                while end < lines.len()
+                    && (lines[end].starts_with([' ', '\t']) || lines[end].starts_with("- "))
+                {
+                    end += 1;
+                }
+            }
+            (&lines[..start], &lines[end..], inline.starts_with('['))
+        }
+        None => (&lines[..], &lines[lines.len()..], false),
+    };
+
+    let mut new_lines: Vec<String> = before.iter().map(|l| l.to_string()).collect();
+    if values.is_empty() {
+        new_lines.push(format!("{}: []", field));
+    } else if flow {
+        let items: Vec<String> = values.iter().map(|v| yaml_list_item(v, true)).collect();
+        new_lines.push(format!("{}: [{}]", field, items.join(", ")));
+    } else {
+        new_lines.push(format!("{}:", field));
+        for value in values {
+            new_lines.push(format!("  - {}", yaml_list_item(value, false)));
+        }
+    }
+    new_lines.extend(after.iter().map(|l| l.to_string()));
+
+    Ok(format!("---\n{}\n---{}", new_lines.join("\n"), markdown_content))
+}
+
+/// Quote a list item when YAML would not read it back as the same string
+fn yaml_list_item(value: &str, flow: bool) -> String {
+    let plain = !value.is_empty()
+        && value.trim() == value
+        && !value.starts_with([
+            '-', '?', ':', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`',
+        ])
+        && !value.contains(": ")
+        && !value.contains(" #")
+        && (!flow || !value.contains([',', '[', ']', '{', '}']))
+        && matches!(
+            serde_yaml::from_str::<serde_yaml::Value>(value),
+            Ok(serde_yaml::Value::String(parsed)) if parsed == value
+        );
+    if plain {
+        value.to_string()
+    } else {
+        format!("{:?}", value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +539,27 @@ depends_on:
         assert_eq!(fm.depends_on, vec!["001-init", "002-setup"]);
     }
 
+    #[test]
+    fn test_parse_frontmatter_with_relations() {
+        let content = r#"---
+status: planned
+related: [010-search]
+blocks:
+  - 012-export
+supersedes:
+  - 003-old-export
+parent: 001-epic
+---
+
+# Spec with relations
+"#;
+        let (fm, _) = parse_frontmatter(content);
+        assert_eq!(fm.related, vec!["010-search"]);
+        assert_eq!(fm.blocks, vec!["012-export"]);
+        assert_eq!(fm.supersedes, vec!["003-old-export"]);
+        assert_eq!(fm.parent, Some("001-epic".to_string()));
+    }
+
     #[test]
     fn test_parse_frontmatter_no_frontmatter() {
         let content = "# Just a title\n\nNo frontmatter here.";
@@ -165,6 +568,62 @@ depends_on:
         assert_eq!(body, content);
     }
 
+    #[test]
+    fn test_parse_frontmatter_checked_errors() {
+        let content = "---\nstatus: planned\ntags:\n\t- ui\n---\n\n# Title\n";
+        let error = parse_frontmatter_checked(content).unwrap_err();
+        assert_eq!(error.line, Some(4));
+        assert!(error.column.is_some());
+
+        let unclosed = parse_frontmatter_checked("---\nstatus: planned\n\n# Title\n");
+        assert_eq!(unclosed.unwrap_err().line, Some(1));
+
+        let (fm, _) = parse_frontmatter(content);
+        assert!(fm.status.is_none());
+    }
+
+    #[test]
+    fn test_set_frontmatter_list() {
+        let content = "---\nstatus: planned\ntags:\n  - ui\n  - api\npriority: high\n---\n\n# Title\n";
+        let tags = vec!["frontend".to_string(), "api".to_string()];
+
+        let updated = set_frontmatter_list(content, "tags", &tags).unwrap();
+        assert_eq!(
+            updated,
+            "---\nstatus: planned\ntags:\n  - frontend\n  - api\npriority: high\n---\n\n# Title\n"
+        );
+
+        let flow = "---\ntags: [ui, api]\n---\n# Title\n";
+        let tags = vec!["a, b".to_string(), "123".to_string()];
+        let updated = set_frontmatter_list(flow, "tags", &tags).unwrap();
+        assert_eq!(updated, "---\ntags: [\"a, b\", \"123\"]\n---\n# Title\n");
+        let (fm, _) = parse_frontmatter(&updated);
+        assert_eq!(fm.tags, vec!["a, b", "123"]);
+    }
+
+    #[test]
+    fn test_parse_source_map() {
+        let content = "---\nstatus: 'done'\ntags: [ui, api]\ndepends_on:\n  - 001-init\n  -\n---\n\n# Title\n\n```\n## Not a heading\n```\n\n## Overview\n";
+        let map = parse_source_map(content);
+
+        let status = map.field("status").unwrap();
+        assert_eq!(status.span, SourceSpan::on_line(1, 0, 6));
+        let value = status.value.as_ref().unwrap();
+        assert_eq!(value.value, "done");
+        assert_eq!(value.span, SourceSpan::on_line(1, 8, 14));
+
+        assert_eq!(map.item_span("tags", "api"), Some(SourceSpan::on_line(2, 11, 14)));
+        assert_eq!(map.item_span("dependsOn", "001-init"), Some(SourceSpan::on_line(4, 4, 12)));
+        assert_eq!(map.field("depends_on").unwrap().items[1].value, "");
+
+        let headings: Vec<(usize, &str, i32)> = map
+            .headings
+            .iter()
+            .map(|h| (h.level, h.text.as_str(), h.span.line))
+            .collect();
+        assert_eq!(headings, vec![(1, "Title", 9), (2, "Overview", 15)]);
+    }
+
     #[test]
     fn test_extract_title() {
         let content = "Some preamble\n\n# The Title\n\nBody content";
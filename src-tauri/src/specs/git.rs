@@ -0,0 +1,513 @@
+//! Git history helpers
+//!
+//! Reads the history of spec files with the `git` CLI. Used as a fallback
+//! when frontmatter doesn't record status transitions, and to reconstruct
+//! past project stats.
+
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::process::Command;
+
+/// Prefix marking the start of a commit in `git log` output
+const COMMIT_MARKER: &str = "__commit__ ";
+
+/// A status observed in a commit touching a spec file
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusChange {
+    pub at: DateTime<Utc>,
+    pub status: String,
+}
+
+/// Frontmatter values changed in a commit touching a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontmatterChange {
+    pub at: DateTime<Utc>,
+    /// Path relative to the directory the history was read for
+    pub path: String,
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub deleted: bool,
+}
+
+/// A commit and the files it touched
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileCommit {
+    pub at: DateTime<Utc>,
+    pub author_name: String,
+    pub author_email: String,
+    /// Paths relative to the directory the history was read for
+    pub paths: Vec<String>,
+}
+
+/// A commit and its message
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitMessage {
+    pub hash: String,
+    pub at: DateTime<Utc>,
+    pub subject: String,
+    pub body: String,
+}
+
+/// A local branch and its tip commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchHead {
+    pub name: String,
+    pub hash: String,
+    /// Commit date of the tip
+    pub at: DateTime<Utc>,
+    /// Whether the branch is merged into the default branch
+    pub merged: bool,
+}
+
+/// List the `limit` most recent commits touching files under `dir`, newest first
+///
+/// Returns an empty list when the directory isn't tracked or git isn't available.
+pub fn file_commits(dir: &Path, limit: usize) -> Vec<FileCommit> {
+    let output = Command::new("git")
+        .arg("log")
+        .arg(format!("--max-count={}", limit))
+        .arg("--relative")
+        .arg("--name-only")
+        .arg(format!("--format={}%aI%x09%an%x09%ae", COMMIT_MARKER))
+        .arg("--")
+        .arg(".")
+        .current_dir(dir)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_name_only_log(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Parse `git log --name-only` output into commits
+fn parse_name_only_log(log: &str) -> Vec<FileCommit> {
+    let mut commits: Vec<FileCommit> = Vec::new();
+
+    for line in log.lines() {
+        if let Some(header) = line.strip_prefix(COMMIT_MARKER) {
+            let mut fields = header.splitn(3, '\t');
+            let at = fields
+                .next()
+                .and_then(|date| DateTime::parse_from_rfc3339(date.trim()).ok());
+            if let Some(at) = at {
+                commits.push(FileCommit {
+                    at: at.with_timezone(&Utc),
+                    author_name: fields.next().unwrap_or_default().to_string(),
+                    author_email: fields.next().unwrap_or_default().to_string(),
+                    paths: Vec::new(),
+                });
+            }
+        } else if !line.trim().is_empty() {
+            if let Some(commit) = commits.last_mut() {
+                commit.paths.push(line.trim().to_string());
+            }
+        }
+    }
+
+    commits
+}
+
+/// Fingerprint of HEAD and the local branches of the repository containing `dir`
+///
+/// Changes whenever a commit is made or a branch is created, moved or deleted.
+/// Returns `None` when the directory isn't tracked or git isn't available.
+pub fn refs_fingerprint(dir: &Path) -> Option<u64> {
+    let output = Command::new("git")
+        .arg("show-ref")
+        .arg("--head")
+        .arg("--heads")
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    output.stdout.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// List the messages of the `limit` most recent commits in the repository
+/// containing `dir`, newest first
+///
+/// Returns an empty list when the directory isn't tracked or git isn't available.
+pub fn commit_messages(dir: &Path, limit: usize) -> Vec<CommitMessage> {
+    let output = Command::new("git")
+        .arg("log")
+        .arg(format!("--max-count={}", limit))
+        .arg(format!("--format={}%H%x09%aI%x09%s%n%b", COMMIT_MARKER))
+        .current_dir(dir)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_message_log(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Parse `git log` output with subject and body into commit messages
+fn parse_message_log(log: &str) -> Vec<CommitMessage> {
+    let mut commits: Vec<CommitMessage> = Vec::new();
+
+    for line in log.lines() {
+        if let Some(header) = line.strip_prefix(COMMIT_MARKER) {
+            let mut fields = header.splitn(3, '\t');
+            let hash = fields.next().unwrap_or_default().to_string();
+            let at = fields
+                .next()
+                .and_then(|date| DateTime::parse_from_rfc3339(date.trim()).ok());
+            if let Some(at) = at {
+                commits.push(CommitMessage {
+                    hash,
+                    at: at.with_timezone(&Utc),
+                    subject: fields.next().unwrap_or_default().to_string(),
+                    body: String::new(),
+                });
+            }
+        } else if let Some(commit) = commits.last_mut() {
+            if !commit.body.is_empty() || !line.trim().is_empty() {
+                commit.body.push_str(line);
+                commit.body.push('\n');
+            }
+        }
+    }
+
+    for commit in &mut commits {
+        commit.body = commit.body.trim_end().to_string();
+    }
+    commits
+}
+
+/// List local branches of the repository containing `dir`
+///
+/// A branch counts as merged when its tip is reachable from `main`, else
+/// `master`, else `HEAD`. Returns an empty list when the directory isn't
+/// tracked or git isn't available.
+pub fn branch_heads(dir: &Path) -> Vec<BranchHead> {
+    let for_each_ref = |merged_into: Option<&str>| {
+        let mut command = Command::new("git");
+        command
+            .arg("for-each-ref")
+            .arg("--format=%(refname:short)%09%(objectname)%09%(committerdate:iso-strict)");
+        if let Some(base) = merged_into {
+            command.arg(format!("--merged={}", base));
+        }
+        match command.arg("refs/heads").current_dir(dir).output() {
+            Ok(output) if output.status.success() => {
+                Some(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+            _ => None,
+        }
+    };
+
+    let Some(refs) = for_each_ref(None) else {
+        return Vec::new();
+    };
+    let branches = parse_branch_refs(&refs);
+    let base = ["main", "master"]
+        .into_iter()
+        .find(|base| branches.iter().any(|b| b.name == *base))
+        .unwrap_or("HEAD");
+    let merged: Vec<String> = for_each_ref(Some(base))
+        .map(|refs| {
+            parse_branch_refs(&refs)
+                .into_iter()
+                .map(|b| b.name)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    branches
+        .into_iter()
+        .map(|branch| BranchHead {
+            merged: branch.name != base && merged.contains(&branch.name),
+            ..branch
+        })
+        .collect()
+}
+
+/// Parse `git for-each-ref` output of name, object and date per line
+fn parse_branch_refs(refs: &str) -> Vec<BranchHead> {
+    refs.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let name = fields.next()?.to_string();
+            let hash = fields.next()?.to_string();
+            let at = DateTime::parse_from_rfc3339(fields.next()?.trim()).ok()?;
+            Some(BranchHead {
+                name,
+                hash,
+                at: at.with_timezone(&Utc),
+                merged: false,
+            })
+        })
+        .collect()
+}
+
+/// Read status and priority changes for every file under `dir`, oldest first
+///
+/// Returns an empty list when the directory isn't tracked or git isn't available.
+pub fn frontmatter_history(dir: &Path) -> Vec<FrontmatterChange> {
+    let output = Command::new("git")
+        .arg("log")
+        .arg("--reverse")
+        .arg("--no-renames")
+        .arg("--relative")
+        .arg(format!("--format={}%aI", COMMIT_MARKER))
+        .arg("-p")
+        .arg("-U0")
+        .arg("--")
+        .arg(".")
+        .current_dir(dir)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_frontmatter_log(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Parse `git log -p` output (oldest first) into per-file frontmatter changes
+fn parse_frontmatter_log(log: &str) -> Vec<FrontmatterChange> {
+    let mut changes: Vec<FrontmatterChange> = Vec::new();
+    let mut commit_at: Option<DateTime<Utc>> = None;
+
+    for line in log.lines() {
+        if let Some(date) = line.strip_prefix(COMMIT_MARKER) {
+            commit_at = DateTime::parse_from_rfc3339(date.trim())
+                .ok()
+                .map(|at| at.with_timezone(&Utc));
+        } else if let Some(paths) = line.strip_prefix("diff --git a/") {
+            let Some(at) = commit_at else { continue };
+            let path = paths.split(" b/").next().unwrap_or(paths).to_string();
+            changes.push(FrontmatterChange {
+                at,
+                path,
+                status: None,
+                priority: None,
+                deleted: false,
+            });
+        } else if let Some(change) = changes.last_mut() {
+            if line.starts_with("deleted file mode") {
+                change.deleted = true;
+            } else if let Some(value) = line.strip_prefix("+status:") {
+                change.status.get_or_insert_with(|| unquote(value));
+            } else if let Some(value) = line.strip_prefix("+priority:") {
+                change.priority.get_or_insert_with(|| unquote(value));
+            }
+        }
+    }
+
+    changes
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string()
+}
+
+/// Reconstruct the status history of every spec under `dir` from one git
+/// log pass, keyed by spec directory name, oldest first
+///
+/// A spec moved into `archived/` keeps the history of its earlier path, since
+/// both share the directory name. Returns an empty map when the directory
+/// isn't tracked or git isn't available.
+pub fn status_histories(dir: &Path) -> HashMap<String, Vec<StatusChange>> {
+    group_status_changes(&frontmatter_history(dir))
+}
+
+/// Group the status changes of spec READMEs by spec directory, dropping
+/// commits that didn't change the status
+fn group_status_changes(changes: &[FrontmatterChange]) -> HashMap<String, Vec<StatusChange>> {
+    let mut histories: HashMap<String, Vec<StatusChange>> = HashMap::new();
+
+    for change in changes {
+        let path = Path::new(&change.path);
+        if path
+            .file_name()
+            .map(|name| name != "README.md")
+            .unwrap_or(true)
+        {
+            continue;
+        }
+        let (Some(spec), Some(status)) =
+            (path.parent().and_then(|p| p.file_name()), &change.status)
+        else {
+            continue;
+        };
+
+        let history = histories
+            .entry(spec.to_string_lossy().into_owned())
+            .or_default();
+        if history.last().map(|c| &c.status != status).unwrap_or(true) {
+            history.push(StatusChange {
+                at: change.at,
+                status: status.clone(),
+            });
+        }
+    }
+
+    histories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_status_changes() {
+        let change = |at: &str, path: &str, status: Option<&str>| FrontmatterChange {
+            at: DateTime::parse_from_rfc3339(at)
+                .unwrap()
+                .with_timezone(&Utc),
+            path: path.to_string(),
+            status: status.map(String::from),
+            priority: None,
+            deleted: false,
+        };
+        let changes = vec![
+            change("2024-03-01T09:00:00Z", "001-a/README.md", Some("planned")),
+            change("2024-03-01T09:00:00Z", "002-b/README.md", Some("planned")),
+            change(
+                "2024-03-02T09:00:00Z",
+                "001-a/README.md",
+                Some("in-progress"),
+            ),
+            change("2024-03-03T09:00:00Z", "001-a/notes.md", Some("draft")),
+            change("2024-03-05T09:00:00Z", "001-a/README.md", None),
+            change("2024-03-10T09:00:00Z", "001-a/README.md", Some("complete")),
+            change("2024-03-10T09:00:00Z", "001-a/README.md", Some("complete")),
+            change(
+                "2024-04-01T09:00:00Z",
+                "archived/001-a/README.md",
+                Some("archived"),
+            ),
+        ];
+
+        let histories = group_status_changes(&changes);
+
+        let statuses: Vec<&str> = histories["001-a"]
+            .iter()
+            .map(|c| c.status.as_str())
+            .collect();
+        assert_eq!(
+            statuses,
+            vec!["planned", "in-progress", "complete", "archived"]
+        );
+        assert_eq!(
+            histories["001-a"][0].at.to_rfc3339(),
+            "2024-03-01T09:00:00+00:00"
+        );
+        assert_eq!(histories["002-b"].len(), 1);
+    }
+
+    #[test]
+    fn test_parse_name_only_log() {
+        let log = "\
+__commit__ 2024-03-04T09:00:00+00:00\tAlice Smith\talice@example.com
+
+001-a/README.md
+001-a/notes.md
+__commit__ 2024-03-01T09:00:00+00:00\tBob\tbob@example.com
+
+002-b/README.md
+";
+
+        let commits = parse_name_only_log(log);
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].author_name, "Alice Smith");
+        assert_eq!(commits[0].author_email, "alice@example.com");
+        assert_eq!(commits[0].paths, vec!["001-a/README.md", "001-a/notes.md"]);
+        assert_eq!(commits[1].paths, vec!["002-b/README.md"]);
+    }
+
+    #[test]
+    fn test_parse_message_log() {
+        let log = "\
+__commit__ 9f2c1e0\t2024-03-04T09:00:00+00:00\tImplement login (LS-042)
+
+Follows spec 042.
+
+Also touches the session store.
+
+__commit__ 1a2b3c4\t2024-03-01T09:00:00+00:00\tInitial commit
+
+";
+
+        let commits = parse_message_log(log);
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].hash, "9f2c1e0");
+        assert_eq!(commits[0].subject, "Implement login (LS-042)");
+        assert_eq!(
+            commits[0].body,
+            "Follows spec 042.\n\nAlso touches the session store."
+        );
+        assert_eq!(commits[1].body, "");
+    }
+
+    #[test]
+    fn test_parse_branch_refs() {
+        let refs = "\
+main\t1a2b3c4\t2024-03-04T09:00:00+01:00
+feature/042-auth\t5d6e7f8\t2024-03-02T10:30:00+00:00
+broken\tabc
+";
+
+        let branches = parse_branch_refs(refs);
+
+        assert_eq!(branches.len(), 2);
+        assert_eq!(branches[1].name, "feature/042-auth");
+        assert_eq!(branches[1].hash, "5d6e7f8");
+        assert_eq!(branches[0].at.to_rfc3339(), "2024-03-04T08:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_frontmatter_log() {
+        let log = "\
+__commit__ 2024-03-01T09:00:00+00:00
+
+diff --git a/001-a/README.md b/001-a/README.md
+new file mode 100644
+@@ -0,0 +1,5 @@
++---
++status: planned
++priority: high
++---
+__commit__ 2024-03-04T09:00:00+00:00
+
+diff --git a/001-a/README.md b/001-a/README.md
+@@ -2 +2 @@
+-status: planned
++status: complete
+diff --git a/002-b/README.md b/002-b/README.md
+deleted file mode 100644
+@@ -1,3 +0,0 @@
+-status: draft
+";
+
+        let changes = parse_frontmatter_log(log);
+
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].path, "001-a/README.md");
+        assert_eq!(changes[0].status.as_deref(), Some("planned"));
+        assert_eq!(changes[0].priority.as_deref(), Some("high"));
+        assert_eq!(changes[1].status.as_deref(), Some("complete"));
+        assert_eq!(changes[1].priority, None);
+        assert!(changes[2].deleted);
+    }
+}
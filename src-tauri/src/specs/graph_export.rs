@@ -0,0 +1,605 @@
+//! Dependency graph export
+//!
+//! Serializes a `DependencyGraph` to Graphviz DOT, Mermaid flowchart text
+//! and GraphML. Node fill follows status and the border follows priority;
+//! edges are styled by relationship type. When the graph carries a computed
+//! layout, DOT and GraphML also embed node positions.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::specs::dependencies::{DependencyEdge, DependencyGraph, DependencyNode, SpecLookup};
+use crate::specs::reader::Spec;
+use crate::specs::xml::escape_xml;
+
+/// Supported export formats
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphExportFormat {
+    Dot,
+    Mermaid,
+    Graphml,
+}
+
+/// Optional filters applied before exporting
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphExportFilter {
+    /// Only keep nodes with one of these statuses (empty keeps all)
+    #[serde(default)]
+    pub status: Vec<String>,
+    /// Only keep nodes carrying this tag
+    pub tag: Option<String>,
+    /// Only keep nodes connected to this spec (its ancestors and descendants),
+    /// given by name, number or node id
+    pub root: Option<String>,
+}
+
+/// Fill color and dash style for a status
+fn status_style(status: &str) -> (&'static str, bool) {
+    let fill = match status {
+        "draft" => "#e5e7eb",
+        "planned" => "#dbeafe",
+        "in-progress" => "#fef3c7",
+        "complete" => "#dcfce7",
+        "archived" => "#f3f4f6",
+        _ => "#ffffff",
+    };
+    (fill, status == "archived")
+}
+
+/// Stroke color and width for a priority
+fn priority_style(priority: &str) -> (&'static str, u8) {
+    match priority {
+        "critical" => ("#dc2626", 3),
+        "high" => ("#ea580c", 2),
+        "low" => ("#9ca3af", 1),
+        _ => ("#6b7280", 1),
+    }
+}
+
+/// DOT edge attributes and Mermaid link for a relationship type
+///
+/// Dependencies keep the plain arrow; other relationships are labeled.
+fn edge_style(edge_type: &str) -> (&'static str, &'static str) {
+    match edge_type {
+        "blocks" => ("color=\"#dc2626\", label=\"blocks\"", "==>|blocks|"),
+        "related" => ("style=dashed, dir=none, label=\"related\"", "-.-|related|"),
+        "supersedes" => ("style=dotted, label=\"supersedes\"", "-.->|supersedes|"),
+        "parent" => ("style=bold, label=\"parent\"", "-->|parent|"),
+        _ => ("", "-->"),
+    }
+}
+
+/// Apply filters to a graph, keeping only edges between retained nodes
+///
+/// `specs` are the specs the graph was built from; the root is resolved
+/// against them like a dependency reference. Fails when `filter.root`
+/// matches no spec in the graph or a number shared by several specs.
+pub fn filter_graph(
+    graph: &DependencyGraph,
+    filter: &GraphExportFilter,
+    specs: &[Spec],
+) -> Result<DependencyGraph, String> {
+    let mut keep: HashSet<&str> = graph
+        .nodes
+        .iter()
+        .filter(|n| filter.status.is_empty() || filter.status.contains(&n.status))
+        .filter(|n| {
+            filter
+                .tag
+                .as_ref()
+                .map(|tag| n.tags.contains(tag))
+                .unwrap_or(true)
+        })
+        .map(|n| n.id.as_str())
+        .collect();
+
+    if let Some(root) = &filter.root {
+        let root_id = root_node_id(graph, specs, root)?;
+        let connected = connected_nodes(graph, root_id);
+        keep.retain(|id| connected.contains(id));
+    }
+
+    let nodes: Vec<DependencyNode> = graph
+        .nodes
+        .iter()
+        .filter(|n| keep.contains(n.id.as_str()))
+        .cloned()
+        .collect();
+    let edges: Vec<DependencyEdge> = graph
+        .edges
+        .iter()
+        .filter(|e| keep.contains(e.source.as_str()) && keep.contains(e.target.as_str()))
+        .cloned()
+        .collect();
+
+    // Any layout no longer matches the filtered node set
+    Ok(DependencyGraph {
+        nodes,
+        edges,
+        layout: None,
+    })
+}
+
+/// Resolve the export root to a node id
+fn root_node_id<'a>(
+    graph: &'a DependencyGraph,
+    specs: &[Spec],
+    root: &str,
+) -> Result<&'a str, String> {
+    let node_id = |id: &str| {
+        graph
+            .nodes
+            .iter()
+            .find(|n| n.id == id)
+            .map(|n| n.id.as_str())
+    };
+    if let Some(id) = node_id(root) {
+        return Ok(id);
+    }
+
+    match SpecLookup::new(specs).candidates(root).as_slice() {
+        [spec] => node_id(&spec.id).ok_or_else(|| format!("Spec '{}' not found", root)),
+        [] => Err(format!("Spec '{}' not found", root)),
+        candidates => {
+            let mut names: Vec<&str> = candidates.iter().map(|s| s.spec_name.as_str()).collect();
+            names.sort_unstable();
+            Err(format!(
+                "Spec '{}' is ambiguous: matches {}",
+                root,
+                names.join(", ")
+            ))
+        }
+    }
+}
+
+/// Collect the root plus every node reachable upstream or downstream from it
+fn connected_nodes<'a>(graph: &'a DependencyGraph, root: &'a str) -> HashSet<&'a str> {
+    let mut result = HashSet::new();
+    result.insert(root);
+
+    for forward in [true, false] {
+        let mut queue = VecDeque::from([root]);
+        let mut seen: HashSet<&str> = HashSet::from([root]);
+        while let Some(current) = queue.pop_front() {
+            for edge in &graph.edges {
+                let (from, to) = if forward {
+                    (edge.source.as_str(), edge.target.as_str())
+                } else {
+                    (edge.target.as_str(), edge.source.as_str())
+                };
+                if from == current && seen.insert(to) {
+                    result.insert(to);
+                    queue.push_back(to);
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Export a graph in the requested format
+pub fn export_graph(graph: &DependencyGraph, format: GraphExportFormat) -> String {
+    match format {
+        GraphExportFormat::Dot => to_dot(graph),
+        GraphExportFormat::Mermaid => to_mermaid(graph),
+        GraphExportFormat::Graphml => to_graphml(graph),
+    }
+}
+
+fn node_label(node: &DependencyNode) -> String {
+    format!("#{:03} {}", node.number, node.name)
+}
+
+/// Layout positions by node id, empty when the graph has no layout
+fn node_positions(graph: &DependencyGraph) -> HashMap<&str, (f64, f64)> {
+    graph
+        .layout
+        .iter()
+        .flat_map(|layout| layout.nodes.iter())
+        .map(|p| (p.id.as_str(), (p.x, p.y)))
+        .collect()
+}
+
+/// Serialize to Graphviz DOT
+///
+/// Embedded positions are pinned (`pos="x,y!"`) with the y axis flipped to
+/// Graphviz orientation; render them with `neato -n`. The layout stacks
+/// layers top to bottom, so the rank direction follows it.
+pub fn to_dot(graph: &DependencyGraph) -> String {
+    let positions = node_positions(graph);
+    let height = graph.layout.as_ref().map(|l| l.height).unwrap_or(0.0);
+
+    let mut out = String::from("digraph dependencies {\n");
+    let rankdir = if graph.layout.is_some() { "TB" } else { "LR" };
+    out.push_str(&format!("  rankdir={};\n", rankdir));
+    out.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+
+    for node in &graph.nodes {
+        let (fill, dashed) = status_style(&node.status);
+        let (stroke, stroke_width) = priority_style(&node.priority);
+        let shape_style = if dashed {
+            "rounded,filled,dashed"
+        } else {
+            "rounded,filled"
+        };
+        let pos = positions
+            .get(node.id.as_str())
+            .map(|(x, y)| format!(", pos=\"{:.1},{:.1}!\"", x, height - y))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "  \"{}\" [label=\"{}\", style=\"{}\", fillcolor=\"{}\", color=\"{}\", penwidth={}{}];\n",
+            escape_dot(&node.id),
+            escape_dot(&node_label(node)),
+            shape_style,
+            fill,
+            stroke,
+            stroke_width,
+            pos
+        ));
+    }
+
+    for edge in &graph.edges {
+        let (attributes, _) = edge_style(&edge.edge_type);
+        let attributes = if attributes.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attributes)
+        };
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\"{};\n",
+            escape_dot(&edge.source),
+            escape_dot(&edge.target),
+            attributes
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Serialize to a Mermaid flowchart
+pub fn to_mermaid(graph: &DependencyGraph) -> String {
+    let ids = mermaid_ids(graph);
+
+    let mut out = String::from("flowchart LR\n");
+    for node in &graph.nodes {
+        out.push_str(&format!(
+            "  {}[\"{}\"]\n",
+            ids[node.id.as_str()],
+            escape_mermaid(&node_label(node))
+        ));
+    }
+
+    for edge in &graph.edges {
+        if let (Some(source), Some(target)) =
+            (ids.get(edge.source.as_str()), ids.get(edge.target.as_str()))
+        {
+            let (_, link) = edge_style(&edge.edge_type);
+            out.push_str(&format!("  {} {} {}\n", source, link, target));
+        }
+    }
+
+    // Status drives the fill via classes, priority drives the stroke per node
+    let mut by_status: Vec<(&str, Vec<&str>)> = Vec::new();
+    for node in &graph.nodes {
+        let id = ids[node.id.as_str()].as_str();
+        match by_status.iter_mut().find(|(s, _)| *s == node.status) {
+            Some((_, members)) => members.push(id),
+            None => by_status.push((node.status.as_str(), vec![id])),
+        }
+    }
+    for (status, members) in &by_status {
+        let (fill, dashed) = status_style(status);
+        let class = mermaid_id(status);
+        let dash = if dashed { ",stroke-dasharray:4 2" } else { "" };
+        out.push_str(&format!(
+            "  classDef status_{} fill:{}{}\n",
+            class, fill, dash
+        ));
+        out.push_str(&format!("  class {} status_{}\n", members.join(","), class));
+    }
+    for node in &graph.nodes {
+        let (stroke, stroke_width) = priority_style(&node.priority);
+        out.push_str(&format!(
+            "  style {} stroke:{},stroke-width:{}px\n",
+            ids[node.id.as_str()],
+            stroke,
+            stroke_width
+        ));
+    }
+
+    out
+}
+
+/// Serialize to GraphML
+pub fn to_graphml(graph: &DependencyGraph) -> String {
+    let positions = node_positions(graph);
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    for (key, name) in [
+        ("d0", "label"),
+        ("d1", "number"),
+        ("d2", "status"),
+        ("d3", "priority"),
+        ("d4", "tags"),
+        ("d5", "fill"),
+        ("d6", "stroke"),
+    ] {
+        let attr_type = if name == "number" { "int" } else { "string" };
+        out.push_str(&format!(
+            "  <key id=\"{}\" for=\"node\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+            key, name, attr_type
+        ));
+    }
+    if !positions.is_empty() {
+        out.push_str("  <key id=\"d7\" for=\"node\" attr.name=\"x\" attr.type=\"double\"/>\n");
+        out.push_str("  <key id=\"d8\" for=\"node\" attr.name=\"y\" attr.type=\"double\"/>\n");
+    }
+    out.push_str("  <key id=\"e0\" for=\"edge\" attr.name=\"type\" attr.type=\"string\"/>\n");
+    out.push_str("  <graph id=\"dependencies\" edgedefault=\"directed\">\n");
+
+    for node in &graph.nodes {
+        let (fill, _) = status_style(&node.status);
+        let (stroke, _) = priority_style(&node.priority);
+        out.push_str(&format!("    <node id=\"{}\">\n", escape_xml(&node.id)));
+        out.push_str(&format!(
+            "      <data key=\"d0\">{}</data>\n",
+            escape_xml(&node.name)
+        ));
+        out.push_str(&format!("      <data key=\"d1\">{}</data>\n", node.number));
+        out.push_str(&format!(
+            "      <data key=\"d2\">{}</data>\n",
+            escape_xml(&node.status)
+        ));
+        out.push_str(&format!(
+            "      <data key=\"d3\">{}</data>\n",
+            escape_xml(&node.priority)
+        ));
+        out.push_str(&format!(
+            "      <data key=\"d4\">{}</data>\n",
+            escape_xml(&node.tags.join(","))
+        ));
+        out.push_str(&format!("      <data key=\"d5\">{}</data>\n", fill));
+        out.push_str(&format!("      <data key=\"d6\">{}</data>\n", stroke));
+        if let Some((x, y)) = positions.get(node.id.as_str()) {
+            out.push_str(&format!("      <data key=\"d7\">{:.1}</data>\n", x));
+            out.push_str(&format!("      <data key=\"d8\">{:.1}</data>\n", y));
+        }
+        out.push_str("    </node>\n");
+    }
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        out.push_str(&format!(
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+            index,
+            escape_xml(&edge.source),
+            escape_xml(&edge.target)
+        ));
+        out.push_str(&format!(
+            "      <data key=\"e0\">{}</data>\n",
+            escape_xml(&edge.edge_type)
+        ));
+        out.push_str("    </edge>\n");
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Mermaid ids by node id, suffixed where two ids sanitize to the same one
+fn mermaid_ids(graph: &DependencyGraph) -> HashMap<&str, String> {
+    let mut taken = HashSet::new();
+    graph
+        .nodes
+        .iter()
+        .map(|n| {
+            let base = mermaid_id(&n.id);
+            let mut id = base.clone();
+            let mut suffix = 2;
+            while !taken.insert(id.clone()) {
+                id = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            (n.id.as_str(), id)
+        })
+        .collect()
+}
+
+fn mermaid_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::dependencies::layout::{compute_layout, LayoutOptions};
+    use crate::specs::reader::SpecBuilder;
+
+    fn node(number: i32, status: &str, priority: &str, tags: Vec<&str>) -> DependencyNode {
+        DependencyNode {
+            id: format!("fs-{:03}-spec", number),
+            name: format!("Spec {}", number),
+            number,
+            status: status.to_string(),
+            priority: priority.to_string(),
+            tags: tags.into_iter().map(String::from).collect(),
+            project_id: None,
+            project_name: None,
+        }
+    }
+
+    fn edge(source: i32, target: i32) -> DependencyEdge {
+        typed_edge(source, target, "dependsOn")
+    }
+
+    fn typed_edge(source: i32, target: i32, edge_type: &str) -> DependencyEdge {
+        DependencyEdge {
+            source: format!("fs-{:03}-spec", source),
+            target: format!("fs-{:03}-spec", target),
+            edge_type: edge_type.to_string(),
+        }
+    }
+
+    fn sample_specs() -> Vec<Spec> {
+        (1..=4)
+            .map(|n| SpecBuilder::new(&format!("{:03}-spec", n)).build())
+            .collect()
+    }
+
+    fn sample_graph() -> DependencyGraph {
+        DependencyGraph {
+            nodes: vec![
+                node(1, "complete", "high", vec!["core"]),
+                node(2, "in-progress", "critical", vec!["core", "ui"]),
+                node(3, "planned", "low", vec!["ui"]),
+                node(4, "planned", "medium", vec![]),
+            ],
+            edges: vec![edge(1, 2), edge(2, 3)],
+            layout: None,
+        }
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(&sample_graph());
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains("rankdir=LR;"));
+        assert!(dot.contains("\"fs-001-spec\" -> \"fs-002-spec\";"));
+        assert!(dot.contains("fillcolor=\"#dcfce7\""));
+        assert!(dot.contains("penwidth=3"));
+    }
+
+    #[test]
+    fn test_edges_styled_by_type() {
+        let mut graph = sample_graph();
+        graph.edges.push(typed_edge(3, 4, "related"));
+        graph.edges.push(typed_edge(1, 4, "blocks"));
+
+        let dot = to_dot(&graph);
+        assert!(dot.contains("\"fs-003-spec\" -> \"fs-004-spec\" [style=dashed, dir=none"));
+        assert!(dot.contains("\"fs-001-spec\" -> \"fs-004-spec\" [color=\"#dc2626\""));
+
+        let mermaid = to_mermaid(&graph);
+        assert!(mermaid.contains("fs_003_spec -.-|related| fs_004_spec"));
+        assert!(mermaid.contains("fs_001_spec ==>|blocks| fs_004_spec"));
+    }
+
+    #[test]
+    fn test_mermaid_ids_do_not_collide() {
+        let mut graph = sample_graph();
+        graph.nodes[0].id = "a-b".to_string();
+        graph.nodes[1].id = "a_b".to_string();
+        graph.edges.clear();
+
+        let mermaid = to_mermaid(&graph);
+        assert!(mermaid.contains("  a_b[\""));
+        assert!(mermaid.contains("  a_b_2[\""));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let mermaid = to_mermaid(&sample_graph());
+        assert!(mermaid.starts_with("flowchart LR"));
+        assert!(mermaid.contains("fs_001_spec --> fs_002_spec"));
+        assert!(mermaid.contains("classDef status_in_progress fill:#fef3c7"));
+        assert!(mermaid.contains("style fs_002_spec stroke:#dc2626,stroke-width:3px"));
+    }
+
+    #[test]
+    fn test_to_graphml() {
+        let graphml = to_graphml(&sample_graph());
+        assert!(graphml.contains("<node id=\"fs-003-spec\">"));
+        assert!(graphml.contains("source=\"fs-002-spec\" target=\"fs-003-spec\""));
+        assert!(graphml.trim_end().ends_with("</graphml>"));
+    }
+
+    #[test]
+    fn test_positions_embedded_with_layout() {
+        let mut graph = sample_graph();
+        assert!(!to_dot(&graph).contains("pos="));
+
+        graph.layout = Some(compute_layout(&graph, &LayoutOptions::default()));
+        let dot = to_dot(&graph);
+        let graphml = to_graphml(&graph);
+        assert_eq!(dot.matches("pos=\"").count(), 4);
+        assert!(dot.contains("rankdir=TB;"));
+        assert!(graphml.contains("attr.name=\"x\""));
+        assert_eq!(graphml.matches("<data key=\"d8\">").count(), 4);
+    }
+
+    #[test]
+    fn test_filter_graph() {
+        let graph = sample_graph();
+        let specs = sample_specs();
+
+        let by_tag = filter_graph(
+            &graph,
+            &GraphExportFilter {
+                tag: Some("ui".to_string()),
+                ..Default::default()
+            },
+            &specs,
+        )
+        .unwrap();
+        assert_eq!(by_tag.nodes.len(), 2);
+        assert_eq!(by_tag.edges.len(), 1);
+
+        let by_root = filter_graph(
+            &graph,
+            &GraphExportFilter {
+                root: Some("3".to_string()),
+                ..Default::default()
+            },
+            &specs,
+        )
+        .unwrap();
+        assert_eq!(by_root.nodes.len(), 3);
+        assert!(by_root.nodes.iter().all(|n| n.number != 4));
+
+        let by_status = filter_graph(
+            &graph,
+            &GraphExportFilter {
+                status: vec!["planned".to_string()],
+                ..Default::default()
+            },
+            &specs,
+        )
+        .unwrap();
+        assert_eq!(by_status.nodes.len(), 2);
+        assert!(by_status.edges.is_empty());
+
+        let unknown_root = GraphExportFilter {
+            root: Some("999".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_graph(&graph, &unknown_root, &specs).unwrap_err(),
+            "Spec '999' not found"
+        );
+
+        let mut duplicated = specs.clone();
+        duplicated.push(SpecBuilder::new("003-other").build());
+        let shared_number = GraphExportFilter {
+            root: Some("3".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_graph(&graph, &shared_number, &duplicated).unwrap_err(),
+            "Spec '3' is ambiguous: matches 003-other, 003-spec"
+        );
+    }
+}
@@ -0,0 +1,453 @@
+//! Daily stats snapshots
+//!
+//! Persists one snapshot of a project's stats per day under
+//! `~/.lean-spec/stats-history/<project>.json` so the UI can chart burnup and
+//! completion-rate trends. Past snapshots can be reconstructed from git
+//! history of the specs directory.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+use crate::config::config_dir;
+use crate::specs::git::FrontmatterChange;
+use crate::specs::stats::{PriorityCount, StatsResult, StatusCount};
+
+const HISTORY_DIR: &str = "stats-history";
+
+/// One lock per history file, so concurrent commands don't lose each other's
+/// snapshots between reading and rewriting the file
+static FILE_LOCKS: Lazy<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = Lazy::new(Default::default);
+
+/// Stats for a project on a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsSnapshot {
+    pub date: NaiveDate,
+    pub total_specs: i32,
+    pub specs_by_status: Vec<StatusCount>,
+    pub specs_by_priority: Vec<PriorityCount>,
+    pub completion_rate: f64,
+    pub active_specs: i32,
+    /// Reconstructed from git history rather than recorded live
+    #[serde(default)]
+    pub backfilled: bool,
+}
+
+impl StatsSnapshot {
+    /// Snapshot of live stats
+    pub fn from_stats(date: NaiveDate, stats: &StatsResult) -> Self {
+        Self {
+            date,
+            total_specs: stats.total_specs,
+            specs_by_status: stats.specs_by_status.clone(),
+            specs_by_priority: stats.specs_by_priority.clone(),
+            completion_rate: stats.completion_rate,
+            active_specs: stats.active_specs,
+            backfilled: false,
+        }
+    }
+
+    /// Snapshot from (status, priority) pairs, one per spec
+    fn from_specs<'a, I>(date: NaiveDate, specs: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, Option<&'a str>)>,
+    {
+        let mut status_counts: BTreeMap<&str, i32> = BTreeMap::new();
+        let mut priority_counts: BTreeMap<&str, i32> = BTreeMap::new();
+        for (status, priority) in specs {
+            *status_counts.entry(status).or_insert(0) += 1;
+            if let Some(priority) = priority {
+                *priority_counts.entry(priority).or_insert(0) += 1;
+            }
+        }
+
+        let total_specs: i32 = status_counts.values().sum();
+        let count = |status: &str| status_counts.get(status).copied().unwrap_or(0);
+        let completion_rate = if total_specs > 0 {
+            count("complete") as f64 / total_specs as f64 * 100.0
+        } else {
+            0.0
+        };
+        let active_specs = count("draft") + count("planned") + count("in-progress");
+
+        let mut specs_by_status: Vec<StatusCount> = status_counts
+            .iter()
+            .map(|(status, count)| StatusCount {
+                status: status.to_string(),
+                count: *count,
+            })
+            .collect();
+        specs_by_status.sort_by_key(|s| Reverse(s.count));
+        let mut specs_by_priority: Vec<PriorityCount> = priority_counts
+            .iter()
+            .map(|(priority, count)| PriorityCount {
+                priority: priority.to_string(),
+                count: *count,
+            })
+            .collect();
+        specs_by_priority.sort_by_key(|p| Reverse(p.count));
+
+        Self {
+            date,
+            total_specs,
+            specs_by_status,
+            specs_by_priority,
+            completion_rate: (completion_rate * 10.0).round() / 10.0,
+            active_specs,
+            backfilled: true,
+        }
+    }
+}
+
+/// JSON-backed store of snapshots, one file per project
+pub struct StatsHistoryStore {
+    dir: PathBuf,
+}
+
+impl StatsHistoryStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Store under the desktop config directory
+    pub fn open_default() -> Option<Self> {
+        config_dir().map(|dir| Self::new(dir.join(HISTORY_DIR)))
+    }
+
+    fn file(&self, project_id: &str) -> PathBuf {
+        let name: String = project_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{}.json", name))
+    }
+
+    /// Load all snapshots for a project, oldest first
+    ///
+    /// A project without a history file has no snapshots; a file that can't
+    /// be read or parsed is an error, so it is never overwritten.
+    pub fn load(&self, project_id: &str) -> Result<Vec<StatsSnapshot>, String> {
+        let raw = match fs::read_to_string(self.file(project_id)) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read stats history: {}", e)),
+        };
+        serde_json::from_str(&raw).map_err(|e| format!("Failed to parse stats history: {}", e))
+    }
+
+    /// Replace the history file through a temporary file, so readers never
+    /// see a partial write
+    fn save(&self, project_id: &str, snapshots: &[StatsSnapshot]) -> Result<(), String> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create stats history directory: {}", e))?;
+        let serialized = serde_json::to_string_pretty(snapshots)
+            .map_err(|e| format!("Failed to serialize stats history: {}", e))?;
+
+        let mut file = NamedTempFile::new_in(&self.dir)
+            .map_err(|e| format!("Failed to write stats history: {}", e))?;
+        file.write_all(serialized.as_bytes())
+            .map_err(|e| format!("Failed to write stats history: {}", e))?;
+        file.persist(self.file(project_id))
+            .map_err(|e| format!("Failed to write stats history: {}", e.error))?;
+        Ok(())
+    }
+
+    /// Load, change and save a project's snapshots while holding its file lock
+    fn update<T>(
+        &self,
+        project_id: &str,
+        change: impl FnOnce(&mut Vec<StatsSnapshot>) -> Option<T>,
+    ) -> Result<Option<T>, String> {
+        let lock = Arc::clone(FILE_LOCKS.lock().entry(self.file(project_id)).or_default());
+        let _guard = lock.lock();
+
+        let mut snapshots = self.load(project_id)?;
+        let changed = change(&mut snapshots);
+        if changed.is_some() {
+            snapshots.sort_by_key(|s| s.date);
+            self.save(project_id, &snapshots)?;
+        }
+        Ok(changed)
+    }
+
+    /// Record a live snapshot unless the day already has one
+    ///
+    /// Returns whether the snapshot was written, so the history file is
+    /// written at most once per day however often stats are read.
+    pub fn record_daily(&self, project_id: &str, snapshot: StatsSnapshot) -> Result<bool, String> {
+        let recorded = self.update(project_id, |snapshots| {
+            if snapshots
+                .iter()
+                .any(|s| s.date == snapshot.date && !s.backfilled)
+            {
+                return None;
+            }
+            snapshots.retain(|s| s.date != snapshot.date);
+            snapshots.push(snapshot);
+            Some(())
+        })?;
+        Ok(recorded.is_some())
+    }
+
+    /// Add backfilled snapshots for days that have no snapshot yet
+    ///
+    /// When `backfill` has several snapshots for a day, the last one wins.
+    /// Returns the number of snapshots added.
+    pub fn merge_backfill(
+        &self,
+        project_id: &str,
+        mut backfill: Vec<StatsSnapshot>,
+    ) -> Result<usize, String> {
+        backfill.sort_by_key(|s| s.date);
+        let backfill: BTreeMap<NaiveDate, StatsSnapshot> =
+            backfill.into_iter().map(|s| (s.date, s)).collect();
+
+        let added = self.update(project_id, |snapshots| {
+            let before = snapshots.len();
+            for snapshot in backfill.into_values() {
+                if !snapshots.iter().any(|s| s.date == snapshot.date) {
+                    snapshots.push(snapshot);
+                }
+            }
+            let added = snapshots.len() - before;
+            (added > 0).then_some(added)
+        })?;
+        Ok(added.unwrap_or(0))
+    }
+}
+
+/// Reconstruct end-of-day snapshots from the git history of a specs directory
+///
+/// Only days with commits touching spec READMEs produce a snapshot. Specs
+/// under `archived/` count as archived, matching the reader.
+///
+/// `git log` lists commits in topological order, where author dates can go
+/// backwards, so changes are replayed in date order to get one snapshot per
+/// day.
+pub fn backfill_snapshots(changes: &[FrontmatterChange]) -> Vec<StatsSnapshot> {
+    let mut changes: Vec<&FrontmatterChange> = changes.iter().collect();
+    changes.sort_by_key(|c| c.at);
+
+    let mut specs: HashMap<&str, (Option<&str>, Option<&str>)> = HashMap::new();
+    let mut snapshots: Vec<StatsSnapshot> = Vec::new();
+
+    let mut index = 0;
+    while index < changes.len() {
+        let date = changes[index].at.date_naive();
+        while index < changes.len() && changes[index].at.date_naive() == date {
+            let change = changes[index];
+            index += 1;
+            if !is_spec_readme(&change.path) {
+                continue;
+            }
+            if change.deleted {
+                specs.remove(change.path.as_str());
+                continue;
+            }
+            let entry = specs.entry(change.path.as_str()).or_default();
+            if change.status.is_some() {
+                entry.0 = change.status.as_deref();
+            }
+            if change.priority.is_some() {
+                entry.1 = change.priority.as_deref();
+            }
+        }
+
+        snapshots.push(StatsSnapshot::from_specs(
+            date,
+            specs.iter().filter_map(|(path, (status, priority))| {
+                let status = if path.starts_with("archived/") {
+                    "archived"
+                } else {
+                    (*status)?
+                };
+                Some((status, *priority))
+            }),
+        ));
+    }
+
+    snapshots
+}
+
+/// Check for `<spec>/README.md` or `archived/<spec>/README.md`
+fn is_spec_readme(path: &str) -> bool {
+    let parts: Vec<&str> = path.split('/').collect();
+    let spec_dir = match parts.as_slice() {
+        [dir, "README.md"] => dir,
+        ["archived", dir, "README.md"] => dir,
+        _ => return false,
+    };
+    spec_dir
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(false)
+}
+
+/// Keep snapshots within an optional date range
+pub fn filter_range(
+    snapshots: Vec<StatsSnapshot>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Vec<StatsSnapshot> {
+    snapshots
+        .into_iter()
+        .filter(|s| from.map(|f| s.date >= f.date_naive()).unwrap_or(true))
+        .filter(|s| to.map(|t| s.date <= t.date_naive()).unwrap_or(true))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn change(at: &str, path: &str, status: Option<&str>, deleted: bool) -> FrontmatterChange {
+        FrontmatterChange {
+            at: DateTime::parse_from_rfc3339(at)
+                .unwrap()
+                .with_timezone(&Utc),
+            path: path.to_string(),
+            status: status.map(String::from),
+            priority: Some("high".to_string()),
+            deleted,
+        }
+    }
+
+    #[test]
+    fn test_record_and_merge_backfill() {
+        let temp = TempDir::new().unwrap();
+        let store = StatsHistoryStore::new(temp.path());
+
+        let live = StatsSnapshot::from_specs(date("2024-03-02"), [("complete", None)]);
+        store
+            .record_daily(
+                "project-1",
+                StatsSnapshot {
+                    backfilled: false,
+                    ..live
+                },
+            )
+            .unwrap();
+
+        let backfill = vec![
+            StatsSnapshot::from_specs(date("2024-03-02"), [("planned", None)]),
+            StatsSnapshot::from_specs(date("2024-03-01"), [("planned", None)]),
+            StatsSnapshot::from_specs(date("2024-03-01"), [("complete", None)]),
+        ];
+        assert_eq!(store.merge_backfill("project-1", backfill).unwrap(), 1);
+
+        let snapshots = store.load("project-1").unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].date, date("2024-03-01"));
+        assert_eq!(snapshots[0].completion_rate, 100.0);
+        // Live snapshots win over reconstructed ones
+        assert!(!snapshots[1].backfilled);
+        assert_eq!(snapshots[1].completion_rate, 100.0);
+
+        // Later reads on the same day leave the live snapshot alone
+        let later = StatsSnapshot {
+            backfilled: false,
+            ..StatsSnapshot::from_specs(date("2024-03-02"), [("planned", None)])
+        };
+        assert!(!store.record_daily("project-1", later.clone()).unwrap());
+        assert_eq!(store.load("project-1").unwrap()[1].completion_rate, 100.0);
+        let next_day = StatsSnapshot {
+            date: date("2024-03-03"),
+            ..later
+        };
+        assert!(store.record_daily("project-1", next_day).unwrap());
+        assert_eq!(store.load("project-1").unwrap().len(), 3);
+
+        // A corrupt file is reported instead of being replaced
+        fs::write(temp.path().join("project-1.json"), "{").unwrap();
+        assert!(store.load("project-1").is_err());
+        let day_after = StatsSnapshot::from_specs(date("2024-03-04"), [("planned", None)]);
+        assert!(store.record_daily("project-1", day_after).is_err());
+        assert_eq!(
+            fs::read_to_string(temp.path().join("project-1.json")).unwrap(),
+            "{"
+        );
+    }
+
+    #[test]
+    fn test_backfill_snapshots() {
+        let changes = vec![
+            change(
+                "2024-03-01T09:00:00Z",
+                "001-a/README.md",
+                Some("planned"),
+                false,
+            ),
+            change(
+                "2024-03-01T10:00:00Z",
+                "002-b/README.md",
+                Some("in-progress"),
+                false,
+            ),
+            change(
+                "2024-03-01T11:00:00Z",
+                "assets/diagram.md",
+                Some("draft"),
+                false,
+            ),
+            change(
+                "2024-03-03T09:00:00Z",
+                "001-a/README.md",
+                Some("complete"),
+                false,
+            ),
+            change("2024-03-04T09:00:00Z", "002-b/README.md", None, true),
+            change(
+                "2024-03-04T09:00:00Z",
+                "archived/002-b/README.md",
+                Some("complete"),
+                false,
+            ),
+        ];
+
+        let snapshots = backfill_snapshots(&changes);
+
+        assert_eq!(snapshots.len(), 3);
+        // A commit listed later with an earlier author date joins its own day
+        let mut reordered = changes.clone();
+        reordered.swap(0, 3);
+        let dates: Vec<NaiveDate> = backfill_snapshots(&reordered)
+            .iter()
+            .map(|s| s.date)
+            .collect();
+        assert_eq!(
+            dates,
+            vec![date("2024-03-01"), date("2024-03-03"), date("2024-03-04")]
+        );
+        assert_eq!(snapshots[0].total_specs, 2);
+        assert_eq!(snapshots[0].active_specs, 2);
+        assert_eq!(snapshots[1].completion_rate, 50.0);
+        let last = &snapshots[2];
+        assert_eq!(last.total_specs, 2);
+        assert!(last
+            .specs_by_status
+            .iter()
+            .any(|s| s.status == "archived" && s.count == 1));
+        assert_eq!(last.specs_by_priority[0].count, 2);
+    }
+}
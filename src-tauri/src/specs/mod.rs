@@ -7,8 +7,27 @@ pub mod commands;
 pub mod constants;
 pub mod frontmatter;
 pub mod reader;
+pub mod relations;
 pub mod stats;
+pub mod flow;
+pub mod forecast;
+pub mod portfolio;
+pub mod settings;
+pub mod schema;
+pub mod staleness;
+pub mod assignees;
+pub mod tags;
+pub mod tokens;
+pub mod fixes;
+pub mod collisions;
+pub mod traceability;
+pub mod code_activity;
+pub mod tasks;
+pub mod git;
+pub mod history;
 pub mod dependencies;
+pub mod graph_export;
+pub mod xml;
 pub mod validation;
 
 // Re-export commands for convenience
@@ -16,12 +35,33 @@ pub use commands::{
     get_specs,
     get_spec_detail,
     get_project_stats,
+    record_stats_snapshot,
+    get_stats_history,
+    get_portfolio_stats,
+    get_flow_metrics,
+    get_completion_forecast,
     get_dependency_graph,
+    export_dependency_graph,
     get_spec_dependencies_cmd,
+    get_spec_impact_cmd,
     search_specs,
     get_specs_by_status,
     get_all_tags,
+    get_tag_analytics,
+    rename_tag,
+    merge_tags,
     validate_spec_cmd,
     validate_all_specs_cmd,
+    export_validation_report,
+    get_load_diagnostics,
+    get_validation_rules,
+    get_frontmatter_schema,
+    fix_spec_issues,
+    get_stale_specs,
+    get_assignee_stats,
+    get_spec_tokens,
+    get_spec_traceability,
+    get_spec_activity,
+    toggle_spec_task,
     update_spec_status,
 };
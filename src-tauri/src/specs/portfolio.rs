@@ -0,0 +1,298 @@
+//! Portfolio stats across registered projects
+//!
+//! Aggregates per-project stats into combined status and priority counts and
+//! ranks projects by in-progress and stale work. Per-project results are
+//! cached and only recomputed when the project's spec files or git refs
+//! change.
+
+use chrono::NaiveDate;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::specs::reader::Spec;
+use crate::specs::staleness::{StaleReason, StalenessReport};
+use crate::specs::stats::{calculate_stats, PriorityCount, StatsResult, StatusCount};
+
+/// Number of projects listed in each ranking
+const RANKING_LIMIT: usize = 5;
+
+/// Stats for one project in the portfolio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectPortfolio {
+    pub project_id: String,
+    pub project_name: String,
+    pub stats: StatsResult,
+    pub in_progress_specs: i32,
+    /// In-progress specs flagged `stale-in-progress` by stale spec detection
+    pub stale_specs: i32,
+}
+
+/// A project and the count it is ranked by
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRanking {
+    pub project_id: String,
+    pub project_name: String,
+    pub count: i32,
+}
+
+/// Combined stats across projects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioStats {
+    pub total_projects: i32,
+    pub total_specs: i32,
+    pub specs_by_status: Vec<StatusCount>,
+    pub specs_by_priority: Vec<PriorityCount>,
+    pub completion_rate: f64,
+    pub active_specs: i32,
+    pub projects: Vec<ProjectPortfolio>,
+    /// Projects with the most in-progress specs
+    pub most_in_progress: Vec<ProjectRanking>,
+    /// Projects with the most stale in-progress specs
+    pub most_stale: Vec<ProjectRanking>,
+}
+
+/// Compute portfolio stats for a single project
+///
+/// The stale count comes from the project's staleness report, so it matches
+/// what stale spec detection shows for the project.
+pub fn summarize_project(
+    project_id: &str,
+    project_name: &str,
+    specs: &[Spec],
+    staleness: &StalenessReport,
+) -> ProjectPortfolio {
+    let in_progress = specs.iter().filter(|s| s.status == "in-progress").count();
+    let stale_specs = staleness
+        .specs
+        .iter()
+        .filter(|s| s.reason == StaleReason::StaleInProgress)
+        .count();
+
+    ProjectPortfolio {
+        project_id: project_id.to_string(),
+        project_name: project_name.to_string(),
+        stats: calculate_stats(specs),
+        in_progress_specs: in_progress as i32,
+        stale_specs: stale_specs as i32,
+    }
+}
+
+/// Combine per-project stats into portfolio totals and rankings
+pub fn aggregate_portfolio(projects: Vec<ProjectPortfolio>) -> PortfolioStats {
+    let mut status_counts: HashMap<String, i32> = HashMap::new();
+    let mut priority_counts: HashMap<String, i32> = HashMap::new();
+    let mut total_specs = 0;
+    let mut active_specs = 0;
+
+    for project in &projects {
+        total_specs += project.stats.total_specs;
+        active_specs += project.stats.active_specs;
+        for entry in &project.stats.specs_by_status {
+            *status_counts.entry(entry.status.clone()).or_insert(0) += entry.count;
+        }
+        for entry in &project.stats.specs_by_priority {
+            *priority_counts.entry(entry.priority.clone()).or_insert(0) += entry.count;
+        }
+    }
+
+    let complete = status_counts.get("complete").copied().unwrap_or(0);
+    let completion_rate = if total_specs > 0 {
+        (complete as f64 / total_specs as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    let mut specs_by_status: Vec<StatusCount> = status_counts
+        .into_iter()
+        .map(|(status, count)| StatusCount { status, count })
+        .collect();
+    specs_by_status.sort_by(|a, b| b.count.cmp(&a.count).then(a.status.cmp(&b.status)));
+
+    let mut specs_by_priority: Vec<PriorityCount> = priority_counts
+        .into_iter()
+        .map(|(priority, count)| PriorityCount { priority, count })
+        .collect();
+    specs_by_priority.sort_by(|a, b| b.count.cmp(&a.count).then(a.priority.cmp(&b.priority)));
+
+    PortfolioStats {
+        total_projects: projects.len() as i32,
+        total_specs,
+        specs_by_status,
+        specs_by_priority,
+        completion_rate: (completion_rate * 10.0).round() / 10.0,
+        active_specs,
+        most_in_progress: rank_projects(&projects, |p| p.in_progress_specs),
+        most_stale: rank_projects(&projects, |p| p.stale_specs),
+        projects,
+    }
+}
+
+fn rank_projects<F>(projects: &[ProjectPortfolio], count: F) -> Vec<ProjectRanking>
+where
+    F: Fn(&ProjectPortfolio) -> i32,
+{
+    let mut ranking: Vec<ProjectRanking> = projects
+        .iter()
+        .filter(|p| count(p) > 0)
+        .map(|p| ProjectRanking {
+            project_id: p.project_id.clone(),
+            project_name: p.project_name.clone(),
+            count: count(p),
+        })
+        .collect();
+    ranking.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(a.project_name.cmp(&b.project_name))
+    });
+    ranking.truncate(RANKING_LIMIT);
+    ranking
+}
+
+/// Cache fingerprint covering everything a project summary depends on
+///
+/// Stale counts depend on git activity, the threshold and the current day,
+/// so those are mixed in with the fingerprint of the spec files.
+pub fn cache_fingerprint(
+    spec_files: u64,
+    git_refs: Option<u64>,
+    stale_after_days: i64,
+    today: NaiveDate,
+) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (spec_files, git_refs, stale_after_days, today).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Per-project results keyed by project id, tagged with a spec fingerprint
+///
+/// Clones share the same entries, so the cache can be handed to blocking
+/// tasks.
+#[derive(Clone, Default)]
+pub struct PortfolioCache {
+    entries: Arc<Mutex<HashMap<String, (u64, ProjectPortfolio)>>>,
+}
+
+impl PortfolioCache {
+    /// Return the cached entry when the fingerprint matches, else compute it
+    ///
+    /// A failed computation is returned as is and leaves the cache untouched.
+    pub fn get_or_compute<F>(
+        &self,
+        project_id: &str,
+        fingerprint: u64,
+        compute: F,
+    ) -> Result<ProjectPortfolio, String>
+    where
+        F: FnOnce() -> Result<ProjectPortfolio, String>,
+    {
+        if let Some((cached, entry)) = self.entries.lock().get(project_id) {
+            if *cached == fingerprint {
+                return Ok(entry.clone());
+            }
+        }
+
+        let entry = compute()?;
+        self.entries
+            .lock()
+            .insert(project_id.to_string(), (fingerprint, entry.clone()));
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::reader::SpecBuilder;
+    use crate::specs::settings::StalenessSettings;
+    use crate::specs::staleness::{detect_stale_specs, ActivityIndex};
+    use chrono::{DateTime, Duration, Utc};
+
+    fn spec(status: &str, priority: &str, updated_days_ago: i64, now: DateTime<Utc>) -> Spec {
+        SpecBuilder::new("001-test")
+            .status(status)
+            .priority(priority)
+            .updated_at(now - Duration::days(updated_days_ago))
+            .build()
+    }
+
+    fn summarize(id: &str, name: &str, specs: &[Spec], now: DateTime<Utc>) -> ProjectPortfolio {
+        let settings = StalenessSettings {
+            in_progress_days: 30,
+            ..StalenessSettings::default()
+        };
+        let staleness = detect_stale_specs(specs, &ActivityIndex::default(), &settings, now);
+        summarize_project(id, name, specs, &staleness)
+    }
+
+    #[test]
+    fn test_aggregate_portfolio() {
+        let now = Utc::now();
+        let alpha = summarize(
+            "a",
+            "Alpha",
+            &[
+                spec("in-progress", "high", 45, now),
+                spec("in-progress", "medium", 2, now),
+                spec("complete", "high", 10, now),
+            ],
+            now,
+        );
+        let beta = summarize(
+            "b",
+            "Beta",
+            &[
+                spec("in-progress", "low", 60, now),
+                spec("in-progress", "low", 90, now),
+                spec("complete", "medium", 1, now),
+                spec("planned", "high", 1, now),
+            ],
+            now,
+        );
+        assert_eq!(alpha.stale_specs, 1);
+
+        let portfolio = aggregate_portfolio(vec![alpha, beta]);
+
+        assert_eq!(portfolio.total_projects, 2);
+        assert_eq!(portfolio.total_specs, 7);
+        assert_eq!(portfolio.specs_by_status[0].status, "in-progress");
+        assert_eq!(portfolio.specs_by_status[0].count, 4);
+        assert_eq!(portfolio.completion_rate, 28.6);
+        assert_eq!(portfolio.most_in_progress[0].project_name, "Alpha");
+        assert_eq!(portfolio.most_stale[0].project_id, "b");
+        assert_eq!(portfolio.most_stale[0].count, 2);
+    }
+
+    #[test]
+    fn test_cache_reuses_unchanged_projects() {
+        let cache = PortfolioCache::default();
+        let now = Utc::now();
+        let compute = || Ok(summarize("a", "Alpha", &[], now));
+
+        cache.get_or_compute("a", 1, compute).unwrap();
+        cache
+            .get_or_compute("a", 1, || panic!("should be cached"))
+            .unwrap();
+        assert!(cache
+            .get_or_compute("a", 2, || Err("bad settings".to_string()))
+            .is_err());
+        let recomputed = cache
+            .get_or_compute("a", 2, || {
+                Ok(summarize(
+                    "a",
+                    "Alpha",
+                    &[spec("planned", "low", 0, now)],
+                    now,
+                ))
+            })
+            .unwrap();
+        assert_eq!(recomputed.stats.total_specs, 1);
+    }
+}
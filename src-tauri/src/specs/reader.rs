@@ -2,13 +2,20 @@
 //!
 //! Reads spec directories and parses README.md files with frontmatter.
 
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::specs::frontmatter::{extract_title, parse_frontmatter};
+use crate::specs::dependencies::SpecLookup;
+use crate::specs::frontmatter::{extract_title, parse_frontmatter_checked};
+use crate::specs::relations::{RelationKind, SpecRelations};
+use crate::specs::tasks::TaskProgress;
 
 /// A full spec with all content
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +43,130 @@ pub struct Spec {
     /// Computed list of specs that depend on this one
     #[serde(default)]
     pub required_by: Vec<String>,
+    /// Related, blocks, supersedes and parent relationships
+    #[serde(default)]
+    pub relations: SpecRelations,
+    /// Task list progress of the body
+    #[serde(flatten)]
+    pub tasks: TaskProgress,
+}
+
+impl Spec {
+    /// Declared references of a given relationship kind
+    pub fn references(&self, kind: RelationKind) -> Vec<&str> {
+        match kind {
+            RelationKind::DependsOn => self.depends_on.iter().map(String::as_str).collect(),
+            _ => self.relations.declared(kind),
+        }
+    }
+
+    /// Case-insensitive match against name, title, content and tags
+    pub fn matches_query(&self, query: &str) -> bool {
+        let lower_query = query.to_lowercase();
+        self.spec_name.to_lowercase().contains(&lower_query)
+            || self
+                .title
+                .as_deref()
+                .unwrap_or("")
+                .to_lowercase()
+                .contains(&lower_query)
+            || self.content_md.to_lowercase().contains(&lower_query)
+            || self
+                .tags
+                .iter()
+                .any(|t| t.to_lowercase().contains(&lower_query))
+    }
+}
+
+/// Builds specs for tests
+///
+/// Starts from a planned spec with an empty body; the number is taken from
+/// the name like the reader does. Setting the content also counts its tasks.
+#[cfg(test)]
+pub(crate) struct SpecBuilder(Spec);
+
+#[cfg(test)]
+impl SpecBuilder {
+    pub fn new(name: &str) -> Self {
+        Self(Spec {
+            id: format!("fs-{}", name),
+            project_id: "test".to_string(),
+            spec_number: name.split('-').next().and_then(|n| n.parse().ok()),
+            spec_name: name.to_string(),
+            title: None,
+            status: "planned".to_string(),
+            priority: None,
+            tags: Vec::new(),
+            assignee: None,
+            content_md: String::new(),
+            content_html: None,
+            created_at: None,
+            updated_at: None,
+            completed_at: None,
+            file_path: format!("specs/{}/README.md", name),
+            github_url: None,
+            synced_at: Utc::now(),
+            depends_on: Vec::new(),
+            required_by: Vec::new(),
+            relations: Default::default(),
+            tasks: Default::default(),
+        })
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.0.title = Some(title.to_string());
+        self
+    }
+
+    pub fn status(mut self, status: &str) -> Self {
+        self.0.status = status.to_string();
+        self
+    }
+
+    pub fn priority(mut self, priority: &str) -> Self {
+        self.0.priority = Some(priority.to_string());
+        self
+    }
+
+    pub fn tags(mut self, tags: &[&str]) -> Self {
+        self.0.tags = tags.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    pub fn assignee(mut self, assignee: Option<&str>) -> Self {
+        self.0.assignee = assignee.map(String::from);
+        self
+    }
+
+    pub fn content(mut self, content: &str) -> Self {
+        self.0.content_md = content.to_string();
+        self.0.tasks = TaskProgress::from_content(content);
+        self
+    }
+
+    pub fn created_at(mut self, at: DateTime<Utc>) -> Self {
+        self.0.created_at = Some(at);
+        self
+    }
+
+    pub fn updated_at(mut self, at: DateTime<Utc>) -> Self {
+        self.0.updated_at = Some(at);
+        self
+    }
+
+    pub fn completed_at(mut self, at: DateTime<Utc>) -> Self {
+        self.0.completed_at = Some(at);
+        self
+    }
+
+    pub fn depends_on(mut self, deps: Vec<String>) -> Self {
+        self.0.depends_on = deps;
+        self
+    }
+
+    pub fn build(self) -> Spec {
+        self.0
+    }
 }
 
 /// Lightweight spec without full content (for list views)
@@ -60,7 +191,14 @@ pub struct LightweightSpec {
     #[serde(default)]
     pub required_by: Vec<String>,
     #[serde(default)]
+    pub relations: SpecRelations,
+    #[serde(default)]
     pub sub_specs_count: i32,
+    #[serde(flatten)]
+    pub tasks: TaskProgress,
+    /// Latest commit or branch mentioning the spec
+    #[serde(default)]
+    pub last_code_activity: Option<DateTime<Utc>>,
 }
 
 impl From<&Spec> for LightweightSpec {
@@ -82,11 +220,50 @@ impl From<&Spec> for LightweightSpec {
             github_url: spec.github_url.clone(),
             depends_on: spec.depends_on.clone(),
             required_by: spec.required_by.clone(),
+            relations: spec.relations.clone(),
             sub_specs_count: 0,
+            tasks: spec.tasks.clone(),
+            last_code_activity: None,
+        }
+    }
+}
+
+/// Why a spec directory did not load
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoadFailure {
+    MissingReadme,
+    UnreadableReadme,
+    InvalidFrontmatter,
+    MissingStatus,
+}
+
+impl LoadFailure {
+    /// Validation code reported for the failure
+    pub fn code(self) -> &'static str {
+        match self {
+            LoadFailure::MissingReadme => "missing-readme",
+            LoadFailure::UnreadableReadme => "unreadable-readme",
+            LoadFailure::InvalidFrontmatter => "invalid-frontmatter",
+            LoadFailure::MissingStatus => "missing-status",
         }
     }
 }
 
+/// A spec directory that was skipped while loading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadDiagnostic {
+    /// Spec directory name
+    pub spec_name: String,
+    pub file_path: String,
+    pub reason: LoadFailure,
+    pub message: String,
+    /// 1-based position of a YAML error
+    pub line: Option<i32>,
+    pub column: Option<i32>,
+}
+
 /// Spec reader for loading specs from filesystem
 pub struct SpecReader {
     specs_dir: PathBuf,
@@ -104,19 +281,25 @@ impl SpecReader {
 
     /// Load all specs from the specs directory
     pub fn load_all(&self) -> Vec<Spec> {
+        self.load_all_with_diagnostics().0
+    }
+
+    /// Load all specs, along with the spec directories that failed to load
+    pub fn load_all_with_diagnostics(&self) -> (Vec<Spec>, Vec<LoadDiagnostic>) {
         let mut specs = Vec::new();
+        let mut diagnostics = Vec::new();
 
         if !self.specs_dir.exists() {
-            return specs;
+            return (specs, diagnostics);
         }
 
         // Load regular specs
-        self.load_specs_from_dir(&self.specs_dir, false, &mut specs);
+        self.load_specs_from_dir(&self.specs_dir, false, &mut specs, &mut diagnostics);
 
         // Load archived specs
         let archived_dir = self.specs_dir.join("archived");
         if archived_dir.exists() {
-            self.load_specs_from_dir(&archived_dir, true, &mut specs);
+            self.load_specs_from_dir(&archived_dir, true, &mut specs, &mut diagnostics);
         }
 
         // Sort by spec number
@@ -124,12 +307,48 @@ impl SpecReader {
 
         // Build required_by relationships
         self.build_required_by(&mut specs);
+        self.build_inverse_relations(&mut specs);
 
-        specs
+        diagnostics.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        (specs, diagnostics)
+    }
+
+    /// Fingerprint of every spec README (path, size and modification time)
+    ///
+    /// Changes whenever a spec file is added, removed or modified, so callers
+    /// can skip rescanning unchanged projects.
+    pub fn fingerprint(&self) -> u64 {
+        let mut readmes: Vec<(PathBuf, u64, Option<SystemTime>)> = Vec::new();
+        for dir in [self.specs_dir.clone(), self.specs_dir.join("archived")] {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                if !name.to_str().map(is_spec_dir_name).unwrap_or(false) {
+                    continue;
+                }
+                let readme = entry.path().join("README.md");
+                if let Ok(metadata) = fs::metadata(&readme) {
+                    readmes.push((readme, metadata.len(), metadata.modified().ok()));
+                }
+            }
+        }
+        readmes.sort();
+
+        let mut hasher = DefaultHasher::new();
+        readmes.hash(&mut hasher);
+        hasher.finish()
     }
 
     /// Load specs from a directory
-    fn load_specs_from_dir(&self, dir: &Path, is_archived: bool, specs: &mut Vec<Spec>) {
+    fn load_specs_from_dir(
+        &self,
+        dir: &Path,
+        is_archived: bool,
+        specs: &mut Vec<Spec>,
+        diagnostics: &mut Vec<LoadDiagnostic>,
+    ) {
         let entries = match fs::read_dir(dir) {
             Ok(entries) => entries,
             Err(_) => return,
@@ -151,18 +370,13 @@ impl SpecReader {
                 continue;
             }
 
-            // Check if directory name matches spec pattern (digits followed by dash)
-            if !dir_name
-                .chars()
-                .next()
-                .map(|c| c.is_ascii_digit())
-                .unwrap_or(false)
-            {
+            if !is_spec_dir_name(dir_name) {
                 continue;
             }
 
-            if let Some(spec) = self.load_spec_from_dir(&path, dir_name, is_archived) {
-                specs.push(spec);
+            match self.load_spec_from_dir(&path, dir_name, is_archived) {
+                Ok(spec) => specs.push(spec),
+                Err(diagnostic) => diagnostics.push(diagnostic),
             }
         }
     }
@@ -173,15 +387,58 @@ impl SpecReader {
         spec_dir: &Path,
         spec_name: &str,
         is_archived: bool,
-    ) -> Option<Spec> {
+    ) -> Result<Spec, LoadDiagnostic> {
+        // Build file path - legacy support for archived/ folder
+        let file_path = if is_archived {
+            format!("specs/archived/{}/README.md", spec_name)
+        } else {
+            format!("specs/{}/README.md", spec_name)
+        };
+        let failure = |reason, message: String, line, column| LoadDiagnostic {
+            spec_name: spec_name.to_string(),
+            file_path: file_path.clone(),
+            reason,
+            message,
+            line,
+            column,
+        };
+
         let readme_path = spec_dir.join("README.md");
-        let content = fs::read_to_string(&readme_path).ok()?;
+        let content = fs::read_to_string(&readme_path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                failure(
+                    LoadFailure::MissingReadme,
+                    "README.md not found".to_string(),
+                    None,
+                    None,
+                )
+            } else {
+                failure(
+                    LoadFailure::UnreadableReadme,
+                    format!("Failed to read README.md: {}", e),
+                    None,
+                    None,
+                )
+            }
+        })?;
 
-        let (frontmatter, body) = parse_frontmatter(&content);
+        let (frontmatter, body) = parse_frontmatter_checked(&content).map_err(|e| {
+            failure(
+                LoadFailure::InvalidFrontmatter,
+                format!("Invalid frontmatter: {}", e.message),
+                e.line,
+                e.column,
+            )
+        })?;
 
         // Must have status in frontmatter
         if frontmatter.status.is_none() {
-            return None;
+            return Err(failure(
+                LoadFailure::MissingStatus,
+                "Frontmatter has no status".to_string(),
+                None,
+                None,
+            ));
         }
 
         // Extract spec number from directory name
@@ -193,13 +450,6 @@ impl SpecReader {
         let title = extract_title(&body);
         let id = format!("fs-{}", spec_name);
 
-        // Build file path - legacy support for archived/ folder
-        let file_path = if is_archived {
-            format!("specs/archived/{}/README.md", spec_name)
-        } else {
-            format!("specs/{}/README.md", spec_name)
-        };
-
         // Log deprecation warning for specs in archived/ folder
         if is_archived {
             eprintln!(
@@ -220,7 +470,8 @@ impl SpecReader {
         // This would allow specs to be broken into multiple documents
         // Track in a future spec once the use case is validated
 
-        Some(Spec {
+        let tasks = TaskProgress::from_content(&content);
+        Ok(Spec {
             id,
             project_id: self.project_id.clone(),
             spec_number,
@@ -244,6 +495,14 @@ impl SpecReader {
             synced_at: Utc::now(),
             depends_on: frontmatter.depends_on.clone(),
             required_by: Vec::new(), // Populated later
+            relations: SpecRelations {
+                related: frontmatter.related.clone(),
+                blocks: frontmatter.blocks.clone(),
+                supersedes: frontmatter.supersedes.clone(),
+                parent: frontmatter.parent.clone(),
+                ..Default::default()
+            },
+            tasks,
         })
     }
 
@@ -269,48 +528,105 @@ impl SpecReader {
     }
 
     /// Build required_by relationships (reverse of depends_on)
-    fn build_required_by(&self, specs: &mut Vec<Spec>) {
-        // Build a map of spec_name -> depends_on
-        let dependency_map: std::collections::HashMap<String, Vec<String>> = specs
-            .iter()
-            .map(|s| (s.spec_name.clone(), s.depends_on.clone()))
-            .collect();
-
-        // For each spec, find all specs that depend on it
+    ///
+    /// References whose number is shared by several specs are skipped rather
+    /// than attributed to all of them.
+    fn build_required_by(&self, specs: &mut [Spec]) {
+        let mut required_by: HashMap<String, Vec<String>> = HashMap::new();
+        let lookup = SpecLookup::new(specs.iter());
+        for spec in specs.iter() {
+            for dep in &spec.depends_on {
+                let Some(target) = lookup.find(dep) else {
+                    continue;
+                };
+                if target.spec_name == spec.spec_name {
+                    continue;
+                }
+                let dependents = required_by.entry(target.spec_name.clone()).or_default();
+                if !dependents.contains(&spec.spec_name) {
+                    dependents.push(spec.spec_name.clone());
+                }
+            }
+        }
+
         for spec in specs.iter_mut() {
-            let mut required_by = Vec::new();
-            for (name, deps) in &dependency_map {
-                if name == &spec.spec_name {
+            spec.required_by = required_by.remove(&spec.spec_name).unwrap_or_default();
+        }
+    }
+
+    /// Build inverse relationships (related_by, blocked_by, superseded_by, children)
+    fn build_inverse_relations(&self, specs: &mut [Spec]) {
+        let mut inverses: HashMap<String, Vec<(RelationKind, String)>> = HashMap::new();
+        let lookup = SpecLookup::new(specs.iter());
+        for spec in specs.iter() {
+            for kind in RelationKind::ALL {
+                if kind == RelationKind::DependsOn {
                     continue;
                 }
-                // Check if any dependency matches this spec
-                for dep in deps {
-                    if dependency_matches(dep, &spec.spec_name, spec.spec_number) {
-                        required_by.push(name.clone());
-                        break;
+                for reference in spec.references(kind) {
+                    let Some(target) = lookup.find(reference) else {
+                        continue;
+                    };
+                    if target.spec_name == spec.spec_name {
+                        continue;
+                    }
+                    let entry = (kind, spec.spec_name.clone());
+                    let targets = inverses.entry(target.spec_name.clone()).or_default();
+                    if !targets.contains(&entry) {
+                        targets.push(entry);
                     }
                 }
             }
-            spec.required_by = required_by;
+        }
+
+        for spec in specs.iter_mut() {
+            for (kind, name) in inverses.remove(&spec.spec_name).unwrap_or_default() {
+                if let Some(inverse) = spec.relations.inverse_mut(kind) {
+                    inverse.push(name);
+                }
+            }
+            spec.relations.archived_hint =
+                !spec.relations.superseded_by.is_empty() && spec.status != "archived";
         }
     }
 
     /// Load a single spec by ID or number
-    pub fn load_spec(&self, spec_id: &str) -> Option<Spec> {
+    ///
+    /// Fails when the spec does not exist, or when a number is shared by
+    /// several specs; the error then lists the candidates.
+    pub fn load_spec(&self, spec_id: &str) -> Result<Spec, String> {
         let specs = self.load_all();
 
-        // Try to parse as number first
+        // Try to parse as number first; a number shared by several specs is ambiguous
         if let Ok(num) = spec_id.parse::<i32>() {
-            return specs.into_iter().find(|s| s.spec_number == Some(num));
+            let mut matches: Vec<Spec> = specs
+                .into_iter()
+                .filter(|s| s.spec_number == Some(num))
+                .collect();
+            return match matches.len() {
+                0 => Err(format!("Spec '{}' not found", spec_id)),
+                1 => Ok(matches.remove(0)),
+                _ => {
+                    let names: Vec<&str> = matches.iter().map(|s| s.spec_name.as_str()).collect();
+                    Err(format!(
+                        "Spec '{}' is ambiguous: matches {}",
+                        spec_id,
+                        names.join(", ")
+                    ))
+                }
+            };
         }
 
         // Try to find by spec_name (could be partial like "035" or full like "035-my-spec")
-        specs.into_iter().find(|s| {
-            s.spec_name == spec_id
-                || s.spec_name.starts_with(&format!("{}-", spec_id))
-                || s.id == spec_id
-                || s.id == format!("fs-{}", spec_id)
-        })
+        specs
+            .into_iter()
+            .find(|s| {
+                s.spec_name == spec_id
+                    || s.spec_name.starts_with(&format!("{}-", spec_id))
+                    || s.id == spec_id
+                    || s.id == format!("fs-{}", spec_id)
+            })
+            .ok_or_else(|| format!("Spec '{}' not found", spec_id))
     }
 
     /// Get specs by status
@@ -323,21 +639,9 @@ impl SpecReader {
 
     /// Search specs by query
     pub fn search(&self, query: &str) -> Vec<Spec> {
-        let lower_query = query.to_lowercase();
         self.load_all()
             .into_iter()
-            .filter(|s| {
-                s.spec_name.to_lowercase().contains(&lower_query)
-                    || s.title
-                        .as_deref()
-                        .unwrap_or("")
-                        .to_lowercase()
-                        .contains(&lower_query)
-                    || s.content_md.to_lowercase().contains(&lower_query)
-                    || s.tags
-                        .iter()
-                        .any(|t| t.to_lowercase().contains(&lower_query))
-            })
+            .filter(|s| s.matches_query(query))
             .collect()
     }
 
@@ -355,24 +659,12 @@ impl SpecReader {
     }
 }
 
-/// Check if a dependency string matches a spec
-fn dependency_matches(dep: &str, spec_name: &str, spec_number: Option<i32>) -> bool {
-    // Direct match
-    if dep == spec_name {
-        return true;
-    }
-
-    // Match by number prefix (e.g., "035" matches "035-my-spec")
-    if let Some(num) = spec_number {
-        let dep_trimmed = dep.trim();
-        if let Some(dep_num_str) = dep_trimmed.split('-').next() {
-            if let Ok(dep_num) = dep_num_str.parse::<i32>() {
-                return dep_num == num;
-            }
-        }
-    }
-
-    false
+/// Check if a directory name matches the spec pattern (starts with a digit)
+fn is_spec_dir_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(false)
 }
 
 #[cfg(test)]
@@ -428,6 +720,87 @@ mod tests {
             .contains(&"002-second-spec".to_string()));
     }
 
+    #[test]
+    fn test_load_diagnostics() {
+        let temp = TempDir::new().unwrap();
+        let specs_dir = temp.path().join("specs");
+        fs::create_dir_all(&specs_dir).unwrap();
+
+        create_test_spec(&specs_dir, "001-ok", "status: planned", "# Ok");
+        create_test_spec(
+            &specs_dir,
+            "002-tab",
+            "status: planned\ntags:\n\t- ui",
+            "# Tab",
+        );
+        create_test_spec(&specs_dir, "003-no-status", "priority: high", "# No status");
+        fs::create_dir_all(specs_dir.join("004-empty")).unwrap();
+
+        let reader = SpecReader::new(&specs_dir, "test-project");
+        let (specs, diagnostics) = reader.load_all_with_diagnostics();
+
+        assert_eq!(specs.len(), 1);
+        let reasons: Vec<LoadFailure> = diagnostics.iter().map(|d| d.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                LoadFailure::InvalidFrontmatter,
+                LoadFailure::MissingStatus,
+                LoadFailure::MissingReadme
+            ]
+        );
+        assert_eq!(diagnostics[0].spec_name, "002-tab");
+        assert_eq!(diagnostics[0].line, Some(4));
+        assert!(!diagnostics[0].message.contains("at line"));
+    }
+
+    #[test]
+    fn test_inverse_relations() {
+        let temp = TempDir::new().unwrap();
+        let specs_dir = temp.path().join("specs");
+        fs::create_dir_all(&specs_dir).unwrap();
+
+        create_test_spec(&specs_dir, "001-epic", "status: in-progress", "# Epic");
+        create_test_spec(&specs_dir, "002-old", "status: complete", "# Old");
+        create_test_spec(
+            &specs_dir,
+            "003-new",
+            "status: planned\nparent: 001-epic\nsupersedes:\n  - 002\nblocks:\n  - 004-next",
+            "# New",
+        );
+        create_test_spec(
+            &specs_dir,
+            "004-next",
+            "status: planned\nrelated: [001]",
+            "# Next",
+        );
+
+        let reader = SpecReader::new(&specs_dir, "test-project");
+        let specs = reader.load_all();
+
+        assert_eq!(specs[0].relations.children, vec!["003-new"]);
+        assert_eq!(specs[0].relations.related_by, vec!["004-next"]);
+        assert_eq!(specs[1].relations.superseded_by, vec!["003-new"]);
+        assert!(specs[1].relations.archived_hint);
+        assert_eq!(specs[3].relations.blocked_by, vec!["003-new"]);
+        assert!(!specs[2].relations.archived_hint);
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_spec_files() {
+        let temp = TempDir::new().unwrap();
+        let specs_dir = temp.path().join("specs");
+        fs::create_dir_all(&specs_dir).unwrap();
+        create_test_spec(&specs_dir, "001-first", "status: planned", "# First");
+
+        let reader = SpecReader::new(&specs_dir, "test-project");
+        let before = reader.fingerprint();
+        assert_eq!(before, reader.fingerprint());
+
+        create_test_spec(&specs_dir, "002-second", "status: planned", "# Second");
+        assert_ne!(before, reader.fingerprint());
+    }
+
     #[test]
     fn test_search_specs() {
         let temp = TempDir::new().unwrap();
@@ -459,14 +832,42 @@ mod tests {
     }
 
     #[test]
-    fn test_dependency_matches() {
-        assert!(dependency_matches(
-            "001-first-spec",
-            "001-first-spec",
-            Some(1)
-        ));
-        assert!(dependency_matches("001", "001-first-spec", Some(1)));
-        assert!(dependency_matches("1", "001-first-spec", Some(1)));
-        assert!(!dependency_matches("002", "001-first-spec", Some(1)));
+    fn test_ambiguous_numbers() {
+        let temp = TempDir::new().unwrap();
+        let specs_dir = temp.path().join("specs");
+        fs::create_dir_all(&specs_dir).unwrap();
+
+        create_test_spec(&specs_dir, "001-first", "status: planned", "# First");
+        create_test_spec(
+            &specs_dir,
+            "001-duplicate",
+            "status: planned",
+            "# Duplicate",
+        );
+        create_test_spec(
+            &specs_dir,
+            "002-second",
+            "status: planned\ndepends_on:\n  - 001\n  - 001-first\nrelated:\n  - \"1\"",
+            "# Second",
+        );
+
+        let reader = SpecReader::new(&specs_dir, "test-project");
+        let specs = reader.load_all();
+        let spec = |name: &str| specs.iter().find(|s| s.spec_name == name).unwrap();
+
+        assert_eq!(spec("001-first").required_by, vec!["002-second"]);
+        assert!(spec("001-duplicate").required_by.is_empty());
+        assert!(spec("001-first").relations.related_by.is_empty());
+
+        assert_eq!(reader.load_spec("2").unwrap().spec_name, "002-second");
+        assert_eq!(
+            reader.load_spec("001-first").unwrap().spec_name,
+            "001-first"
+        );
+        assert_eq!(
+            reader.load_spec("1").unwrap_err(),
+            "Spec '1' is ambiguous: matches 001-duplicate, 001-first"
+        );
+        assert_eq!(reader.load_spec("9").unwrap_err(), "Spec '9' not found");
     }
 }
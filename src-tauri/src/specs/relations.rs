@@ -0,0 +1,155 @@
+//! Typed relationships between specs
+//!
+//! Besides `depends_on`, frontmatter can declare `related`, `blocks`,
+//! `supersedes` and `parent` relationships. Each kind has its own edge type
+//! in the dependency graph and its own computed inverse.
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of relationship declared in frontmatter
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum RelationKind {
+    DependsOn,
+    Related,
+    Blocks,
+    Supersedes,
+    Parent,
+}
+
+impl RelationKind {
+    /// All relationship kinds, in display order
+    pub const ALL: [RelationKind; 5] = [
+        RelationKind::DependsOn,
+        RelationKind::Related,
+        RelationKind::Blocks,
+        RelationKind::Supersedes,
+        RelationKind::Parent,
+    ];
+
+    /// Edge type used in the dependency graph
+    pub fn edge_type(self) -> &'static str {
+        match self {
+            RelationKind::DependsOn => "dependsOn",
+            RelationKind::Related => "related",
+            RelationKind::Blocks => "blocks",
+            RelationKind::Supersedes => "supersedes",
+            RelationKind::Parent => "parent",
+        }
+    }
+
+    /// Frontmatter key the relationship is declared with
+    pub fn field_name(self) -> &'static str {
+        match self {
+            RelationKind::DependsOn => "depends_on",
+            RelationKind::Related => "related",
+            RelationKind::Blocks => "blocks",
+            RelationKind::Supersedes => "supersedes",
+            RelationKind::Parent => "parent",
+        }
+    }
+
+    /// Validation code reported when a reference cannot be resolved
+    pub fn broken_code(self) -> &'static str {
+        match self {
+            RelationKind::DependsOn => "broken-dependency",
+            RelationKind::Related => "broken-related",
+            RelationKind::Blocks => "broken-blocks",
+            RelationKind::Supersedes => "broken-supersedes",
+            RelationKind::Parent => "broken-parent",
+        }
+    }
+
+    /// Orient a graph edge for a relationship declared by `declaring` on `referenced`
+    ///
+    /// Edges point from prerequisite to dependent: a dependency points to the
+    /// spec that depends on it and a parent points to its child. The other
+    /// kinds point from the declaring spec to the referenced one.
+    pub fn edge_endpoints<'a>(self, declaring: &'a str, referenced: &'a str) -> (&'a str, &'a str) {
+        match self {
+            RelationKind::DependsOn | RelationKind::Parent => (referenced, declaring),
+            _ => (declaring, referenced),
+        }
+    }
+}
+
+/// Relationships beyond `depends_on`, declared and computed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecRelations {
+    /// Declared in frontmatter
+    #[serde(default)]
+    pub related: Vec<String>,
+    #[serde(default)]
+    pub blocks: Vec<String>,
+    #[serde(default)]
+    pub supersedes: Vec<String>,
+    pub parent: Option<String>,
+    /// Computed inverses
+    #[serde(default)]
+    pub related_by: Vec<String>,
+    #[serde(default)]
+    pub blocked_by: Vec<String>,
+    #[serde(default)]
+    pub superseded_by: Vec<String>,
+    #[serde(default)]
+    pub children: Vec<String>,
+    /// Set when another spec supersedes this one but it isn't archived yet
+    #[serde(default)]
+    pub archived_hint: bool,
+}
+
+impl SpecRelations {
+    /// Declared references for a non-`depends_on` kind
+    pub fn declared(&self, kind: RelationKind) -> Vec<&str> {
+        match kind {
+            RelationKind::DependsOn => Vec::new(),
+            RelationKind::Related => self.related.iter().map(String::as_str).collect(),
+            RelationKind::Blocks => self.blocks.iter().map(String::as_str).collect(),
+            RelationKind::Supersedes => self.supersedes.iter().map(String::as_str).collect(),
+            RelationKind::Parent => self.parent.as_deref().into_iter().collect(),
+        }
+    }
+
+    /// Mutable computed inverse list for a non-`depends_on` kind
+    pub fn inverse_mut(&mut self, kind: RelationKind) -> Option<&mut Vec<String>> {
+        match kind {
+            RelationKind::DependsOn => None,
+            RelationKind::Related => Some(&mut self.related_by),
+            RelationKind::Blocks => Some(&mut self.blocked_by),
+            RelationKind::Supersedes => Some(&mut self.superseded_by),
+            RelationKind::Parent => Some(&mut self.children),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_endpoints() {
+        assert_eq!(RelationKind::DependsOn.edge_endpoints("b", "a"), ("a", "b"));
+        assert_eq!(
+            RelationKind::Parent.edge_endpoints("child", "p"),
+            ("p", "child")
+        );
+        assert_eq!(RelationKind::Blocks.edge_endpoints("a", "b"), ("a", "b"));
+        assert_eq!(
+            RelationKind::Supersedes.edge_endpoints("new", "old"),
+            ("new", "old")
+        );
+    }
+
+    #[test]
+    fn test_declared() {
+        let relations = SpecRelations {
+            related: vec!["001-a".to_string()],
+            parent: Some("002-b".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(relations.declared(RelationKind::Related), vec!["001-a"]);
+        assert_eq!(relations.declared(RelationKind::Parent), vec!["002-b"]);
+        assert!(relations.declared(RelationKind::Blocks).is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+//! Project frontmatter schema
+//!
+//! A project can describe its custom frontmatter fields (`epic`, `estimate`,
+//! `reviewers`, ...) with a JSON Schema in
+//! `<project>/.lean-spec/frontmatter.schema.json`. Each spec's full
+//! frontmatter is validated against it, and keys that are neither built in nor
+//! declared in the schema's `properties` are reported with the closest known
+//! key as a suggestion.
+
+use serde_json::Value;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+const SCHEMA_DIR: &str = ".lean-spec";
+const SCHEMA_FILE: &str = "frontmatter.schema.json";
+
+/// Minimum similarity for a known key to be suggested for an unknown one
+const SUGGESTION_THRESHOLD: f64 = 0.7;
+
+/// Frontmatter keys LeanSpec understands without a schema
+const BUILTIN_FIELDS: &[&str] = &[
+    "status",
+    "priority",
+    "tags",
+    "assignee",
+    "created",
+    "created_at",
+    "createdAt",
+    "updated_at",
+    "updatedAt",
+    "completed_at",
+    "completedAt",
+    "depends_on",
+    "dependsOn",
+    "related",
+    "blocks",
+    "supersedes",
+    "parent",
+    "transitions",
+];
+
+/// A compiled frontmatter schema
+#[derive(Debug)]
+pub struct FrontmatterSchema {
+    /// The schema as written, for the UI
+    pub schema: Value,
+    validator: jsonschema::Validator,
+}
+
+/// A frontmatter value that does not satisfy the schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// JSON pointer segments to the failing value; empty for the whole frontmatter
+    pub path: Vec<String>,
+    pub message: String,
+}
+
+impl SchemaViolation {
+    /// Path as written in YAML terms, such as `reviewers[0]`
+    pub fn display_path(&self) -> String {
+        let mut display = String::new();
+        for segment in &self.path {
+            if segment.parse::<usize>().is_ok() && !display.is_empty() {
+                display.push_str(&format!("[{}]", segment));
+            } else {
+                if !display.is_empty() {
+                    display.push('.');
+                }
+                display.push_str(segment);
+            }
+        }
+        display
+    }
+}
+
+/// A frontmatter key unknown to LeanSpec and the schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownField {
+    pub key: String,
+    pub suggestion: Option<String>,
+}
+
+impl FrontmatterSchema {
+    /// Compile a schema
+    pub fn new(schema: Value) -> Result<Self, String> {
+        let validator = jsonschema::validator_for(&schema).map_err(|e| e.to_string())?;
+        Ok(Self { schema, validator })
+    }
+
+    /// Load the project's schema, or `None` when the project has none
+    ///
+    /// A schema file that can't be read, parsed or compiled is an error
+    /// naming the file, rather than silently skipping schema validation.
+    pub fn load(project_root: impl AsRef<Path>) -> Result<Option<Self>, String> {
+        let path = project_root.as_ref().join(SCHEMA_DIR).join(SCHEMA_FILE);
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+
+        serde_json::from_str(&raw)
+            .map_err(|e| e.to_string())
+            .and_then(Self::new)
+            .map(Some)
+            .map_err(|e| format!("Invalid frontmatter schema in {}: {}", path.display(), e))
+    }
+
+    /// Validate a spec's frontmatter
+    pub fn validate(&self, frontmatter: &Value) -> Vec<SchemaViolation> {
+        self.validator
+            .iter_errors(frontmatter)
+            .map(|error| SchemaViolation {
+                path: error
+                    .instance_path
+                    .to_string()
+                    .split('/')
+                    .skip(1)
+                    .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+                    .collect(),
+                message: error.to_string(),
+            })
+            .collect()
+    }
+
+    /// Top-level keys neither built in nor declared in the schema's `properties`
+    pub fn unknown_fields(&self, frontmatter: &Value) -> Vec<UnknownField> {
+        let Some(fields) = frontmatter.as_object() else {
+            return Vec::new();
+        };
+        let declared: Vec<&str> = self
+            .schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(|properties| properties.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+        let known: Vec<&str> = BUILTIN_FIELDS.iter().copied().chain(declared).collect();
+
+        fields
+            .keys()
+            .filter(|key| !known.contains(&key.as_str()))
+            .map(|key| UnknownField {
+                key: key.clone(),
+                suggestion: closest(key, &known),
+            })
+            .collect()
+    }
+}
+
+/// The known key most similar to `key`, if similar enough
+fn closest(key: &str, known: &[&str]) -> Option<String> {
+    let key = key.to_lowercase();
+    known
+        .iter()
+        .map(|candidate| {
+            let similarity = strsim::jaro_winkler(&key, &candidate.to_lowercase());
+            (candidate, similarity)
+        })
+        .filter(|(_, similarity)| *similarity >= SUGGESTION_THRESHOLD)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(candidate, _)| candidate.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn schema() -> FrontmatterSchema {
+        FrontmatterSchema::new(json!({
+            "type": "object",
+            "required": ["status"],
+            "properties": {
+                "epic": { "type": "string" },
+                "estimate": { "type": "number", "minimum": 0 },
+                "reviewers": { "type": "array", "items": { "type": "string" } }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_frontmatter() {
+        let frontmatter = json!({
+            "status": "planned",
+            "estimate": -1,
+            "reviewers": ["alice", 42]
+        });
+
+        let mut paths: Vec<String> = schema()
+            .validate(&frontmatter)
+            .iter()
+            .map(SchemaViolation::display_path)
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec!["estimate", "reviewers[1]"]);
+
+        let missing = schema().validate(&json!({ "epic": "auth" }));
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].path.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_fields() {
+        let frontmatter = json!({
+            "status": "planned",
+            "reviewer": ["alice"],
+            "estimat": 3,
+            "epic": "auth",
+            "zzz": true
+        });
+
+        let mut unknown = schema().unknown_fields(&frontmatter);
+        unknown.sort_by(|a, b| a.key.cmp(&b.key));
+        let suggestions: Vec<(&str, Option<&str>)> = unknown
+            .iter()
+            .map(|u| (u.key.as_str(), u.suggestion.as_deref()))
+            .collect();
+        assert_eq!(
+            suggestions,
+            vec![
+                ("estimat", Some("estimate")),
+                ("reviewer", Some("reviewers")),
+                ("zzz", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_schema() {
+        let temp = TempDir::new().unwrap();
+        assert!(FrontmatterSchema::load(temp.path()).unwrap().is_none());
+
+        let dir = temp.path().join(SCHEMA_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(SCHEMA_FILE), r#"{ "type": "object" }"#).unwrap();
+        assert!(FrontmatterSchema::load(temp.path()).unwrap().is_some());
+
+        fs::write(dir.join(SCHEMA_FILE), r#"{ "type": 12 }"#).unwrap();
+        let error = FrontmatterSchema::load(temp.path()).unwrap_err();
+        assert!(error.starts_with("Invalid frontmatter schema in"));
+    }
+}
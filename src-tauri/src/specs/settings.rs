@@ -0,0 +1,216 @@
+//! Per-project analysis settings
+//!
+//! Read from `<project>/.lean-spec/config.json`. Every section is optional and
+//! falls back to defaults, so existing projects need no configuration.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::specs::tokens::TokenizerKind;
+use crate::specs::validation::rules::RuleParams;
+use crate::specs::validation::IssueSeverity;
+
+const SETTINGS_DIR: &str = ".lean-spec";
+const SETTINGS_FILE: &str = "config.json";
+
+/// Settings for a single project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSettings {
+    #[serde(default)]
+    pub staleness: StalenessSettings,
+    #[serde(default)]
+    pub tokens: TokenSettings,
+    #[serde(default)]
+    pub validation: ValidationSettings,
+}
+
+/// Thresholds for stale and neglected spec detection, in days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StalenessSettings {
+    /// `in-progress` specs with no file change for this long
+    pub in_progress_days: i64,
+    /// `planned` specs without dependents waiting this long
+    pub planned_days: i64,
+    /// `draft` specs never touched since creation for this long
+    pub draft_days: i64,
+    /// Assignees with no activity for this long
+    pub assignee_inactive_days: i64,
+}
+
+impl Default for StalenessSettings {
+    fn default() -> Self {
+        Self {
+            in_progress_days: 14,
+            planned_days: 60,
+            draft_days: 30,
+            assignee_inactive_days: 21,
+        }
+    }
+}
+
+/// Token counting and spec size thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TokenSettings {
+    /// Count with this tokenizer regardless of the configured model
+    pub tokenizer: Option<TokenizerKind>,
+    /// Count for this model instead of the default chat model
+    pub model: Option<String>,
+    /// Specs above this many tokens are flagged as getting large
+    pub warning_tokens: usize,
+    /// Specs above this many tokens should be split
+    pub max_tokens: usize,
+}
+
+impl Default for TokenSettings {
+    fn default() -> Self {
+        Self {
+            tokenizer: None,
+            model: None,
+            warning_tokens: 3500,
+            max_tokens: 5000,
+        }
+    }
+}
+
+/// Validation rule configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ValidationSettings {
+    /// Per-rule overrides, keyed by issue code
+    pub rules: HashMap<String, RuleSettings>,
+    /// Markdown sections (heading text) required for specs in a status
+    pub required_sections: HashMap<String, Vec<String>>,
+}
+
+/// Overrides for a single validation rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleSettings {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub severity: Option<IssueSeverity>,
+    #[serde(default)]
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ValidationSettings {
+    pub fn is_enabled(&self, code: &str) -> bool {
+        self.rules.get(code).map(|r| r.enabled).unwrap_or(true)
+    }
+
+    /// Configured severity for a rule, if overridden
+    pub fn severity(&self, code: &str) -> Option<IssueSeverity> {
+        self.rules.get(code).and_then(|r| r.severity.clone())
+    }
+
+    pub fn params(&self, code: &str) -> RuleParams<'_> {
+        self.rules
+            .get(code)
+            .map(|r| RuleParams::new(&r.params))
+            .unwrap_or_default()
+    }
+
+    pub fn required_sections_for(&self, status: &str) -> &[String] {
+        self.required_sections
+            .get(status)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+impl ProjectSettings {
+    /// Load settings for a project root, using defaults when absent
+    ///
+    /// A settings file that can't be read or parsed is an error naming the
+    /// file, so a typo doesn't silently reset every threshold to its default.
+    pub fn load(project_root: impl AsRef<Path>) -> Result<Self, String> {
+        let path = project_root.as_ref().join(SETTINGS_DIR).join(SETTINGS_FILE);
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+
+        serde_json::from_str(&raw)
+            .map_err(|e| format!("Invalid project settings in {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_partial_settings() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join(".lean-spec");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("config.json"),
+            r#"{
+                "specsDir": "specs",
+                "staleness": { "inProgressDays": 7 },
+                "tokens": { "tokenizer": "o200k", "maxTokens": 8000 },
+                "validation": {
+                    "rules": {
+                        "missing-overview": { "enabled": false },
+                        "invalid-priority": { "severity": "error" }
+                    },
+                    "requiredSections": { "complete": ["Test Plan"] }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let settings = ProjectSettings::load(temp.path()).unwrap();
+
+        assert_eq!(settings.staleness.in_progress_days, 7);
+        assert_eq!(settings.staleness.planned_days, 60);
+        assert_eq!(settings.tokens.tokenizer, Some(TokenizerKind::O200k));
+        assert_eq!(settings.tokens.max_tokens, 8000);
+        assert_eq!(settings.tokens.warning_tokens, 3500);
+
+        let validation = &settings.validation;
+        assert!(!validation.is_enabled("missing-overview"));
+        assert!(validation.is_enabled("invalid-priority"));
+        assert_eq!(
+            validation.severity("invalid-priority"),
+            Some(IssueSeverity::Error)
+        );
+        assert_eq!(validation.required_sections_for("complete"), ["Test Plan"]);
+        assert!(validation.required_sections_for("planned").is_empty());
+    }
+
+    #[test]
+    fn test_missing_settings_use_defaults() {
+        let temp = TempDir::new().unwrap();
+        let settings = ProjectSettings::load(temp.path()).unwrap();
+        assert_eq!(settings.staleness.draft_days, 30);
+    }
+
+    #[test]
+    fn test_invalid_settings_are_an_error() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join(".lean-spec");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("config.json"),
+            r#"{ "staleness": { "inProgressDays": "soon" } }"#,
+        )
+        .unwrap();
+
+        let error = ProjectSettings::load(temp.path()).unwrap_err();
+        assert!(error.starts_with("Invalid project settings in"));
+    }
+}
@@ -0,0 +1,431 @@
+//! Stale and neglected spec detection
+//!
+//! Flags `in-progress` specs with no recent file changes, `planned` specs
+//! nobody depends on that have waited too long, `draft` specs never touched
+//! since creation, and open specs whose assignee has gone quiet. Commits and
+//! branches mentioning a spec count as activity on it, so a spec coded
+//! against without editing its README is not stale. Thresholds come from the
+//! project's `StalenessSettings`.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::specs::assignees::AssigneeAliases;
+use crate::specs::flow::SpecTimeline;
+use crate::specs::git::FileCommit;
+use crate::specs::reader::Spec;
+use crate::specs::settings::StalenessSettings;
+use crate::specs::validation::{IssueSeverity, ValidationIssue};
+
+/// Why a spec was flagged
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StaleReason {
+    StaleInProgress,
+    NeglectedPlanned,
+    UntouchedDraft,
+    InactiveAssignee,
+}
+
+impl StaleReason {
+    /// Validation issue code for the reason
+    pub fn code(self) -> &'static str {
+        match self {
+            StaleReason::StaleInProgress => "stale-in-progress",
+            StaleReason::NeglectedPlanned => "neglected-planned",
+            StaleReason::UntouchedDraft => "untouched-draft",
+            StaleReason::InactiveAssignee => "inactive-assignee",
+        }
+    }
+
+    fn severity(self) -> IssueSeverity {
+        match self {
+            StaleReason::StaleInProgress | StaleReason::InactiveAssignee => IssueSeverity::Warning,
+            StaleReason::NeglectedPlanned | StaleReason::UntouchedDraft => IssueSeverity::Info,
+        }
+    }
+}
+
+/// A flagged spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleSpec {
+    pub spec_name: String,
+    pub title: Option<String>,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub reason: StaleReason,
+    /// Days since the relevant activity
+    pub days: i64,
+    pub message: String,
+}
+
+impl StaleSpec {
+    /// Express the finding as a validation issue
+    pub fn to_issue(&self) -> ValidationIssue {
+        ValidationIssue {
+            severity: self.reason.severity(),
+            code: self.reason.code().to_string(),
+            message: self.message.clone(),
+            line: None,
+            span: None,
+            edge: None,
+        }
+    }
+}
+
+/// Result of stale spec detection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StalenessReport {
+    pub thresholds: StalenessSettings,
+    pub specs: Vec<StaleSpec>,
+}
+
+/// Recent activity per spec directory and per git author
+#[derive(Debug, Default)]
+pub struct ActivityIndex {
+    last_changed: HashMap<String, DateTime<Utc>>,
+    commit_counts: HashMap<String, usize>,
+    code_activity: HashMap<String, DateTime<Utc>>,
+    authors: HashMap<String, DateTime<Utc>>,
+    aliases: AssigneeAliases,
+}
+
+impl ActivityIndex {
+    /// Index commits whose paths are relative to the specs directory
+    pub fn from_commits(commits: &[FileCommit]) -> Self {
+        let mut index = Self::default();
+
+        for commit in commits {
+            let mut touched: Vec<&str> = commit
+                .paths
+                .iter()
+                .filter_map(|path| spec_dir_of(path))
+                .collect();
+            touched.sort_unstable();
+            touched.dedup();
+            if touched.is_empty() {
+                continue;
+            }
+
+            for spec_name in touched {
+                let last = index
+                    .last_changed
+                    .entry(spec_name.to_string())
+                    .or_insert(commit.at);
+                *last = (*last).max(commit.at);
+                *index
+                    .commit_counts
+                    .entry(spec_name.to_string())
+                    .or_insert(0) += 1;
+            }
+
+            let email = commit.author_email.to_lowercase();
+            let local_part = email.split('@').next().unwrap_or_default().to_string();
+            for key in [commit.author_name.to_lowercase(), email, local_part] {
+                if key.is_empty() {
+                    continue;
+                }
+                let last = index.authors.entry(key).or_insert(commit.at);
+                *last = (*last).max(commit.at);
+            }
+        }
+
+        index
+    }
+
+    /// Resolve assignees and commit authors through project aliases
+    pub fn with_aliases(mut self, aliases: AssigneeAliases) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Record a change time for a spec without git history (e.g. file mtime)
+    pub fn record_fallback(&mut self, spec_name: &str, at: DateTime<Utc>) {
+        self.last_changed.entry(spec_name.to_string()).or_insert(at);
+    }
+
+    /// Record the latest commit or branch mentioning a spec
+    pub fn record_code_activity(&mut self, spec_name: &str, at: DateTime<Utc>) {
+        let last = self
+            .code_activity
+            .entry(spec_name.to_string())
+            .or_insert(at);
+        *last = (*last).max(at);
+    }
+
+    /// Whether the spec has any recorded activity
+    pub fn contains(&self, spec_name: &str) -> bool {
+        self.last_changed.contains_key(spec_name)
+    }
+
+    fn last_changed(&self, spec: &Spec) -> Option<DateTime<Utc>> {
+        let changed = self
+            .last_changed
+            .get(&spec.spec_name)
+            .copied()
+            .or(spec.updated_at)
+            .or(spec.created_at);
+        match (changed, self.code_activity.get(&spec.spec_name)) {
+            (Some(changed), Some(coded)) => Some(changed.max(*coded)),
+            (changed, coded) => changed.or(coded.copied()),
+        }
+    }
+
+    fn author_last_active(&self, assignee: &str) -> Option<DateTime<Utc>> {
+        let assignee = self.aliases.resolve(assignee);
+        self.authors
+            .iter()
+            .filter(|(author, _)| self.aliases.resolve(author) == assignee)
+            .map(|(_, at)| *at)
+            .max()
+    }
+}
+
+/// Spec directory name for a path relative to the specs directory
+fn spec_dir_of(path: &str) -> Option<&str> {
+    let mut parts = path.split('/');
+    let first = parts.next()?;
+    let dir = if first == "archived" {
+        parts.next()?
+    } else {
+        first
+    };
+    parts.next()?;
+    dir.starts_with(|c: char| c.is_ascii_digit()).then_some(dir)
+}
+
+/// Find stale and neglected specs
+pub fn detect_stale_specs(
+    specs: &[Spec],
+    activity: &ActivityIndex,
+    settings: &StalenessSettings,
+    now: DateTime<Utc>,
+) -> StalenessReport {
+    let days_since = |at: DateTime<Utc>| (now - at).num_days();
+    let mut flagged = Vec::new();
+
+    // Last activity per assignee: git authorship, else changes to their specs
+    let mut assignee_activity: HashMap<String, DateTime<Utc>> = HashMap::new();
+    for spec in specs {
+        let (Some(assignee), Some(changed)) = (&spec.assignee, activity.last_changed(spec)) else {
+            continue;
+        };
+        let last = assignee_activity
+            .entry(activity.aliases.resolve(assignee))
+            .or_insert(changed);
+        *last = (*last).max(changed);
+    }
+
+    for spec in specs {
+        let last_changed = activity.last_changed(spec);
+        let mut flag = |reason: StaleReason, days: i64, message: String| {
+            flagged.push(StaleSpec {
+                spec_name: spec.spec_name.clone(),
+                title: spec.title.clone(),
+                status: spec.status.clone(),
+                assignee: spec.assignee.clone(),
+                reason,
+                days,
+                message,
+            });
+        };
+
+        match spec.status.as_str() {
+            "in-progress" => {
+                if let Some(days) = last_changed.map(days_since) {
+                    if days > settings.in_progress_days {
+                        flag(
+                            StaleReason::StaleInProgress,
+                            days,
+                            format!("In progress with no changes for {} days", days),
+                        );
+                    }
+                }
+            }
+            "planned" if spec.required_by.is_empty() => {
+                let timeline = SpecTimeline::for_spec(spec, Vec::new);
+                let planned_since = timeline
+                    .events
+                    .iter()
+                    .rev()
+                    .find(|e| e.status == "planned")
+                    .map(|e| e.at)
+                    .or(spec.created_at)
+                    .or(last_changed);
+                if let Some(days) = planned_since.map(days_since) {
+                    if days > settings.planned_days {
+                        flag(
+                            StaleReason::NeglectedPlanned,
+                            days,
+                            format!("Planned for {} days and nothing depends on it", days),
+                        );
+                    }
+                }
+            }
+            "draft" => {
+                let created = spec.created_at.or(last_changed);
+                let touched_commits = activity
+                    .commit_counts
+                    .get(&spec.spec_name)
+                    .copied()
+                    .unwrap_or(0)
+                    > 1;
+                let touched_updated = match (spec.updated_at, created) {
+                    (Some(updated), Some(created)) => updated - created > Duration::days(1),
+                    _ => false,
+                };
+                if let Some(days) = created.map(days_since) {
+                    if !touched_commits && !touched_updated && days > settings.draft_days {
+                        flag(
+                            StaleReason::UntouchedDraft,
+                            days,
+                            format!("Draft untouched since it was created {} days ago", days),
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if matches!(spec.status.as_str(), "planned" | "in-progress") {
+            if let Some(assignee) = &spec.assignee {
+                let last_active = activity.author_last_active(assignee).or_else(|| {
+                    assignee_activity
+                        .get(&activity.aliases.resolve(assignee))
+                        .copied()
+                });
+                if let Some(days) = last_active.map(days_since) {
+                    if days > settings.assignee_inactive_days {
+                        flag(
+                            StaleReason::InactiveAssignee,
+                            days,
+                            format!(
+                                "Assignee '{}' has not changed anything in {} days",
+                                assignee, days
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    flagged.sort_by(|a, b| b.days.cmp(&a.days).then(a.spec_name.cmp(&b.spec_name)));
+    StalenessReport {
+        thresholds: settings.clone(),
+        specs: flagged,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::reader::SpecBuilder;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn spec(name: &str, status: &str, created_days_ago: i64, assignee: Option<&str>) -> Spec {
+        SpecBuilder::new(name)
+            .status(status)
+            .assignee(assignee)
+            .content(&format!("---\nstatus: {}\n---\n", status))
+            .created_at(now() - Duration::days(created_days_ago))
+            .build()
+    }
+
+    fn commit(days_ago: i64, author: &str, paths: &[&str]) -> FileCommit {
+        FileCommit {
+            at: now() - Duration::days(days_ago),
+            author_name: author.to_string(),
+            author_email: format!("{}@example.com", author.to_lowercase()),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    fn reasons(report: &StalenessReport, name: &str) -> Vec<StaleReason> {
+        report
+            .specs
+            .iter()
+            .filter(|s| s.spec_name == name)
+            .map(|s| s.reason)
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_stale_specs() {
+        let mut planned_with_dependents = spec("004-base", "planned", 120, None);
+        planned_with_dependents.required_by = vec!["005-next".to_string()];
+        let specs = vec![
+            spec("001-zombie", "in-progress", 90, None),
+            spec("002-active", "in-progress", 90, None),
+            spec("003-waiting", "planned", 120, None),
+            planned_with_dependents,
+            spec("006-idea", "draft", 45, None),
+            spec("007-edited-idea", "draft", 45, None),
+            spec("008-coded", "in-progress", 90, None),
+        ];
+        let mut activity = ActivityIndex::from_commits(&[
+            commit(2, "Alice", &["002-active/README.md"]),
+            commit(30, "Alice", &["001-zombie/README.md"]),
+            commit(40, "Alice", &["007-edited-idea/README.md"]),
+            commit(
+                45,
+                "Alice",
+                &["007-edited-idea/README.md", "006-idea/README.md"],
+            ),
+            commit(30, "Alice", &["008-coded/README.md"]),
+        ]);
+        activity.record_code_activity("008-coded", now() - Duration::days(3));
+
+        let report = detect_stale_specs(&specs, &activity, &StalenessSettings::default(), now());
+
+        assert_eq!(
+            reasons(&report, "001-zombie"),
+            vec![StaleReason::StaleInProgress]
+        );
+        assert!(reasons(&report, "002-active").is_empty());
+        assert_eq!(
+            reasons(&report, "003-waiting"),
+            vec![StaleReason::NeglectedPlanned]
+        );
+        assert!(reasons(&report, "004-base").is_empty());
+        assert_eq!(
+            reasons(&report, "006-idea"),
+            vec![StaleReason::UntouchedDraft]
+        );
+        assert!(reasons(&report, "007-edited-idea").is_empty());
+        assert!(reasons(&report, "008-coded").is_empty());
+    }
+
+    #[test]
+    fn test_inactive_assignee() {
+        let specs = vec![
+            spec("001-a", "in-progress", 5, Some("@bob")),
+            spec("002-b", "planned", 5, Some("alice")),
+        ];
+        let activity = ActivityIndex::from_commits(&[
+            commit(1, "Alice", &["002-b/README.md"]),
+            commit(60, "Bob", &["001-a/README.md"]),
+        ]);
+        let settings = StalenessSettings {
+            in_progress_days: 90,
+            ..Default::default()
+        };
+
+        let report = detect_stale_specs(&specs, &activity, &settings, now());
+
+        assert_eq!(
+            reasons(&report, "001-a"),
+            vec![StaleReason::InactiveAssignee]
+        );
+        assert!(reasons(&report, "002-b").is_empty());
+        assert_eq!(report.specs[0].to_issue().code, "inactive-assignee");
+    }
+}
@@ -21,6 +21,11 @@ pub struct StatsResult {
     pub total_tags: i32,
     pub avg_tags_per_spec: f64,
     pub specs_with_dependencies: i32,
+    /// Task list items across all specs
+    pub tasks_total: i32,
+    pub tasks_done: i32,
+    /// Percentage of task list items checked off
+    pub task_completion_rate: f64,
 }
 
 /// Count by status
@@ -48,6 +53,8 @@ pub fn calculate_stats(specs: &[Spec]) -> StatsResult {
     let mut priority_counts: HashMap<String, i32> = HashMap::new();
     let mut total_tags = 0;
     let mut specs_with_dependencies = 0;
+    let mut tasks_total = 0;
+    let mut tasks_done = 0;
 
     for spec in specs {
         *status_counts.entry(spec.status.clone()).or_insert(0) += 1;
@@ -61,6 +68,9 @@ pub fn calculate_stats(specs: &[Spec]) -> StatsResult {
         if !spec.depends_on.is_empty() {
             specs_with_dependencies += 1;
         }
+
+        tasks_total += spec.tasks.tasks_total as i32;
+        tasks_done += spec.tasks.tasks_done as i32;
     }
 
     let complete_count = status_counts.get("complete").copied().unwrap_or(0);
@@ -74,6 +84,12 @@ pub fn calculate_stats(specs: &[Spec]) -> StatsResult {
         + status_counts.get("planned").copied().unwrap_or(0)
         + status_counts.get("in-progress").copied().unwrap_or(0);
 
+    let task_completion_rate = if tasks_total > 0 {
+        (tasks_done as f64 / tasks_total as f64) * 100.0
+    } else {
+        0.0
+    };
+
     let avg_tags_per_spec = if total_specs > 0 {
         total_tags as f64 / total_specs as f64
     } else {
@@ -111,35 +127,31 @@ pub fn calculate_stats(specs: &[Spec]) -> StatsResult {
         total_tags: unique_tags.len() as i32,
         avg_tags_per_spec: (avg_tags_per_spec * 100.0).round() / 100.0,
         specs_with_dependencies,
+        tasks_total,
+        tasks_done,
+        task_completion_rate: (task_completion_rate * 10.0).round() / 10.0,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use crate::specs::reader::SpecBuilder;
 
     fn create_test_spec(status: &str, priority: Option<&str>, tags: Vec<&str>, deps: Vec<&str>) -> Spec {
+        let content = match status {
+            "in-progress" => "## Plan\n\n- [x] Design\n- [ ] Build\n- [ ] Ship\n",
+            _ => "",
+        };
         Spec {
-            id: "test".to_string(),
-            project_id: "test".to_string(),
-            spec_number: Some(1),
-            spec_name: "test-spec".to_string(),
-            title: Some("Test".to_string()),
-            status: status.to_string(),
             priority: priority.map(String::from),
-            tags: tags.into_iter().map(String::from).collect(),
-            assignee: None,
-            content_md: String::new(),
-            content_html: None,
-            created_at: None,
-            updated_at: None,
-            completed_at: None,
-            file_path: "specs/test/README.md".to_string(),
-            github_url: None,
-            synced_at: Utc::now(),
-            depends_on: deps.into_iter().map(String::from).collect(),
-            required_by: Vec::new(),
+            ..SpecBuilder::new("test-spec")
+                .title("Test")
+                .status(status)
+                .tags(&tags)
+                .content(content)
+                .depends_on(deps.into_iter().map(String::from).collect())
+                .build()
         }
     }
 
@@ -159,6 +171,8 @@ mod tests {
         assert_eq!(stats.completion_rate, 50.0);
         assert_eq!(stats.total_tags, 4); // unique tags
         assert_eq!(stats.specs_with_dependencies, 2);
+        assert_eq!((stats.tasks_total, stats.tasks_done), (3, 1));
+        assert_eq!(stats.task_completion_rate, 33.3);
     }
 
     #[test]
@@ -0,0 +1,279 @@
+//! Tag taxonomy analytics and tag rewriting
+//!
+//! Reports how tags are used (per status, together, only once) and flags
+//! near-duplicates such as `ui` / `UI` / `frontend-ui` so they can be merged.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::specs::reader::Spec;
+use crate::specs::stats::StatusCount;
+
+/// Minimum normalized Levenshtein similarity for two tags to be flagged
+const SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// Usage of a single tag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagUsage {
+    pub tag: String,
+    pub count: i32,
+    pub by_status: Vec<StatusCount>,
+}
+
+/// Two tags used on the same specs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagPair {
+    pub first: String,
+    pub second: String,
+    pub count: i32,
+}
+
+/// Why two tags look like duplicates
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicateKind {
+    /// Same tag apart from case or separators (`ui` / `UI`, `dark-mode` / `dark_mode`)
+    Spelling,
+    /// One tag is a word of the other (`ui` / `frontend-ui`)
+    Contained,
+    /// Small edit distance (`frontend` / `fronted`)
+    Similar,
+}
+
+/// A pair of tags that are probably the same concept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NearDuplicateTags {
+    pub first: String,
+    pub second: String,
+    pub kind: DuplicateKind,
+    /// Normalized similarity from 0 to 1
+    pub similarity: f64,
+}
+
+/// A tag used by a single spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanTag {
+    pub tag: String,
+    pub spec_name: String,
+}
+
+/// Tag analytics for a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagAnalytics {
+    /// Most used first
+    pub tags: Vec<TagUsage>,
+    /// Most frequent pairs first
+    pub co_occurrence: Vec<TagPair>,
+    pub near_duplicates: Vec<NearDuplicateTags>,
+    pub orphans: Vec<OrphanTag>,
+}
+
+/// Analyze tag usage across specs
+pub fn analyze_tags(specs: &[Spec]) -> TagAnalytics {
+    let mut usage: BTreeMap<&str, BTreeMap<&str, i32>> = BTreeMap::new();
+    let mut first_spec: HashMap<&str, &str> = HashMap::new();
+    let mut pairs: BTreeMap<(&str, &str), i32> = BTreeMap::new();
+
+    for spec in specs {
+        let mut tags: Vec<&str> = spec.tags.iter().map(String::as_str).collect();
+        tags.sort_unstable();
+        tags.dedup();
+
+        for (i, tag) in tags.iter().enumerate() {
+            *usage
+                .entry(tag)
+                .or_default()
+                .entry(spec.status.as_str())
+                .or_insert(0) += 1;
+            first_spec.entry(tag).or_insert(spec.spec_name.as_str());
+            for other in &tags[i + 1..] {
+                *pairs.entry((tag, other)).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut tags: Vec<TagUsage> = usage
+        .iter()
+        .map(|(tag, statuses)| TagUsage {
+            tag: tag.to_string(),
+            count: statuses.values().sum(),
+            by_status: statuses
+                .iter()
+                .map(|(status, count)| StatusCount {
+                    status: status.to_string(),
+                    count: *count,
+                })
+                .collect(),
+        })
+        .collect();
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then(a.tag.cmp(&b.tag)));
+
+    let mut co_occurrence: Vec<TagPair> = pairs
+        .into_iter()
+        .map(|((first, second), count)| TagPair {
+            first: first.to_string(),
+            second: second.to_string(),
+            count,
+        })
+        .collect();
+    co_occurrence.sort_by_key(|p| Reverse(p.count));
+
+    let orphans = tags
+        .iter()
+        .filter(|t| t.count == 1)
+        .map(|t| OrphanTag {
+            tag: t.tag.clone(),
+            spec_name: first_spec[t.tag.as_str()].to_string(),
+        })
+        .collect();
+
+    let names: Vec<&str> = usage.keys().copied().collect();
+    TagAnalytics {
+        tags,
+        co_occurrence,
+        near_duplicates: find_near_duplicates(&names),
+        orphans,
+    }
+}
+
+/// Find pairs of tags that are likely spelling variants of each other
+fn find_near_duplicates(tags: &[&str]) -> Vec<NearDuplicateTags> {
+    let normalized: Vec<(String, Vec<String>)> = tags
+        .iter()
+        .map(|tag| {
+            let words: Vec<String> = tag
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .map(str::to_lowercase)
+                .collect();
+            (words.concat(), words)
+        })
+        .collect();
+
+    let mut duplicates = Vec::new();
+    for i in 0..tags.len() {
+        for j in i + 1..tags.len() {
+            let (a, a_words) = &normalized[i];
+            let (b, b_words) = &normalized[j];
+            if a.is_empty() || b.is_empty() {
+                continue;
+            }
+
+            let similarity = strsim::normalized_levenshtein(a, b);
+            let kind = if a == b {
+                DuplicateKind::Spelling
+            } else if (a_words.len() == 1 && b_words.contains(a))
+                || (b_words.len() == 1 && a_words.contains(b))
+            {
+                DuplicateKind::Contained
+            } else if similarity >= SIMILARITY_THRESHOLD {
+                DuplicateKind::Similar
+            } else {
+                continue;
+            };
+
+            duplicates.push(NearDuplicateTags {
+                first: tags[i].to_string(),
+                second: tags[j].to_string(),
+                kind,
+                similarity: (similarity * 100.0).round() / 100.0,
+            });
+        }
+    }
+    duplicates
+}
+
+/// Replace any of `sources` in a tag list with `target`
+///
+/// Keeps the position of the first replaced tag and drops duplicates.
+/// Returns `None` when none of the sources are present.
+pub fn replace_tags(tags: &[String], sources: &[String], target: &str) -> Option<Vec<String>> {
+    if !tags.iter().any(|t| sources.contains(t)) {
+        return None;
+    }
+
+    let mut updated: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = if sources.contains(tag) {
+            target
+        } else {
+            tag.as_str()
+        };
+        if !updated.iter().any(|t| t == tag) {
+            updated.push(tag.to_string());
+        }
+    }
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::reader::SpecBuilder;
+
+    fn spec(name: &str, status: &str, tags: &[&str]) -> Spec {
+        SpecBuilder::new(name).status(status).tags(tags).build()
+    }
+
+    #[test]
+    fn test_analyze_tags() {
+        let specs = vec![
+            spec("001-a", "planned", &["ui", "api"]),
+            spec("002-b", "complete", &["ui", "api"]),
+            spec("003-c", "planned", &["UI", "frontend-ui"]),
+            spec("004-d", "planned", &["ui", "database"]),
+        ];
+
+        let analytics = analyze_tags(&specs);
+
+        let ui = &analytics.tags[0];
+        assert_eq!((ui.tag.as_str(), ui.count), ("ui", 3));
+        assert_eq!(ui.by_status.len(), 2);
+
+        let top_pair = &analytics.co_occurrence[0];
+        assert_eq!(
+            (top_pair.first.as_str(), top_pair.second.as_str()),
+            ("api", "ui")
+        );
+        assert_eq!(top_pair.count, 2);
+
+        let orphans: Vec<&str> = analytics.orphans.iter().map(|o| o.tag.as_str()).collect();
+        assert_eq!(orphans, vec!["UI", "database", "frontend-ui"]);
+
+        let kind = |a: &str, b: &str| {
+            analytics
+                .near_duplicates
+                .iter()
+                .find(|d| (d.first == a && d.second == b) || (d.first == b && d.second == a))
+                .map(|d| d.kind)
+        };
+        assert_eq!(kind("UI", "ui"), Some(DuplicateKind::Spelling));
+        assert_eq!(kind("frontend-ui", "ui"), Some(DuplicateKind::Contained));
+        assert_eq!(kind("api", "ui"), None);
+    }
+
+    #[test]
+    fn test_near_duplicates_by_edit_distance() {
+        let duplicates = find_near_duplicates(&["frontend", "fronted", "backend"]);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].kind, DuplicateKind::Similar);
+    }
+
+    #[test]
+    fn test_replace_tags() {
+        let tags: Vec<String> = ["UI", "api", "ui"].iter().map(|t| t.to_string()).collect();
+        let sources = vec!["UI".to_string(), "frontend-ui".to_string()];
+
+        assert_eq!(
+            replace_tags(&tags, &sources, "ui").unwrap(),
+            vec!["ui", "api"]
+        );
+        assert!(replace_tags(&tags, &["db".to_string()], "database").is_none());
+    }
+}
@@ -0,0 +1,187 @@
+//! Markdown task lists
+//!
+//! Counts `- [ ]` and `- [x]` items in a spec's body, per section, and flips
+//! single checkboxes. Items in frontmatter and fenced code blocks are ignored.
+
+use serde::{Deserialize, Serialize};
+
+/// Task progress of a section, named after its nearest heading
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionTasks {
+    /// Heading text; empty for tasks before the first heading
+    pub section: String,
+    pub tasks_total: usize,
+    pub tasks_done: usize,
+}
+
+/// Task progress of a spec
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TaskProgress {
+    pub tasks_total: usize,
+    pub tasks_done: usize,
+    /// Sections with at least one task, in document order
+    pub task_sections: Vec<SectionTasks>,
+}
+
+/// A task list item
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskItem<'a> {
+    /// 1-based line number
+    pub line: usize,
+    pub done: bool,
+    pub text: &'a str,
+}
+
+impl TaskProgress {
+    /// Count the tasks in a spec's content
+    pub fn from_content(content: &str) -> Self {
+        let mut progress = Self::default();
+        for (section, task) in sectioned_tasks(content) {
+            progress.tasks_total += 1;
+            progress.tasks_done += usize::from(task.done);
+
+            let same_section = progress
+                .task_sections
+                .last()
+                .is_some_and(|s| s.section == section);
+            if !same_section {
+                progress.task_sections.push(SectionTasks {
+                    section: section.to_string(),
+                    tasks_total: 0,
+                    tasks_done: 0,
+                });
+            }
+            if let Some(current) = progress.task_sections.last_mut() {
+                current.tasks_total += 1;
+                current.tasks_done += usize::from(task.done);
+            }
+        }
+        progress
+    }
+}
+
+/// Parse a task list item (`- [ ] text`, `* [x] text`, `1. [X] text`)
+///
+/// Returns whether it is done and its text.
+pub fn task_item(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    let rest = trimmed.strip_prefix(['-', '*', '+']).or_else(|| {
+        let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+        (digits > 0)
+            .then(|| trimmed[digits..].strip_prefix(['.', ')']))
+            .flatten()
+    })?;
+    let rest = rest.strip_prefix(' ')?.trim_start();
+    let done = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    Some((done, rest[3..].trim()))
+}
+
+/// Task items of the body with the heading they fall under
+fn sectioned_tasks(content: &str) -> Vec<(&str, TaskItem<'_>)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    if lines.first().is_some_and(|l| l.trim_end() == "---") {
+        if let Some(close) = lines.iter().skip(1).position(|l| l.trim_end() == "---") {
+            start = close + 2;
+        }
+    }
+
+    let mut fence: Option<&str> = None;
+    let mut section = "";
+    let mut tasks = Vec::new();
+    for (index, line) in lines.iter().enumerate().skip(start) {
+        let trimmed = line.trim_start();
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            match fence {
+                Some(open) if open == marker => fence = None,
+                None => fence = Some(marker),
+                _ => {}
+            }
+            continue;
+        }
+        if fence.is_some() {
+            continue;
+        }
+
+        // The title heading does not start a section
+        if let Some(heading) = trimmed.strip_prefix("##") {
+            section = heading.trim_start_matches('#').trim();
+        } else if let Some((done, text)) = task_item(line) {
+            tasks.push((
+                section,
+                TaskItem {
+                    line: index + 1,
+                    done,
+                    text,
+                },
+            ));
+        }
+    }
+    tasks
+}
+
+/// Flip the checkbox of the task on a 1-based line
+pub fn toggle_task(content: &str, line: usize) -> Result<String, String> {
+    if !sectioned_tasks(content)
+        .iter()
+        .any(|(_, task)| task.line == line)
+    {
+        return Err(format!("Line {} is not a task list item", line));
+    }
+
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(String::from).collect();
+    let target = &mut lines[line - 1];
+    let (box_start, checked) = ["[ ]", "[x]", "[X]"]
+        .iter()
+        .filter_map(|marker| target.find(marker).map(|i| (i, *marker != "[ ]")))
+        .min_by_key(|(i, _)| *i)
+        .ok_or_else(|| format!("Line {} is not a task list item", line))?;
+    let replacement = if checked { "[ ]" } else { "[x]" };
+    target.replace_range(box_start..box_start + 3, replacement);
+    Ok(lines.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "---\nstatus: in-progress\n---\n\n# Auth\n\n- [x] Loose task\n\n## Plan\n\n- [x] Design\n- [ ] Build\n  * [X] Nested\n1. [ ] Ship\n\n```md\n- [ ] Example\n```\n\n## Test\n\n- [ ] Unit tests `[x]`\n- not a task\n-[ ] not a task either\n";
+
+    #[test]
+    fn test_task_progress() {
+        let progress = TaskProgress::from_content(CONTENT);
+
+        assert_eq!((progress.tasks_total, progress.tasks_done), (6, 3));
+        let sections: Vec<(&str, usize, usize)> = progress
+            .task_sections
+            .iter()
+            .map(|s| (s.section.as_str(), s.tasks_total, s.tasks_done))
+            .collect();
+        assert_eq!(sections, vec![("", 1, 1), ("Plan", 4, 2), ("Test", 1, 0)]);
+        assert_eq!(task_item("  * [X] Nested"), Some((true, "Nested")));
+        assert_eq!(task_item("-[ ] no"), None);
+    }
+
+    #[test]
+    fn test_toggle_task() {
+        let checked = toggle_task(CONTENT, 12).unwrap();
+        assert!(checked.contains("\n- [x] Build\n"));
+        let unchecked = toggle_task(&checked, 12).unwrap();
+        assert_eq!(unchecked, CONTENT);
+
+        let nested = toggle_task(CONTENT, 13).unwrap();
+        assert!(nested.contains("\n  * [ ] Nested\n"));
+        let inline = toggle_task(CONTENT, 22).unwrap();
+        assert!(inline.contains("\n- [x] Unit tests `[x]`\n"));
+
+        assert!(toggle_task(CONTENT, 17).is_err());
+        assert!(toggle_task(CONTENT, 23).is_err());
+        assert!(toggle_task(CONTENT, 99).is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! Token counting
+//!
+//! Counts spec tokens with the BPE vocabulary of the model a project works
+//! with. The OpenAI vocabularies are bundled with the app, so counting works
+//! offline. Models without a published vocabulary (Claude, Gemini, ...) are
+//! counted with `cl100k` as a stand-in, and those counts are flagged as
+//! approximate.
+
+use serde::{Deserialize, Serialize};
+use tiktoken_rs::{cl100k_base_singleton, o200k_base_singleton, CoreBPE};
+
+use crate::specs::settings::TokenSettings;
+
+/// Counts tokens in text
+pub trait Tokenizer: Send + Sync {
+    fn count(&self, text: &str) -> usize;
+}
+
+/// Available tokenizers
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenizerKind {
+    /// Words and punctuation approximation, no vocabulary needed
+    Heuristic,
+    /// GPT-4 / GPT-3.5 vocabulary, also the fallback for other model families
+    #[default]
+    Cl100k,
+    /// GPT-4o, GPT-4.1, GPT-5 and o-series vocabulary
+    O200k,
+}
+
+impl TokenizerKind {
+    /// Tokenizer for a model id such as `gpt-4o-mini` or `openai/gpt-4.1`
+    ///
+    /// Models without a published vocabulary get `cl100k`.
+    pub fn for_model(model: &str) -> Self {
+        Self::vocabulary(model).unwrap_or(TokenizerKind::Cl100k)
+    }
+
+    /// The vocabulary a model id tokenizes with, when it is one we bundle
+    pub fn vocabulary(model: &str) -> Option<Self> {
+        let model = model.rsplit('/').next().unwrap_or(model).to_lowercase();
+        let o200k = [
+            "gpt-4o",
+            "gpt-4.1",
+            "gpt-4.5",
+            "gpt-5",
+            "chatgpt-4o",
+            "o1",
+            "o3",
+            "o4",
+        ];
+        let cl100k = ["gpt-4", "gpt-3.5", "text-embedding-3", "text-embedding-ada"];
+        if o200k.iter().any(|prefix| model.starts_with(prefix)) {
+            Some(TokenizerKind::O200k)
+        } else if cl100k.iter().any(|prefix| model.starts_with(prefix)) {
+            Some(TokenizerKind::Cl100k)
+        } else {
+            None
+        }
+    }
+
+    /// Tokenizer for the model family a session runner drives
+    pub fn for_runner(runner: &str) -> Option<Self> {
+        match runner {
+            "codex" | "copilot" => Some(TokenizerKind::O200k),
+            "claude" | "gemini" => Some(TokenizerKind::Cl100k),
+            _ => None,
+        }
+    }
+
+    /// The vocabulary a runner's models tokenize with, when it is one we bundle
+    ///
+    /// Copilot can drive models of several families, so it has none.
+    fn runner_vocabulary(runner: &str) -> Option<Self> {
+        match runner {
+            "codex" => Some(TokenizerKind::O200k),
+            _ => None,
+        }
+    }
+
+    pub fn tokenizer(self) -> &'static dyn Tokenizer {
+        match self {
+            TokenizerKind::Heuristic => &HeuristicTokenizer,
+            TokenizerKind::Cl100k => &BpeTokenizer(cl100k_base_singleton),
+            TokenizerKind::O200k => &BpeTokenizer(o200k_base_singleton),
+        }
+    }
+}
+
+/// Roughly 1.3 tokens per word plus 0.5 per punctuation character
+struct HeuristicTokenizer;
+
+impl Tokenizer for HeuristicTokenizer {
+    fn count(&self, text: &str) -> usize {
+        let word_count = text.split_whitespace().count();
+        let special_chars = text
+            .chars()
+            .filter(|c| !c.is_alphanumeric() && !c.is_whitespace())
+            .count();
+
+        ((word_count as f64 * 1.3) + (special_chars as f64 * 0.5)).ceil() as usize
+    }
+}
+
+/// Byte-pair encoding with a bundled vocabulary, loaded on first use
+struct BpeTokenizer(fn() -> &'static CoreBPE);
+
+impl Tokenizer for BpeTokenizer {
+    fn count(&self, text: &str) -> usize {
+        (self.0)().encode_ordinary(text).len()
+    }
+}
+
+/// Tokenizer and thresholds used for a project's specs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBudget {
+    pub tokenizer: TokenizerKind,
+    /// Model the tokenizer was chosen for, when known
+    pub model: Option<String>,
+    /// The tokenizer isn't known to be the target model's own vocabulary,
+    /// so counts are estimates
+    pub approximate: bool,
+    /// Above this a spec gets `moderate-token-count`
+    pub warning_tokens: usize,
+    /// Above this a spec gets `high-token-count`
+    pub max_tokens: usize,
+}
+
+impl Default for TokenBudget {
+    fn default() -> Self {
+        TokenBudget::resolve(&TokenSettings::default(), None, None)
+    }
+}
+
+impl TokenBudget {
+    /// Pick the tokenizer for a project
+    ///
+    /// An explicit tokenizer or model in the project settings wins, then the
+    /// default chat model, then the project's default runner. Counts are
+    /// exact only when the tokenizer is the vocabulary of that model or
+    /// runner.
+    pub fn resolve(
+        settings: &TokenSettings,
+        chat_model: Option<String>,
+        runner: Option<String>,
+    ) -> Self {
+        let model = settings.model.clone().or(chat_model);
+        let tokenizer = settings
+            .tokenizer
+            .or_else(|| model.as_deref().map(TokenizerKind::for_model))
+            .or_else(|| runner.as_deref().and_then(TokenizerKind::for_runner))
+            .unwrap_or_default();
+        let target = match &model {
+            Some(model) => TokenizerKind::vocabulary(model),
+            None => runner.as_deref().and_then(TokenizerKind::runner_vocabulary),
+        };
+
+        Self {
+            approximate: target != Some(tokenizer),
+            tokenizer,
+            model,
+            warning_tokens: settings.warning_tokens,
+            max_tokens: settings.max_tokens,
+        }
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        self.tokenizer.tokenizer().count(text)
+    }
+}
+
+/// Token count of a spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecTokenCount {
+    pub spec_name: String,
+    pub tokens: usize,
+    #[serde(flatten)]
+    pub budget: TokenBudget,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenizer_for_model() {
+        assert_eq!(
+            TokenizerKind::for_model("gpt-4o-mini"),
+            TokenizerKind::O200k
+        );
+        assert_eq!(TokenizerKind::for_model("openai/o3"), TokenizerKind::O200k);
+        assert_eq!(
+            TokenizerKind::for_model("gpt-4-turbo"),
+            TokenizerKind::Cl100k
+        );
+        assert_eq!(
+            TokenizerKind::for_model("claude-sonnet-4"),
+            TokenizerKind::Cl100k
+        );
+        assert_eq!(TokenizerKind::vocabulary("claude-sonnet-4"), None);
+    }
+
+    #[test]
+    fn test_bpe_counts() {
+        let text = "Hello world";
+        assert_eq!(TokenizerKind::Cl100k.tokenizer().count(text), 2);
+        assert_eq!(TokenizerKind::O200k.tokenizer().count(text), 2);
+        assert!(TokenizerKind::Heuristic.tokenizer().count(text) < 10);
+
+        let long_text = "Hello world ".repeat(1000);
+        assert!(TokenizerKind::Heuristic.tokenizer().count(&long_text) > 2000);
+    }
+
+    #[test]
+    fn test_resolve_budget() {
+        let settings = TokenSettings::default();
+        let budget = TokenBudget::resolve(&settings, Some("gpt-4.1".to_string()), None);
+        assert_eq!(budget.tokenizer, TokenizerKind::O200k);
+        assert_eq!(budget.max_tokens, 5000);
+        assert!(!budget.approximate);
+
+        let budget = TokenBudget::resolve(&settings, None, Some("codex".to_string()));
+        assert_eq!(budget.tokenizer, TokenizerKind::O200k);
+        assert!(budget.model.is_none());
+        assert!(!budget.approximate);
+
+        let budget = TokenBudget::resolve(&settings, None, Some("claude".to_string()));
+        assert_eq!(budget.tokenizer, TokenizerKind::Cl100k);
+        assert!(budget.approximate);
+
+        let pinned = TokenSettings {
+            tokenizer: Some(TokenizerKind::Heuristic),
+            ..TokenSettings::default()
+        };
+        let budget = TokenBudget::resolve(&pinned, Some("gpt-4.1".to_string()), None);
+        assert_eq!(budget.tokenizer, TokenizerKind::Heuristic);
+        assert!(budget.approximate);
+    }
+}
@@ -0,0 +1,264 @@
+//! Spec-to-code traceability
+//!
+//! Scans a project's source tree and commit messages for references to spec
+//! numbers, such as `spec 042`, `specs/042-auth`, `LS-042` or a test named
+//! `test_spec_042_login`. Paths ignored by `.gitignore` and the specs
+//! directory itself are skipped.
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::specs::git::CommitMessage;
+use crate::specs::reader::Spec;
+
+/// Files larger than this are not scanned
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Referencing lines are trimmed to this many characters
+const MAX_LINE_CHARS: usize = 200;
+
+static SPEC_MENTION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(?:specs?[ /#:_-]?|ls-)(\d{1,4})").unwrap());
+
+/// A source line referencing a spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeReference {
+    /// Path relative to the project root
+    pub file_path: String,
+    /// 1-based line number
+    pub line: usize,
+    pub text: String,
+}
+
+/// A commit whose message references a spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitReference {
+    pub hash: String,
+    pub at: DateTime<Utc>,
+    pub subject: String,
+}
+
+/// Where a spec is referenced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecTraceability {
+    pub spec_name: String,
+    pub status: String,
+    pub references: Vec<CodeReference>,
+    pub commits: Vec<CommitReference>,
+    /// Complete, but no source file references it
+    pub untraced: bool,
+}
+
+/// Traceability of every spec in a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceabilityReport {
+    pub specs: Vec<SpecTraceability>,
+    pub files_scanned: usize,
+}
+
+/// Spec numbers mentioned in a line of text
+pub fn mentioned_numbers(text: &str) -> Vec<i32> {
+    let mut numbers: Vec<i32> = SPEC_MENTION
+        .captures_iter(text)
+        .filter(|caps| {
+            let whole = caps.get(0).unwrap();
+            let before = text[..whole.start()].chars().next_back();
+            let after = text[whole.end()..].chars().next();
+            !before.is_some_and(|c| c.is_ascii_alphanumeric())
+                && !after.is_some_and(|c| c.is_ascii_digit())
+        })
+        .filter_map(|caps| caps[1].parse().ok())
+        .collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+}
+
+/// Find references to `specs` in files under `root` and in `commits`
+///
+/// `specs_dir` is skipped, since specs referencing each other say nothing
+/// about where they are implemented.
+pub fn trace_specs(
+    specs: &[Spec],
+    root: &Path,
+    specs_dir: &Path,
+    commits: &[CommitMessage],
+) -> TraceabilityReport {
+    let (references, files_scanned) = scan_files(root, specs_dir);
+
+    let mut commit_references: BTreeMap<i32, Vec<CommitReference>> = BTreeMap::new();
+    for commit in commits {
+        let message = format!("{}\n{}", commit.subject, commit.body);
+        for number in mentioned_numbers(&message) {
+            commit_references
+                .entry(number)
+                .or_default()
+                .push(CommitReference {
+                    hash: commit.hash.clone(),
+                    at: commit.at,
+                    subject: commit.subject.clone(),
+                });
+        }
+    }
+
+    // A number shared by several specs is attributed to each of them
+    let specs = specs
+        .iter()
+        .map(|spec| {
+            let number = spec.spec_number.unwrap_or(-1);
+            let references = references.get(&number).cloned().unwrap_or_default();
+            SpecTraceability {
+                spec_name: spec.spec_name.clone(),
+                status: spec.status.clone(),
+                untraced: spec.status == "complete" && references.is_empty(),
+                references,
+                commits: commit_references.get(&number).cloned().unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    TraceabilityReport {
+        specs,
+        files_scanned,
+    }
+}
+
+/// References per spec number in text files under `root`, and the number of
+/// files scanned
+///
+/// Hidden files such as CI workflows are included; only `.gitignore` rules,
+/// the `.git` directory and the specs directory are excluded.
+fn scan_files(root: &Path, specs_dir: &Path) -> (BTreeMap<i32, Vec<CodeReference>>, usize) {
+    let specs_dir = dunce::canonicalize(specs_dir).unwrap_or_else(|_| specs_dir.to_path_buf());
+    let walker = ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            entry.file_name() != ".git"
+                && dunce::canonicalize(entry.path())
+                    .map(|path| path != specs_dir)
+                    .unwrap_or(true)
+        })
+        .build();
+
+    let mut references: BTreeMap<i32, Vec<CodeReference>> = BTreeMap::new();
+    let mut files_scanned = 0;
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file())
+            || entry.metadata().map(|m| m.len()).unwrap_or(0) > MAX_FILE_BYTES
+        {
+            continue;
+        }
+        // Binary and non-UTF-8 files are skipped
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        files_scanned += 1;
+
+        let file_path = entry
+            .path()
+            .strip_prefix(root)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        for (index, line) in content.lines().enumerate() {
+            for number in mentioned_numbers(line) {
+                references.entry(number).or_default().push(CodeReference {
+                    file_path: file_path.clone(),
+                    line: index + 1,
+                    text: line.trim().chars().take(MAX_LINE_CHARS).collect(),
+                });
+            }
+        }
+    }
+
+    for file_references in references.values_mut() {
+        file_references.sort_by(|a, b| (&a.file_path, a.line).cmp(&(&b.file_path, b.line)));
+    }
+    (references, files_scanned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::reader::SpecBuilder;
+
+    fn spec(name: &str, status: &str) -> Spec {
+        SpecBuilder::new(name).status(status).build()
+    }
+
+    #[test]
+    fn test_mentioned_numbers() {
+        assert_eq!(mentioned_numbers("// Implements spec 042"), vec![42]);
+        assert_eq!(
+            mentioned_numbers("See specs/042-auth/README.md and LS-7"),
+            vec![7, 42]
+        );
+        assert_eq!(mentioned_numbers("fn test_spec_012_login() {}"), vec![12]);
+        assert_eq!(mentioned_numbers("Spec #3, spec 3"), vec![3]);
+        assert!(mentioned_numbers("inspect 42, spec 12345, aspects 1").is_empty());
+    }
+
+    #[test]
+    fn test_trace_specs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write(".gitignore", "target/\n");
+        write(
+            "src/auth.rs",
+            "use x;\n\n// Implements spec 042\nfn login() {}\n",
+        );
+        write("tests/auth.rs", "#[test]\nfn spec_042_login() {}\n");
+        write("target/debug/auth.rs", "// spec 042\n");
+        write(".github/workflows/auth.yml", "# Checks spec 042\n");
+        write(".git/COMMIT_EDITMSG", "Implement spec 042\n");
+        write("specs/042-auth/README.md", "Depends on spec 043\n");
+
+        let commits = vec![CommitMessage {
+            hash: "abc123".to_string(),
+            at: Utc::now(),
+            subject: "Add search (LS-043)".to_string(),
+            body: String::new(),
+        }];
+        let specs = vec![spec("042-auth", "complete"), spec("043-search", "complete")];
+
+        let report = trace_specs(&specs, root, &root.join("specs"), &commits);
+
+        assert_eq!(report.files_scanned, 4);
+        let auth = &report.specs[0];
+        let locations: Vec<(&str, usize)> = auth
+            .references
+            .iter()
+            .map(|r| (r.file_path.as_str(), r.line))
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                (".github/workflows/auth.yml", 1),
+                ("src/auth.rs", 3),
+                ("tests/auth.rs", 2)
+            ]
+        );
+        assert_eq!(auth.references[1].text, "// Implements spec 042");
+        assert!(!auth.untraced);
+
+        let search = &report.specs[1];
+        assert!(search.references.is_empty());
+        assert_eq!(search.commits[0].hash, "abc123");
+        assert!(search.untraced);
+    }
+}
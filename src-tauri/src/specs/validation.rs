@@ -3,10 +3,26 @@
 //! Validates spec structure, frontmatter, and content.
 
 use serde::{Deserialize, Serialize};
-
-use crate::specs::constants::{VALID_PRIORITIES, VALID_STATUSES};
-use crate::specs::frontmatter::parse_frontmatter;
-use crate::specs::reader::Spec;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::specs::collisions::{find_duplicate_numbers, find_similar_slugs};
+use crate::specs::dependencies::{
+    parse_external_reference, DependencyEdge, ExternalResolution, ExternalSpecs, SpecLookup,
+};
+use crate::specs::frontmatter::{parse_frontmatter, parse_source_map, SourceSpan};
+use crate::specs::reader::{LoadDiagnostic, Spec};
+use crate::specs::relations::RelationKind;
+use crate::specs::schema::FrontmatterSchema;
+use crate::specs::settings::ValidationSettings;
+use crate::specs::tokens::TokenBudget;
+
+pub mod report;
+pub mod rules;
+
+use rules::content::{linked_spec, relative_links};
+use rules::{builtin_rules, RuleContext, RuleInfo, CROSS_SPEC_RULES};
 
 /// Validation result for a spec
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +41,21 @@ pub struct ValidationIssue {
     pub code: String,
     pub message: String,
     pub line: Option<i32>,
+    /// Source range of the issue; `line` repeats its start line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SourceSpan>,
+    /// Relationship the issue refers to, for cross-spec findings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edge: Option<DependencyEdge>,
+}
+
+impl ValidationIssue {
+    /// Point the issue at a source range
+    pub fn at(mut self, span: Option<SourceSpan>) -> Self {
+        self.line = span.map(|s| s.line);
+        self.span = span;
+        self
+    }
 }
 
 /// Severity of a validation issue
@@ -36,130 +67,143 @@ pub enum IssueSeverity {
     Info,
 }
 
-/// Validate a single spec
-pub fn validate_spec(spec: &Spec) -> ValidationResult {
-    let mut issues = Vec::new();
-
-    // Parse frontmatter for validation
-    let (frontmatter, body) = parse_frontmatter(&spec.content_md);
+/// Project-specific validation inputs
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    /// Tokenizer and thresholds for the token count checks
+    pub tokens: TokenBudget,
+    /// Rule overrides and required sections
+    pub settings: ValidationSettings,
+    /// Specs directory on disk, for checking relative links
+    pub specs_dir: Option<PathBuf>,
+    /// The project's frontmatter schema, when it has one
+    pub schema: Option<Arc<FrontmatterSchema>>,
+}
 
-    // Check required fields
-    if frontmatter.status.is_none() {
-        issues.push(ValidationIssue {
-            severity: IssueSeverity::Error,
-            code: "missing-status".to_string(),
-            message: "Spec must have a status field in frontmatter".to_string(),
-            line: None,
-        });
-    }
+/// Validate a single spec
+pub fn validate_spec(spec: &Spec, options: &ValidationOptions) -> ValidationResult {
+    let (frontmatter, _) = parse_frontmatter(&spec.content_md);
+    let source = parse_source_map(&spec.content_md);
+    let ctx = RuleContext::new(spec, &frontmatter, &source, options);
 
-    // Check valid status values
-    if let Some(status) = &frontmatter.status {
-        if !VALID_STATUSES.contains(&status.as_str()) {
-            issues.push(ValidationIssue {
-                severity: IssueSeverity::Error,
-                code: "invalid-status".to_string(),
-                message: format!(
-                    "Invalid status '{}'. Must be one of: {}",
-                    status,
-                    VALID_STATUSES.join(", ")
-                ),
-                line: None,
-            });
+    let mut issues = Vec::new();
+    for rule in builtin_rules() {
+        if !options.settings.is_enabled(rule.code()) {
+            continue;
         }
-    }
-
-    // Check valid priority values
-    if let Some(priority) = &frontmatter.priority {
-        if !VALID_PRIORITIES.contains(&priority.as_str()) {
-            issues.push(ValidationIssue {
-                severity: IssueSeverity::Warning,
-                code: "invalid-priority".to_string(),
-                message: format!(
-                    "Invalid priority '{}'. Recommended: {}",
-                    priority,
-                    VALID_PRIORITIES.join(", ")
-                ),
-                line: None,
-            });
+        let params = options.settings.params(rule.code());
+        for finding in rule.check(&ctx, params) {
+            issues.push(
+                ValidationIssue {
+                    severity: rule.default_severity(),
+                    code: rule.code().to_string(),
+                    message: finding.message,
+                    line: None,
+                    span: None,
+                    edge: None,
+                }
+                .at(finding.span),
+            );
         }
     }
 
-    // Check for title in body
-    if spec.title.is_none() {
-        issues.push(ValidationIssue {
-            severity: IssueSeverity::Warning,
-            code: "missing-title".to_string(),
-            message: "Spec should have a title (H1 heading)".to_string(),
-            line: None,
-        });
-    }
-
-    // Check for required sections
-    let has_overview = body.contains("## Overview") || body.contains("## overview");
-    // Note: Validation for Design, Plan, Test sections was removed as these are
-    // optional sections per LeanSpec philosophy (minimal required structure).
-    // Only Overview section check remains as it's the core requirement.
-
-    if !has_overview {
-        issues.push(ValidationIssue {
-            severity: IssueSeverity::Info,
-            code: "missing-overview".to_string(),
-            message: "Consider adding an ## Overview section".to_string(),
-            line: None,
-        });
-    }
+    let mut result = ValidationResult {
+        spec_name: spec.spec_name.clone(),
+        valid: true,
+        issues,
+    };
+    apply_rule_settings(&mut result, &options.settings);
+    result
+}
 
-    // Check for dangling dependencies
-    for dep in &frontmatter.depends_on {
-        // This is a basic check; full validation would require all specs
-        if dep.trim().is_empty() {
-            issues.push(ValidationIssue {
-                severity: IssueSeverity::Warning,
-                code: "empty-dependency".to_string(),
-                message: "Empty dependency in depends_on list".to_string(),
-                line: None,
-            });
+/// Drop issues from disabled rules, apply severity overrides and recompute validity
+///
+/// Applies to every issue code, including cross-spec and staleness findings.
+pub fn apply_rule_settings(result: &mut ValidationResult, settings: &ValidationSettings) {
+    result
+        .issues
+        .retain(|issue| settings.is_enabled(&issue.code));
+    for issue in result.issues.iter_mut() {
+        if let Some(severity) = settings.severity(&issue.code) {
+            issue.severity = severity;
         }
     }
+    result.valid = !result
+        .issues
+        .iter()
+        .any(|i| i.severity == IssueSeverity::Error);
+}
 
-    // Estimate token count (rough approximation)
-    let estimated_tokens = estimate_tokens(&spec.content_md);
-    if estimated_tokens > 5000 {
-        issues.push(ValidationIssue {
-            severity: IssueSeverity::Warning,
-            code: "high-token-count".to_string(),
-            message: format!(
-                "Spec has {} tokens, exceeds maximum of 5000. Consider splitting into smaller specs.",
-                estimated_tokens
-            ),
-            line: None,
-        });
-    } else if estimated_tokens > 3500 {
-        issues.push(ValidationIssue {
-            severity: IssueSeverity::Info,
-            code: "moderate-token-count".to_string(),
-            message: format!(
-                "Spec has {} tokens, approaching maximum of 5000. Consider splitting if content grows.",
-                estimated_tokens
-            ),
-            line: None,
-        });
-    }
+/// Built-in single-spec and cross-spec rules with the project's
+/// configuration applied
+pub fn list_rules(settings: &ValidationSettings) -> Vec<RuleInfo> {
+    let single_spec = builtin_rules()
+        .into_iter()
+        .map(|rule| (rule.code(), rule.description(), rule.default_severity()));
+    let cross_spec = CROSS_SPEC_RULES
+        .iter()
+        .map(|rule| (rule.code, rule.description, rule.default_severity.clone()));
+
+    single_spec
+        .chain(cross_spec)
+        .map(|(code, description, default_severity)| RuleInfo {
+            code: code.to_string(),
+            description: description.to_string(),
+            severity: settings
+                .severity(code)
+                .unwrap_or_else(|| default_severity.clone()),
+            default_severity,
+            enabled: settings.is_enabled(code),
+            params: settings
+                .rules
+                .get(code)
+                .map(|r| r.params.clone())
+                .unwrap_or_default(),
+        })
+        .collect()
+}
 
+/// Report a spec directory that failed to load as a failed validation result
+pub fn load_failure_result(diagnostic: &LoadDiagnostic) -> ValidationResult {
+    let span = diagnostic.line.map(|line| {
+        let column = diagnostic.column.unwrap_or(1);
+        SourceSpan {
+            line,
+            column,
+            end_line: line,
+            end_column: column + 1,
+        }
+    });
     ValidationResult {
-        spec_name: spec.spec_name.clone(),
-        valid: !issues.iter().any(|i| i.severity == IssueSeverity::Error),
-        issues,
+        spec_name: diagnostic.spec_name.clone(),
+        valid: false,
+        issues: vec![ValidationIssue {
+            severity: IssueSeverity::Error,
+            code: diagnostic.reason.code().to_string(),
+            message: diagnostic.message.clone(),
+            line: None,
+            span: None,
+            edge: None,
+        }
+        .at(span)],
     }
 }
 
 /// Validate all specs with cross-spec checks
-pub fn validate_all_specs(specs: &[Spec]) -> Vec<ValidationResult> {
-    let mut results: Vec<ValidationResult> = specs.iter().map(validate_spec).collect();
+///
+/// Cross-project `project:spec` dependencies are checked against `external`.
+pub fn validate_all_specs(
+    specs: &[Spec],
+    external: &ExternalSpecs,
+    options: &ValidationOptions,
+) -> Vec<ValidationResult> {
+    let mut results: Vec<ValidationResult> = specs
+        .iter()
+        .map(|spec| validate_spec(spec, options))
+        .collect();
 
     // Build spec name set for dependency validation
-    let spec_names: std::collections::HashSet<String> = specs
+    let spec_names: HashSet<String> = specs
         .iter()
         .flat_map(|s| {
             let mut names = vec![s.spec_name.clone()];
@@ -171,88 +215,340 @@ pub fn validate_all_specs(specs: &[Spec]) -> Vec<ValidationResult> {
         })
         .collect();
 
-    // Check for broken dependencies
+    let duplicate_numbers = find_duplicate_numbers(specs);
+    let similar_slugs = find_similar_slugs(specs);
+    let lookup = SpecLookup::new(specs);
+
+    // Open specs depending on each spec, for the archived-spec check
+    let mut open_dependents: HashMap<&str, Vec<&Spec>> = HashMap::new();
+    for spec in specs.iter().filter(|s| is_open_status(&s.status)) {
+        let targets: HashSet<&str> = spec
+            .depends_on
+            .iter()
+            .filter_map(|dep| lookup.find(dep))
+            .map(|d| d.spec_name.as_str())
+            .filter(|name| *name != spec.spec_name)
+            .collect();
+        for target in targets {
+            open_dependents.entry(target).or_default().push(spec);
+        }
+    }
+
+    // Check for broken references of every relationship kind
     for (result, spec) in results.iter_mut().zip(specs.iter()) {
-        let (frontmatter, _) = parse_frontmatter(&spec.content_md);
+        let source = parse_source_map(&spec.content_md);
 
-        for dep in &frontmatter.depends_on {
-            let trimmed = dep.trim();
-            if trimmed.is_empty() {
+        // Numbers must be unique so references by number resolve to one spec
+        for collision in duplicate_numbers
+            .iter()
+            .filter(|c| c.spec_names.contains(&spec.spec_name))
+        {
+            let others: Vec<&str> = collision
+                .spec_names
+                .iter()
+                .filter(|name| **name != spec.spec_name)
+                .map(String::as_str)
+                .collect();
+            let mut message = format!(
+                "Spec number {:03} is also used by {}",
+                collision.number,
+                others.join(", ")
+            );
+            match collision
+                .renumber
+                .iter()
+                .find(|r| r.spec_name == spec.spec_name)
+            {
+                Some(renumbering) => message.push_str(&format!(
+                    ". Renumber to {:03} ({}).",
+                    renumbering.number, renumbering.new_name
+                )),
+                None => message.push('.'),
+            }
+            result.issues.push(ValidationIssue {
+                severity: IssueSeverity::Error,
+                code: "duplicate-spec-number".to_string(),
+                message,
+                line: None,
+                span: None,
+                edge: None,
+            });
+        }
+
+        for collision in &similar_slugs {
+            let other = if collision.first == spec.spec_name {
+                &collision.second
+            } else if collision.second == spec.spec_name {
+                &collision.first
+            } else {
                 continue;
+            };
+            result.issues.push(ValidationIssue {
+                severity: IssueSeverity::Warning,
+                code: "similar-spec-slug".to_string(),
+                message: format!(
+                    "Spec name is nearly identical to '{}'. Is it a duplicate?",
+                    other
+                ),
+                line: None,
+                span: None,
+                edge: None,
+            });
+        }
+        for kind in RelationKind::ALL {
+            for dep in spec.references(kind) {
+                let trimmed = dep.trim();
+                if trimmed.is_empty() {
+                    continue;
+                }
+
+                // Cross-project references are checked against registered projects
+                if let Some((project_key, _)) = parse_external_reference(trimmed) {
+                    if let Some(issue) = check_external_reference(dep, project_key, external) {
+                        result
+                            .issues
+                            .push(issue.at(source.item_span(kind.field_name(), dep)));
+                    }
+                    continue;
+                }
+
+                // Check if referenced spec exists
+                let exists = spec_names.contains(trimmed)
+                    || trimmed
+                        .split('-')
+                        .next()
+                        .and_then(|n| n.parse::<i32>().ok())
+                        .map(|num| spec_names.contains(&num.to_string()))
+                        .unwrap_or(false);
+
+                if exists {
+                    let candidates = lookup.candidates(trimmed);
+                    if candidates.len() > 1 {
+                        let names: Vec<&str> =
+                            candidates.iter().map(|s| s.spec_name.as_str()).collect();
+                        result.issues.push(
+                            ValidationIssue {
+                                severity: IssueSeverity::Warning,
+                                code: "ambiguous-reference".to_string(),
+                                message: format!(
+                                    "Spec '{}' referenced in {} matches {}. Use the full spec name.",
+                                    dep,
+                                    kind.field_name(),
+                                    names.join(", ")
+                                ),
+                                line: None,
+                                span: None,
+                                edge: None,
+                            }
+                            .at(source.item_span(kind.field_name(), dep)),
+                        );
+                    }
+                } else {
+                    let message = match kind {
+                        RelationKind::DependsOn => format!("Dependency '{}' not found", dep),
+                        _ => format!(
+                            "Spec '{}' referenced in {} not found",
+                            dep,
+                            kind.field_name()
+                        ),
+                    };
+                    result.issues.push(
+                        ValidationIssue {
+                            severity: IssueSeverity::Warning,
+                            code: kind.broken_code().to_string(),
+                            message,
+                            line: None,
+                            span: None,
+                            edge: None,
+                        }
+                        .at(source.item_span(kind.field_name(), dep)),
+                    );
+                }
             }
+        }
 
-            // Check if dependency exists
-            let exists = spec_names.contains(trimmed)
-                || trimmed
-                    .split('-')
-                    .next()
-                    .and_then(|n| n.parse::<i32>().ok())
-                    .map(|num| spec_names.contains(&num.to_string()))
-                    .unwrap_or(false);
-
-            if !exists {
-                result.issues.push(ValidationIssue {
-                    severity: IssueSeverity::Warning,
-                    code: "broken-dependency".to_string(),
-                    message: format!("Dependency '{}' not found", dep),
+        // Links into other spec directories must resolve to a loaded spec
+        let relative = spec
+            .file_path
+            .strip_prefix("specs/")
+            .unwrap_or(&spec.file_path);
+        let spec_dir = Path::new(relative).parent().unwrap_or(Path::new(""));
+        for link in relative_links(&spec.content_md) {
+            let Some(target) = linked_spec(spec_dir, &link.target) else {
+                continue;
+            };
+            if !specs.iter().any(|s| s.spec_name == target) {
+                result.issues.push(
+                    ValidationIssue {
+                        severity: IssueSeverity::Warning,
+                        code: "broken-spec-link".to_string(),
+                        message: format!("Linked spec '{}' not found", target),
+                        line: None,
+                        span: None,
+                        edge: None,
+                    }
+                    .at(Some(link.span)),
+                );
+            }
+        }
+
+        // Status consistency between a spec and what it depends on
+        for dep in &spec.depends_on {
+            let target = match external.get(dep) {
+                Some(ExternalResolution::Resolved { project, spec: ext }) => Some((
+                    format!("{}:{}", project.id, ext.id),
+                    ext.spec_name.as_str(),
+                    ext.status.as_str(),
+                )),
+                _ => lookup
+                    .find(dep)
+                    .filter(|d| d.spec_name != spec.spec_name)
+                    .map(|d| (d.id.clone(), d.spec_name.as_str(), d.status.as_str())),
+            };
+            let Some((target_id, target_name, target_status)) = target else {
+                continue;
+            };
+
+            let finding = match (spec.status.as_str(), target_status) {
+                ("complete", "draft" | "planned" | "in-progress") => Some((
+                    "complete-with-open-dependency",
+                    format!(
+                        "Spec is complete but its dependency '{}' is still {}",
+                        target_name, target_status
+                    ),
+                )),
+                ("in-progress", "draft") => Some((
+                    "in-progress-depends-on-draft",
+                    format!(
+                        "Spec is in progress but its dependency '{}' is still a draft",
+                        target_name
+                    ),
+                )),
+                _ => None,
+            };
+
+            if let Some((code, message)) = finding {
+                result.issues.push(
+                    ValidationIssue {
+                        severity: IssueSeverity::Warning,
+                        code: code.to_string(),
+                        message,
+                        line: None,
+                        span: None,
+                        edge: Some(dependency_edge(&target_id, &spec.id)),
+                    }
+                    .at(source.item_span("depends_on", dep)),
+                );
+            }
+        }
+
+        // Archived specs should not be required by open specs
+        if spec.status == "archived" {
+            let dependents = open_dependents.get(spec.spec_name.as_str());
+            for dependent in dependents.into_iter().flatten() {
+                result.issues.push(
+                    ValidationIssue {
+                        severity: IssueSeverity::Warning,
+                        code: "archived-with-open-dependents".to_string(),
+                        message: format!(
+                            "Spec is archived but '{}' ({}) still depends on it",
+                            dependent.spec_name, dependent.status
+                        ),
+                        line: None,
+                        span: None,
+                        edge: Some(dependency_edge(&spec.id, &dependent.id)),
+                    }
+                    .at(source.value_span("status")),
+                );
+            }
+        }
+
+        // Superseded specs should eventually be archived
+        if spec.relations.archived_hint {
+            result.issues.push(
+                ValidationIssue {
+                    severity: IssueSeverity::Info,
+                    code: "superseded-not-archived".to_string(),
+                    message: format!(
+                        "Spec is superseded by {}. Consider archiving it.",
+                        spec.relations.superseded_by.join(", ")
+                    ),
                     line: None,
-                });
-                // Update valid status if this creates an error
-                if result.valid
-                    && result
-                        .issues
-                        .iter()
-                        .any(|i| i.severity == IssueSeverity::Error)
-                {
-                    result.valid = false;
+                    span: None,
+                    edge: None,
                 }
-            }
+                .at(source.value_span("status")),
+            );
         }
+
+        apply_rule_settings(result, &options.settings);
     }
 
     results
 }
 
-/// Estimate token count for content
-/// Uses a rough heuristic of ~4 characters per token for English text
-fn estimate_tokens(content: &str) -> i32 {
-    let word_count = content.split_whitespace().count();
-    let special_chars = content
-        .chars()
-        .filter(|c| !c.is_alphanumeric() && !c.is_whitespace())
-        .count();
-
-    // Roughly 1.3 tokens per word + 0.5 for special chars
-    ((word_count as f64 * 1.3) + (special_chars as f64 * 0.5)).ceil() as i32
+/// Whether a status counts as open work
+fn is_open_status(status: &str) -> bool {
+    matches!(status, "draft" | "planned" | "in-progress")
+}
+
+/// Edge from a dependency to the spec that depends on it
+fn dependency_edge(dependency_id: &str, dependent_id: &str) -> DependencyEdge {
+    DependencyEdge {
+        source: dependency_id.to_string(),
+        target: dependent_id.to_string(),
+        edge_type: RelationKind::DependsOn.edge_type().to_string(),
+    }
+}
+
+/// Check a `project:spec` reference against the resolved external specs
+fn check_external_reference(
+    dep: &str,
+    project_key: &str,
+    external: &ExternalSpecs,
+) -> Option<ValidationIssue> {
+    match external.get(dep)? {
+        ExternalResolution::Resolved { .. } => None,
+        ExternalResolution::UnknownProject => Some(ValidationIssue {
+            severity: IssueSeverity::Warning,
+            code: "unknown-project".to_string(),
+            message: format!(
+                "Dependency '{}' references project '{}', which is not registered",
+                dep, project_key
+            ),
+            line: None,
+            span: None,
+            edge: None,
+        }),
+        ExternalResolution::UnknownSpec { project } => Some(ValidationIssue {
+            severity: IssueSeverity::Warning,
+            code: "broken-dependency".to_string(),
+            message: format!(
+                "Dependency '{}' not found in project '{}'",
+                dep, project.name
+            ),
+            line: None,
+            span: None,
+            edge: None,
+        }),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use crate::specs::reader::SpecBuilder;
 
     fn create_test_spec_with_content(content: &str) -> Spec {
         let (fm, _) = parse_frontmatter(content);
         Spec {
-            id: "test".to_string(),
-            project_id: "test".to_string(),
-            spec_number: Some(1),
-            spec_name: "001-test".to_string(),
             title: super::super::frontmatter::extract_title(content),
             status: fm.status.unwrap_or_else(|| "planned".to_string()),
             priority: fm.priority,
             tags: fm.tags,
             assignee: fm.assignee,
-            content_md: content.to_string(),
-            content_html: None,
-            created_at: None,
-            updated_at: None,
-            completed_at: None,
-            file_path: "specs/001-test/README.md".to_string(),
-            github_url: None,
-            synced_at: Utc::now(),
             depends_on: fm.depends_on,
-            required_by: Vec::new(),
+            ..SpecBuilder::new("001-test").content(content).build()
         }
     }
 
@@ -283,7 +579,7 @@ Some design content.
 Test criteria.
 "#;
         let spec = create_test_spec_with_content(content);
-        let result = validate_spec(&spec);
+        let result = validate_spec(&spec, &ValidationOptions::default());
 
         assert!(result.valid);
         assert!(result
@@ -301,7 +597,7 @@ priority: high
 # Missing Status
 "#;
         let spec = create_test_spec_with_content(content);
-        let result = validate_spec(&spec);
+        let result = validate_spec(&spec, &ValidationOptions::default());
 
         assert!(!result.valid);
         assert!(result.issues.iter().any(|i| i.code == "missing-status"));
@@ -316,21 +612,292 @@ status: invalid-status
 # Invalid Status
 "#;
         let spec = create_test_spec_with_content(content);
-        let result = validate_spec(&spec);
+        let result = validate_spec(&spec, &ValidationOptions::default());
+
+        assert!(!result.valid);
+        let issue = result
+            .issues
+            .iter()
+            .find(|i| i.code == "invalid-status")
+            .unwrap();
+        assert_eq!(issue.line, Some(2));
+        let span = issue.span.unwrap();
+        assert_eq!((span.column, span.end_column), (9, 23));
+    }
+
+    #[test]
+    fn test_validate_all_specs_broken_relations() {
+        let mut base = create_test_spec_with_content(
+            "---\nstatus: planned\nrelated:\n  - 404-missing\n---\n\n# Base\n\nSee [old](../002-old/README.md) and [gone](../003-gone).\n",
+        );
+        base.relations.related = vec!["404-missing".to_string()];
+        base.relations.parent = Some("001".to_string());
+        let mut old = create_test_spec_with_content("---\nstatus: complete\n---\n\n# Old\n");
+        old.spec_number = Some(2);
+        old.spec_name = "002-old".to_string();
+        old.relations.superseded_by = vec!["001-test".to_string()];
+        old.relations.archived_hint = true;
+
+        let results = validate_all_specs(
+            &[base, old],
+            &ExternalSpecs::default(),
+            &ValidationOptions::default(),
+        );
+
+        let broken = results[0]
+            .issues
+            .iter()
+            .find(|i| i.code == "broken-related")
+            .unwrap();
+        assert_eq!(broken.line, Some(4));
+        assert!(!results[0].issues.iter().any(|i| i.code == "broken-parent"));
+        let links: Vec<&str> = results[0]
+            .issues
+            .iter()
+            .filter(|i| i.code == "broken-spec-link")
+            .map(|i| i.message.as_str())
+            .collect();
+        assert_eq!(links, vec!["Linked spec '003-gone' not found"]);
+        assert!(results[1]
+            .issues
+            .iter()
+            .any(|i| i.code == "superseded-not-archived"));
+    }
+
+    #[test]
+    fn test_duplicate_numbers_and_ambiguous_references() {
+        let spec_named = |number: i32, name: &str, content: &str| {
+            let mut spec = create_test_spec_with_content(content);
+            spec.spec_number = Some(number);
+            spec.spec_name = name.to_string();
+            spec
+        };
+        let specs = vec![
+            spec_named(42, "042-auth", "---\nstatus: planned\n---\n\n# Auth\n"),
+            spec_named(
+                42,
+                "042-billing",
+                "---\nstatus: planned\n---\n\n# Billing\n",
+            ),
+            spec_named(
+                43,
+                "043-user-auth",
+                "---\nstatus: planned\ndepends_on:\n  - '042'\n---\n\n# UI\n",
+            ),
+            spec_named(44, "044-user-authn", "---\nstatus: planned\n---\n\n# Dup\n"),
+        ];
+
+        let results = validate_all_specs(
+            &specs,
+            &ExternalSpecs::default(),
+            &ValidationOptions::default(),
+        );
+        let messages = |index: usize, code: &str| -> Vec<String> {
+            results[index]
+                .issues
+                .iter()
+                .filter(|i| i.code == code)
+                .map(|i| i.message.clone())
+                .collect()
+        };
+
+        assert_eq!(
+            messages(0, "duplicate-spec-number"),
+            vec!["Spec number 042 is also used by 042-billing."]
+        );
+        assert_eq!(
+            messages(1, "duplicate-spec-number"),
+            vec!["Spec number 042 is also used by 042-auth. Renumber to 045 (045-billing)."]
+        );
+        assert!(!results[1].valid);
+        assert_eq!(
+            messages(2, "ambiguous-reference"),
+            vec!["Spec '042' referenced in depends_on matches 042-auth, 042-billing. Use the full spec name."]
+        );
+        assert!(messages(2, "broken-dependency").is_empty());
+        assert_eq!(messages(3, "similar-spec-slug").len(), 1);
+    }
+
+    #[test]
+    fn test_validate_cross_project_dependencies() {
+        let content =
+            "---\nstatus: planned\ndepends_on:\n  - backend:042\n  - ghost:001\n---\n\n# X\n";
+        let spec = create_test_spec_with_content(content);
+
+        let mut external = ExternalSpecs::default();
+        external.insert("ghost:001", ExternalResolution::UnknownProject);
+
+        let results = validate_all_specs(&[spec], &external, &ValidationOptions::default());
+        let codes: Vec<&str> = results[0].issues.iter().map(|i| i.code.as_str()).collect();
+
+        assert!(codes.contains(&"unknown-project"));
+        assert!(!codes.contains(&"broken-dependency"));
+    }
+
+    #[test]
+    fn test_validate_status_consistency() {
+        let spec_with = |num: i32, name: &str, status: &str, deps: &[&str]| {
+            let mut spec = create_test_spec_with_content(&format!(
+                "---\nstatus: {}\n---\n\n# {}\n\n## Overview\n",
+                status, name
+            ));
+            spec.spec_number = Some(num);
+            spec.spec_name = name.to_string();
+            spec.id = format!("fs-{}", name);
+            spec.depends_on = deps.iter().map(|d| d.to_string()).collect();
+            spec
+        };
+        let specs = vec![
+            spec_with(1, "001-draft", "draft", &[]),
+            spec_with(2, "002-planned", "planned", &[]),
+            spec_with(3, "003-done", "complete", &["002"]),
+            spec_with(4, "004-wip", "in-progress", &["001-draft"]),
+            spec_with(5, "005-old", "archived", &[]),
+            spec_with(6, "006-next", "planned", &["005"]),
+        ];
+
+        let results = validate_all_specs(
+            &specs,
+            &ExternalSpecs::default(),
+            &ValidationOptions::default(),
+        );
+
+        let complete = results[2]
+            .issues
+            .iter()
+            .find(|i| i.code == "complete-with-open-dependency")
+            .unwrap();
+        let edge = complete.edge.as_ref().unwrap();
+        assert_eq!(edge.source, "fs-002-planned");
+        assert_eq!(edge.target, "fs-003-done");
+
+        assert!(results[3]
+            .issues
+            .iter()
+            .any(|i| i.code == "in-progress-depends-on-draft"));
+
+        let archived = results[4]
+            .issues
+            .iter()
+            .find(|i| i.code == "archived-with-open-dependents")
+            .unwrap();
+        assert_eq!(archived.edge.as_ref().unwrap().target, "fs-006-next");
+    }
+
+    #[test]
+    fn test_rule_settings_and_required_sections() {
+        let content = r#"---
+status: complete
+priority: urgent
+---
+
+# Done Spec
+
+## Design
+
+Notes.
+"#;
+        let spec = create_test_spec_with_content(content);
+        let settings: ValidationSettings = serde_json::from_str(
+            r#"{
+                "rules": {
+                    "missing-overview": { "enabled": false },
+                    "invalid-priority": { "severity": "error" }
+                },
+                "requiredSections": { "complete": ["Test Plan", "Design"] }
+            }"#,
+        )
+        .unwrap();
+        let options = ValidationOptions {
+            settings,
+            ..ValidationOptions::default()
+        };
+
+        let result = validate_spec(&spec, &options);
 
         assert!(!result.valid);
-        assert!(result.issues.iter().any(|i| i.code == "invalid-status"));
+        assert!(result.issues.iter().all(|i| i.code != "missing-overview"));
+        let priority = result
+            .issues
+            .iter()
+            .find(|i| i.code == "invalid-priority")
+            .unwrap();
+        assert_eq!(priority.severity, IssueSeverity::Error);
+        let sections: Vec<&str> = result
+            .issues
+            .iter()
+            .filter(|i| i.code == "missing-required-section")
+            .map(|i| i.message.as_str())
+            .collect();
+        assert_eq!(
+            sections,
+            vec!["Specs that are complete must have a ## Test Plan section"]
+        );
+
+        // Cross-spec checks are listed alongside single-spec rules
+        let rules = list_rules(&options.settings);
+        assert!(rules
+            .iter()
+            .any(|r| r.code == "missing-overview" && !r.enabled));
+        let link = rules.iter().find(|r| r.code == "broken-spec-link").unwrap();
+        assert_eq!(link.severity, IssueSeverity::Warning);
+        assert!(rules.iter().any(|r| r.code == "duplicate-spec-number"));
     }
 
     #[test]
-    fn test_estimate_tokens() {
-        let short_text = "Hello world";
-        let long_text = "Hello world ".repeat(1000);
+    fn test_load_failure_result() {
+        let diagnostic = LoadDiagnostic {
+            spec_name: "002-tab".to_string(),
+            file_path: "specs/002-tab/README.md".to_string(),
+            reason: crate::specs::reader::LoadFailure::InvalidFrontmatter,
+            message: "found a tab character".to_string(),
+            line: Some(4),
+            column: Some(3),
+        };
+
+        let result = load_failure_result(&diagnostic);
 
-        let short_tokens = estimate_tokens(short_text);
-        let long_tokens = estimate_tokens(&long_text);
+        assert!(!result.valid);
+        assert_eq!(result.issues[0].code, "invalid-frontmatter");
+        assert_eq!(result.issues[0].line, Some(4));
+        assert_eq!(result.issues[0].span.as_ref().unwrap().column, 3);
+    }
+
+    #[test]
+    fn test_frontmatter_schema_rules() {
+        let spec = create_test_spec_with_content(
+            "---\nstatus: planned\nreviewers:\n  - alice\n  - 42\nepik: auth\n---\n\n# Test\n\n## Overview\n\nText\n",
+        );
+        let schema = FrontmatterSchema::new(serde_json::json!({
+            "properties": {
+                "epic": { "type": "string" },
+                "reviewers": { "type": "array", "items": { "type": "string" } }
+            }
+        }))
+        .unwrap();
+        let options = ValidationOptions {
+            schema: Some(Arc::new(schema)),
+            ..ValidationOptions::default()
+        };
 
-        assert!(short_tokens < 10);
-        assert!(long_tokens > 2000);
+        let result = validate_spec(&spec, &options);
+
+        let violation = result
+            .issues
+            .iter()
+            .find(|i| i.code == "frontmatter-schema")
+            .unwrap();
+        assert!(violation.message.starts_with("reviewers[1]: "));
+        assert_eq!(violation.line, Some(5));
+        let unknown = result
+            .issues
+            .iter()
+            .find(|i| i.code == "unknown-frontmatter-field")
+            .unwrap();
+        assert_eq!(
+            unknown.message,
+            "Unknown frontmatter field 'epik'. Did you mean 'epic'?"
+        );
+        assert_eq!(unknown.line, Some(6));
     }
 }
@@ -0,0 +1,355 @@
+//! Validation report export
+//!
+//! Serializes project validation results as SARIF 2.1.0 and JUnit XML so they
+//! can be attached to code review and CI tooling. File paths are relative to
+//! the project root.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use super::{list_rules, IssueSeverity, ValidationIssue, ValidationResult};
+use crate::specs::settings::ValidationSettings;
+use crate::specs::xml::escape_xml;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Supported report formats
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationReportFormat {
+    Sarif,
+    Junit,
+}
+
+/// Validation result of a spec and its README path relative to the project root
+pub struct ReportEntry {
+    pub path: String,
+    pub result: ValidationResult,
+}
+
+/// Serialize validation results in the given format
+pub fn export_report(
+    entries: &[ReportEntry],
+    settings: &ValidationSettings,
+    format: ValidationReportFormat,
+) -> String {
+    match format {
+        ValidationReportFormat::Sarif => to_sarif(entries, settings),
+        ValidationReportFormat::Junit => to_junit(entries),
+    }
+}
+
+fn to_sarif(entries: &[ReportEntry], settings: &ValidationSettings) -> String {
+    let descriptions: BTreeMap<String, String> = list_rules(settings)
+        .into_iter()
+        .map(|rule| (rule.code, rule.description))
+        .collect();
+
+    // Only rules that reported something, in a stable order
+    let mut codes: Vec<&str> = entries
+        .iter()
+        .flat_map(|entry| entry.result.issues.iter().map(|i| i.code.as_str()))
+        .collect();
+    codes.sort_unstable();
+    codes.dedup();
+
+    let rules: Vec<serde_json::Value> = codes
+        .iter()
+        .map(|code| {
+            let mut rule = json!({ "id": code });
+            if let Some(description) = descriptions.get(*code) {
+                rule["shortDescription"] = json!({ "text": description });
+            }
+            rule
+        })
+        .collect();
+
+    let codes = &codes;
+    let results: Vec<serde_json::Value> = entries
+        .iter()
+        .flat_map(|entry| {
+            entry.result.issues.iter().map(move |issue| {
+                let mut location = json!({
+                    "artifactLocation": { "uri": entry.path, "uriBaseId": "%SRCROOT%" }
+                });
+                if let Some(region) = sarif_region(issue) {
+                    location["region"] = region;
+                }
+                json!({
+                    "ruleId": issue.code,
+                    "ruleIndex": codes.iter().position(|c| *c == issue.code),
+                    "level": sarif_level(&issue.severity),
+                    "message": { "text": issue.message },
+                    "locations": [{ "physicalLocation": location }],
+                })
+            })
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "LeanSpec",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "originalUriBaseIds": { "%SRCROOT%": { "uri": "./" } },
+            "results": results,
+        }]
+    });
+    serde_json::to_string_pretty(&log).unwrap_or_default()
+}
+
+fn sarif_level(severity: &IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Error => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Info => "note",
+    }
+}
+
+fn sarif_region(issue: &ValidationIssue) -> Option<serde_json::Value> {
+    match (issue.span, issue.line) {
+        (Some(span), _) => Some(json!({
+            "startLine": span.line,
+            "startColumn": span.column,
+            "endLine": span.end_line,
+            "endColumn": span.end_column,
+        })),
+        (None, Some(line)) => Some(json!({ "startLine": line })),
+        (None, None) => None,
+    }
+}
+
+/// One test case per spec; error issues fail it, other issues go to its output
+fn to_junit(entries: &[ReportEntry]) -> String {
+    let failures = entries.iter().filter(|e| !e.result.valid).count();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"LeanSpec validation\" tests=\"{}\" failures=\"{}\">\n",
+        entries.len(),
+        failures
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"specs\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n",
+        entries.len(),
+        failures
+    ));
+
+    for entry in entries {
+        let (errors, others): (Vec<&ValidationIssue>, Vec<&ValidationIssue>) = entry
+            .result
+            .issues
+            .iter()
+            .partition(|i| i.severity == IssueSeverity::Error);
+
+        xml.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\"",
+            escape_xml(&entry.result.spec_name),
+            escape_xml(&entry.path),
+            escape_xml(&entry.path)
+        ));
+        if errors.is_empty() && others.is_empty() {
+            xml.push_str("/>\n");
+            continue;
+        }
+        xml.push_str(">\n");
+
+        if let Some(first) = errors.first() {
+            let body: Vec<String> = errors.iter().map(|i| junit_line(&entry.path, i)).collect();
+            xml.push_str(&format!(
+                "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                escape_xml(&first.message),
+                escape_xml(&first.code),
+                escape_xml(&body.join("\n"))
+            ));
+        }
+        if !others.is_empty() {
+            let body: Vec<String> = others.iter().map(|i| junit_line(&entry.path, i)).collect();
+            xml.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape_xml(&body.join("\n"))
+            ));
+        }
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+/// `path:line:column: severity [code] message`
+fn junit_line(path: &str, issue: &ValidationIssue) -> String {
+    let location = match (issue.span, issue.line) {
+        (Some(span), _) => format!("{}:{}:{}", path, span.line, span.column),
+        (None, Some(line)) => format!("{}:{}", path, line),
+        (None, None) => path.to_string(),
+    };
+    format!(
+        "{}: {} [{}] {}",
+        location,
+        sarif_level(&issue.severity),
+        issue.code,
+        issue.message
+    )
+}
+
+/// Write a report to `output_path`, relative to the project root
+///
+/// Absolute paths and paths leading outside the root, including through
+/// symlinked directories, are refused. Missing directories are created.
+pub fn write_report(root: &Path, output_path: &str, report: &str) -> Result<PathBuf, String> {
+    let outside = || format!("Report path '{}' is outside the project", output_path);
+
+    let mut relative = PathBuf::new();
+    for component in Path::new(output_path).components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !relative.pop() {
+                    return Err(outside());
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return Err(outside()),
+        }
+    }
+    if relative.as_os_str().is_empty() {
+        return Err(format!("Report path '{}' is not a file", output_path));
+    }
+
+    let root = dunce::canonicalize(root)
+        .map_err(|e| format!("Failed to resolve {}: {}", root.display(), e))?;
+    let path = root.join(&relative);
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .and_then(|p| dunce::canonicalize(p).ok());
+    if !existing.is_some_and(|p| p.starts_with(&root)) {
+        return Err(outside());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&path, report).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::frontmatter::SourceSpan;
+
+    fn entries() -> Vec<ReportEntry> {
+        let issue = |severity, code: &str, span: Option<SourceSpan>| {
+            ValidationIssue {
+                severity,
+                code: code.to_string(),
+                message: format!("{} <found>", code),
+                line: None,
+                span: None,
+                edge: None,
+            }
+            .at(span)
+        };
+        vec![
+            ReportEntry {
+                path: "specs/001-a/README.md".to_string(),
+                result: ValidationResult {
+                    spec_name: "001-a".to_string(),
+                    valid: false,
+                    issues: vec![
+                        issue(
+                            IssueSeverity::Error,
+                            "invalid-status",
+                            Some(SourceSpan::on_line(1, 8, 12)),
+                        ),
+                        issue(IssueSeverity::Info, "missing-overview", None),
+                    ],
+                },
+            },
+            ReportEntry {
+                path: "specs/002-b/README.md".to_string(),
+                result: ValidationResult {
+                    spec_name: "002-b".to_string(),
+                    valid: true,
+                    issues: Vec::new(),
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn test_sarif_report() {
+        let sarif = export_report(
+            &entries(),
+            &ValidationSettings::default(),
+            ValidationReportFormat::Sarif,
+        );
+        let log: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+        let run = &log["runs"][0];
+
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "invalid-status");
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "specs/001-a/README.md");
+        assert_eq!(location["region"]["startLine"], 2);
+        assert_eq!(location["region"]["startColumn"], 9);
+        assert_eq!(run["results"][1]["level"], "note");
+        assert!(run["results"][1]["locations"][0]["physicalLocation"]
+            .get("region")
+            .is_none());
+    }
+
+    #[test]
+    fn test_junit_report() {
+        let xml = export_report(
+            &entries(),
+            &ValidationSettings::default(),
+            ValidationReportFormat::Junit,
+        );
+
+        assert!(
+            xml.contains("<testsuites name=\"LeanSpec validation\" tests=\"2\" failures=\"1\">")
+        );
+        assert!(xml.contains("<failure message=\"invalid-status &lt;found&gt;\" type=\"invalid-status\">specs/001-a/README.md:2:9: error [invalid-status]"));
+        assert!(xml.contains("<system-out>specs/001-a/README.md: note [missing-overview]"));
+        assert!(xml.contains("<testcase name=\"002-b\" classname=\"specs/002-b/README.md\" file=\"specs/002-b/README.md\"/>"));
+    }
+
+    #[test]
+    fn test_write_report() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("project");
+        fs::create_dir_all(&root).unwrap();
+
+        let path = write_report(&root, "reports/./ci/../validation.sarif", "{}").unwrap();
+        assert!(path.ends_with("reports/validation.sarif"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+
+        for escaping in [
+            "../outside.sarif",
+            "reports/../../outside.sarif",
+            "/tmp/x.sarif",
+        ] {
+            assert_eq!(
+                write_report(&root, escaping, "{}").unwrap_err(),
+                format!("Report path '{}' is outside the project", escaping)
+            );
+        }
+        assert!(!temp.path().join("outside.sarif").exists());
+        assert!(write_report(&root, ".", "{}").is_err());
+    }
+}
@@ -0,0 +1,660 @@
+//! Validation rules
+//!
+//! Each rule is named by the `ValidationIssue::code` it reports. Projects can
+//! disable rules, override their severity and pass parameters through the
+//! `validation` section of `.lean-spec/config.json`:
+//!
+//! ```json
+//! {
+//!   "validation": {
+//!     "rules": {
+//!       "missing-overview": { "enabled": false },
+//!       "invalid-priority": { "severity": "error", "params": { "allowed": ["p0", "p1"] } }
+//!     },
+//!     "requiredSections": { "complete": ["Test Plan"] }
+//!   }
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::cell::OnceCell;
+
+use crate::specs::constants::{VALID_PRIORITIES, VALID_STATUSES};
+use crate::specs::frontmatter::{frontmatter_value, Frontmatter, SourceMap, SourceSpan};
+use crate::specs::reader::Spec;
+use crate::specs::validation::{IssueSeverity, ValidationOptions};
+
+pub mod content;
+
+/// Spec being validated, parsed once for all rules
+pub struct RuleContext<'a> {
+    pub spec: &'a Spec,
+    pub frontmatter: &'a Frontmatter,
+    /// Positions of frontmatter fields and headings
+    pub source: &'a SourceMap,
+    pub options: &'a ValidationOptions,
+    tokens: OnceCell<usize>,
+}
+
+impl<'a> RuleContext<'a> {
+    pub fn new(
+        spec: &'a Spec,
+        frontmatter: &'a Frontmatter,
+        source: &'a SourceMap,
+        options: &'a ValidationOptions,
+    ) -> Self {
+        Self {
+            spec,
+            frontmatter,
+            source,
+            options,
+            tokens: OnceCell::new(),
+        }
+    }
+
+    /// Token count of the spec, tokenized once on first use
+    pub fn tokens(&self) -> usize {
+        *self
+            .tokens
+            .get_or_init(|| self.options.tokens.count(&self.spec.content_md))
+    }
+
+    /// The token count as shown in messages, marked when it is an estimate
+    fn tokens_label(&self) -> String {
+        if self.options.tokens.approximate {
+            format!("about {}", self.tokens())
+        } else {
+            self.tokens().to_string()
+        }
+    }
+}
+
+/// Parameters configured for a rule
+#[derive(Clone, Copy, Default)]
+pub struct RuleParams<'a>(Option<&'a serde_json::Map<String, serde_json::Value>>);
+
+impl<'a> RuleParams<'a> {
+    pub fn new(params: &'a serde_json::Map<String, serde_json::Value>) -> Self {
+        Self(Some(params))
+    }
+
+    fn get(&self, key: &str) -> Option<&'a serde_json::Value> {
+        self.0.and_then(|params| params.get(key))
+    }
+
+    pub fn usize(&self, key: &str) -> Option<usize> {
+        self.get(key)?.as_u64().map(|n| n as usize)
+    }
+
+    pub fn string(&self, key: &str) -> Option<&'a str> {
+        self.get(key)?.as_str()
+    }
+
+    pub fn strings(&self, key: &str) -> Option<Vec<&'a str>> {
+        self.get(key)?
+            .as_array()
+            .map(|items| items.iter().filter_map(|v| v.as_str()).collect())
+    }
+}
+
+/// A problem found by a rule
+pub struct Finding {
+    pub message: String,
+    pub span: Option<SourceSpan>,
+}
+
+impl Finding {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            span: None,
+        }
+    }
+
+    fn at(mut self, span: Option<SourceSpan>) -> Self {
+        self.span = span;
+        self
+    }
+}
+
+/// A named check on a single spec
+pub trait ValidationRule: Send + Sync {
+    /// Stable name, used as the issue code
+    fn code(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn default_severity(&self) -> IssueSeverity;
+    fn check(&self, ctx: &RuleContext, params: RuleParams) -> Vec<Finding>;
+}
+
+/// Rule as listed for the UI, with the project's configuration applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleInfo {
+    pub code: String,
+    pub description: String,
+    pub default_severity: IssueSeverity,
+    pub severity: IssueSeverity,
+    pub enabled: bool,
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A check comparing specs with each other, run by `validate_all_specs`
+///
+/// Cross-spec checks take no parameters, but can be disabled or given another
+/// severity like single-spec rules.
+pub struct CrossSpecRule {
+    pub code: &'static str,
+    pub description: &'static str,
+    pub default_severity: IssueSeverity,
+}
+
+/// All built-in cross-spec checks
+pub const CROSS_SPEC_RULES: [CrossSpecRule; 14] = [
+    CrossSpecRule {
+        code: "duplicate-spec-number",
+        description: "Spec number is used by another spec",
+        default_severity: IssueSeverity::Error,
+    },
+    CrossSpecRule {
+        code: "similar-spec-slug",
+        description: "Spec name is nearly identical to another spec's",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "ambiguous-reference",
+        description: "Reference by number matches several specs",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "broken-dependency",
+        description: "Spec in depends_on does not exist",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "broken-related",
+        description: "Spec in related does not exist",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "broken-blocks",
+        description: "Spec in blocks does not exist",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "broken-supersedes",
+        description: "Spec in supersedes does not exist",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "broken-parent",
+        description: "Parent spec does not exist",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "unknown-project",
+        description: "Cross-project dependency names an unregistered project",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "broken-spec-link",
+        description: "Markdown link points to a spec that does not exist",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "complete-with-open-dependency",
+        description: "Complete spec depends on an open spec",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "in-progress-depends-on-draft",
+        description: "In-progress spec depends on a draft",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "archived-with-open-dependents",
+        description: "Archived spec is still required by open specs",
+        default_severity: IssueSeverity::Warning,
+    },
+    CrossSpecRule {
+        code: "superseded-not-archived",
+        description: "Superseded spec is not archived",
+        default_severity: IssueSeverity::Info,
+    },
+];
+
+/// All built-in single-spec rules, in reporting order
+pub fn builtin_rules() -> Vec<Box<dyn ValidationRule>> {
+    let mut rules: Vec<Box<dyn ValidationRule>> = vec![
+        Box::new(MissingStatus),
+        Box::new(InvalidStatus),
+        Box::new(InvalidPriority),
+        Box::new(MissingTitle),
+        Box::new(MissingOverview),
+        Box::new(MissingRequiredSection),
+        Box::new(EmptyDependency),
+        Box::new(FrontmatterSchemaViolation),
+        Box::new(UnknownFrontmatterField),
+        Box::new(HighTokenCount),
+        Box::new(ModerateTokenCount),
+    ];
+    rules.extend(content::content_rules());
+    rules
+}
+
+struct MissingStatus;
+
+impl ValidationRule for MissingStatus {
+    fn code(&self) -> &'static str {
+        "missing-status"
+    }
+
+    fn description(&self) -> &'static str {
+        "Frontmatter must have a status"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Error
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        if ctx.frontmatter.status.is_some() {
+            return Vec::new();
+        }
+        vec![Finding::new("Spec must have a status field in frontmatter").at(ctx.source.frontmatter)]
+    }
+}
+
+struct InvalidStatus;
+
+impl ValidationRule for InvalidStatus {
+    fn code(&self) -> &'static str {
+        "invalid-status"
+    }
+
+    fn description(&self) -> &'static str {
+        "Status must be a known value"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Error
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        match &ctx.frontmatter.status {
+            Some(status) if !VALID_STATUSES.contains(&status.as_str()) => {
+                vec![Finding::new(format!(
+                    "Invalid status '{}'. Must be one of: {}",
+                    status,
+                    VALID_STATUSES.join(", ")
+                ))
+                .at(ctx.source.value_span("status"))]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Params: `allowed` (list of priorities)
+struct InvalidPriority;
+
+impl ValidationRule for InvalidPriority {
+    fn code(&self) -> &'static str {
+        "invalid-priority"
+    }
+
+    fn description(&self) -> &'static str {
+        "Priority should be one of the allowed values"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    fn check(&self, ctx: &RuleContext, params: RuleParams) -> Vec<Finding> {
+        let allowed = params
+            .strings("allowed")
+            .unwrap_or_else(|| VALID_PRIORITIES.to_vec());
+        match &ctx.frontmatter.priority {
+            Some(priority) if !allowed.contains(&priority.as_str()) => {
+                vec![Finding::new(format!(
+                    "Invalid priority '{}'. Recommended: {}",
+                    priority,
+                    allowed.join(", ")
+                ))
+                .at(ctx.source.value_span("priority"))]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+struct MissingTitle;
+
+impl ValidationRule for MissingTitle {
+    fn code(&self) -> &'static str {
+        "missing-title"
+    }
+
+    fn description(&self) -> &'static str {
+        "Spec should have an H1 title"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        if ctx.spec.title.is_some() {
+            return Vec::new();
+        }
+        // Point at the first heading, which is often meant to be the title
+        let first_heading = ctx.source.headings.first().map(|h| h.span);
+        vec![Finding::new("Spec should have a title (H1 heading)").at(first_heading)]
+    }
+}
+
+/// Params: `section` (heading text, default `Overview`)
+struct MissingOverview;
+
+impl ValidationRule for MissingOverview {
+    fn code(&self) -> &'static str {
+        "missing-overview"
+    }
+
+    fn description(&self) -> &'static str {
+        "Spec should have an overview section"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Info
+    }
+
+    fn check(&self, ctx: &RuleContext, params: RuleParams) -> Vec<Finding> {
+        let section = params.string("section").unwrap_or("Overview");
+        if ctx.source.section(section).is_some() {
+            return Vec::new();
+        }
+        vec![Finding::new(format!(
+            "Consider adding an ## {} section",
+            section
+        ))]
+    }
+}
+
+/// Sections come from `requiredSections` for the spec's status
+struct MissingRequiredSection;
+
+impl ValidationRule for MissingRequiredSection {
+    fn code(&self) -> &'static str {
+        "missing-required-section"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sections the project requires for the spec's status"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Error
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        let required = ctx.options.settings.required_sections_for(&ctx.spec.status);
+        missing_sections(ctx.source, required)
+            .into_iter()
+            .map(|section| {
+                Finding::new(format!(
+                    "Specs that are {} must have a ## {} section",
+                    ctx.spec.status, section
+                ))
+            })
+            .collect()
+    }
+}
+
+struct EmptyDependency;
+
+impl ValidationRule for EmptyDependency {
+    fn code(&self) -> &'static str {
+        "empty-dependency"
+    }
+
+    fn description(&self) -> &'static str {
+        "depends_on entries must not be empty"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        let spans: Vec<SourceSpan> = ctx
+            .source
+            .field("depends_on")
+            .map(|field| {
+                field
+                    .items
+                    .iter()
+                    .filter(|item| item.value.is_empty())
+                    .map(|item| item.span)
+                    .collect()
+            })
+            .unwrap_or_default();
+        ctx.frontmatter
+            .depends_on
+            .iter()
+            .filter(|dep| dep.trim().is_empty())
+            .enumerate()
+            .map(|(index, _)| {
+                Finding::new("Empty dependency in depends_on list").at(spans.get(index).copied())
+            })
+            .collect()
+    }
+}
+
+/// Checks the frontmatter against `.lean-spec/frontmatter.schema.json`
+struct FrontmatterSchemaViolation;
+
+impl ValidationRule for FrontmatterSchemaViolation {
+    fn code(&self) -> &'static str {
+        "frontmatter-schema"
+    }
+
+    fn description(&self) -> &'static str {
+        "Frontmatter must match the project's frontmatter schema"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        let Some(schema) = &ctx.options.schema else {
+            return Vec::new();
+        };
+        let Some(frontmatter) = frontmatter_value(&ctx.spec.content_md) else {
+            return Vec::new();
+        };
+
+        schema
+            .validate(&frontmatter)
+            .into_iter()
+            .map(|violation| {
+                let span = match violation.path.as_slice() {
+                    [] => ctx.source.frontmatter,
+                    [key] => ctx.source.value_span(key),
+                    [key, index, ..] => index
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| ctx.source.field(key)?.items.get(index))
+                        .map(|item| item.span)
+                        .or_else(|| ctx.source.value_span(key)),
+                };
+                let message = if violation.path.is_empty() {
+                    format!("Frontmatter: {}", violation.message)
+                } else {
+                    format!("{}: {}", violation.display_path(), violation.message)
+                };
+                Finding::new(message).at(span)
+            })
+            .collect()
+    }
+}
+
+/// Keys neither built in nor declared in the project's frontmatter schema
+struct UnknownFrontmatterField;
+
+impl ValidationRule for UnknownFrontmatterField {
+    fn code(&self) -> &'static str {
+        "unknown-frontmatter-field"
+    }
+
+    fn description(&self) -> &'static str {
+        "Frontmatter keys must be built in or declared in the frontmatter schema"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        let Some(schema) = &ctx.options.schema else {
+            return Vec::new();
+        };
+        let Some(frontmatter) = frontmatter_value(&ctx.spec.content_md) else {
+            return Vec::new();
+        };
+
+        schema
+            .unknown_fields(&frontmatter)
+            .into_iter()
+            .map(|field| {
+                let message = match &field.suggestion {
+                    Some(suggestion) => format!(
+                        "Unknown frontmatter field '{}'. Did you mean '{}'?",
+                        field.key, suggestion
+                    ),
+                    None => format!("Unknown frontmatter field '{}'", field.key),
+                };
+                let span = ctx.source.field(&field.key).map(|f| f.span);
+                Finding::new(message).at(span)
+            })
+            .collect()
+    }
+}
+
+/// Params: `maxTokens` (defaults to the project's token settings)
+struct HighTokenCount;
+
+impl ValidationRule for HighTokenCount {
+    fn code(&self) -> &'static str {
+        "high-token-count"
+    }
+
+    fn description(&self) -> &'static str {
+        "Spec is larger than the token maximum"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    fn check(&self, ctx: &RuleContext, params: RuleParams) -> Vec<Finding> {
+        let max_tokens = max_tokens(ctx, params);
+        let tokens = ctx.tokens();
+        if tokens <= max_tokens {
+            return Vec::new();
+        }
+        vec![Finding::new(format!(
+            "Spec has {} tokens, exceeds maximum of {}. Consider splitting into smaller specs.",
+            ctx.tokens_label(),
+            max_tokens
+        ))]
+    }
+}
+
+/// Params: `warningTokens` (defaults to the project's token settings)
+struct ModerateTokenCount;
+
+impl ValidationRule for ModerateTokenCount {
+    fn code(&self) -> &'static str {
+        "moderate-token-count"
+    }
+
+    fn description(&self) -> &'static str {
+        "Spec is approaching the token maximum"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Info
+    }
+
+    fn check(&self, ctx: &RuleContext, params: RuleParams) -> Vec<Finding> {
+        let warning_tokens = params
+            .usize("warningTokens")
+            .unwrap_or(ctx.options.tokens.warning_tokens);
+        let max_tokens = max_tokens(ctx, RuleParams::default());
+        let tokens = ctx.tokens();
+        if tokens <= warning_tokens || tokens > max_tokens {
+            return Vec::new();
+        }
+        vec![Finding::new(format!(
+            "Spec has {} tokens, approaching maximum of {}. Consider splitting if content grows.",
+            ctx.tokens_label(),
+            max_tokens
+        ))]
+    }
+}
+
+/// Token maximum, honoring an override on the `high-token-count` rule
+fn max_tokens(ctx: &RuleContext, params: RuleParams) -> usize {
+    params
+        .usize("maxTokens")
+        .or_else(|| {
+            ctx.options
+                .settings
+                .params("high-token-count")
+                .usize("maxTokens")
+        })
+        .unwrap_or(ctx.options.tokens.max_tokens)
+}
+
+/// Required sections with no matching `##`-`######` heading
+pub fn missing_sections<'a>(source: &SourceMap, required: &'a [String]) -> Vec<&'a String> {
+    required
+        .iter()
+        .filter(|section| source.section(section).is_none())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_sections() {
+        let source = crate::specs::frontmatter::parse_source_map(
+            "# Title\n\n## Overview\n\n### test plan \n\nText ## Design\n```\n## Notes\n```",
+        );
+        let required: Vec<String> = ["Overview", "Test Plan", "Design", "Title", "Notes"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(
+            missing_sections(&source, &required),
+            vec!["Design", "Title", "Notes"]
+        );
+    }
+
+    #[test]
+    fn test_rule_params() {
+        let params: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(r#"{ "maxTokens": 800, "allowed": ["p0", "p1"] }"#).unwrap();
+        let params = RuleParams::new(&params);
+
+        assert_eq!(params.usize("maxTokens"), Some(800));
+        assert_eq!(params.strings("allowed"), Some(vec!["p0", "p1"]));
+        assert_eq!(params.string("section"), None);
+        assert_eq!(RuleParams::default().usize("maxTokens"), None);
+    }
+}
@@ -0,0 +1,504 @@
+//! Markdown content rules
+//!
+//! Checks the body of a spec: relative links, heading structure, empty
+//! sections, leftover placeholders and unchecked tasks. Fenced code blocks and
+//! inline code are ignored.
+
+use std::path::{Component, Path};
+
+use super::{Finding, RuleContext, RuleParams, ValidationRule};
+use crate::specs::frontmatter::SourceSpan;
+use crate::specs::tasks::task_item;
+use crate::specs::validation::IssueSeverity;
+
+struct BrokenLink;
+
+impl ValidationRule for BrokenLink {
+    fn code(&self) -> &'static str {
+        "broken-link"
+    }
+
+    fn description(&self) -> &'static str {
+        "Relative links and images must point to existing files"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        let Some(specs_dir) = &ctx.options.specs_dir else {
+            return Vec::new();
+        };
+        let relative = ctx
+            .spec
+            .file_path
+            .strip_prefix("specs/")
+            .unwrap_or(&ctx.spec.file_path);
+        let Some(spec_dir) = Path::new(relative).parent() else {
+            return Vec::new();
+        };
+
+        relative_links(&ctx.spec.content_md)
+            .into_iter()
+            // Links into other specs are checked against the loaded specs
+            .filter(|link| linked_spec(spec_dir, &link.target).is_none())
+            .filter(|link| !specs_dir.join(spec_dir).join(&link.target).exists())
+            .map(|link| {
+                let kind = if link.image { "Image" } else { "Link" };
+                Finding::new(format!("{} target '{}' does not exist", kind, link.target))
+                    .at(Some(link.span))
+            })
+            .collect()
+    }
+}
+
+struct HeadingStructure;
+
+impl ValidationRule for HeadingStructure {
+    fn code(&self) -> &'static str {
+        "heading-structure"
+    }
+
+    fn description(&self) -> &'static str {
+        "A single H1 and no skipped heading levels"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let mut previous_level = 0;
+        let mut seen_h1 = false;
+
+        for heading in &ctx.source.headings {
+            if heading.level == 1 {
+                if seen_h1 {
+                    findings.push(
+                        Finding::new(format!("Multiple H1 headings: '{}'", heading.text))
+                            .at(Some(heading.span)),
+                    );
+                }
+                seen_h1 = true;
+            } else if previous_level > 0 && heading.level > previous_level + 1 {
+                findings.push(
+                    Finding::new(format!(
+                        "Heading '{}' skips from H{} to H{}",
+                        heading.text, previous_level, heading.level
+                    ))
+                    .at(Some(heading.span)),
+                );
+            }
+            previous_level = heading.level;
+        }
+        findings
+    }
+}
+
+struct EmptySection;
+
+impl ValidationRule for EmptySection {
+    fn code(&self) -> &'static str {
+        "empty-section"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sections should have content"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Info
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        let lines: Vec<&str> = ctx.spec.content_md.lines().collect();
+        let headings = &ctx.source.headings;
+
+        headings
+            .iter()
+            .enumerate()
+            .filter(|(_, heading)| heading.level >= 2)
+            .filter(|(i, heading)| {
+                let next = headings.get(i + 1);
+                // A subsection counts as content
+                if next.is_some_and(|n| n.level > heading.level) {
+                    return false;
+                }
+                let start = heading.span.line as usize;
+                let end = next
+                    .map(|n| n.span.line as usize - 1)
+                    .unwrap_or(lines.len());
+                lines[start.min(end)..end]
+                    .iter()
+                    .all(|line| line.trim().is_empty())
+            })
+            .map(|(_, heading)| {
+                Finding::new(format!("Section '{}' is empty", heading.text)).at(Some(heading.span))
+            })
+            .collect()
+    }
+}
+
+struct IncompleteTask;
+
+impl ValidationRule for IncompleteTask {
+    fn code(&self) -> &'static str {
+        "incomplete-task"
+    }
+
+    fn description(&self) -> &'static str {
+        "Complete specs should have no unchecked task items"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    fn check(&self, ctx: &RuleContext, _params: RuleParams) -> Vec<Finding> {
+        if ctx.spec.status != "complete" {
+            return Vec::new();
+        }
+        body_lines(&ctx.spec.content_md)
+            .into_iter()
+            .filter_map(|(index, line)| {
+                let task = unchecked_task(&line)?;
+                let start = char_column(&line, line.len() - line.trim_start().len());
+                let end = line.trim_end().chars().count();
+                Some(
+                    Finding::new(format!("Spec is complete but task '{}' is unchecked", task))
+                        .at(Some(SourceSpan::on_line(index, start, end))),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Params: `markers` (list of words, default `TODO`, `TBD`)
+struct PlaceholderMarker;
+
+impl ValidationRule for PlaceholderMarker {
+    fn code(&self) -> &'static str {
+        "placeholder-marker"
+    }
+
+    fn description(&self) -> &'static str {
+        "Leftover TODO/TBD markers in the spec body"
+    }
+
+    fn default_severity(&self) -> IssueSeverity {
+        IssueSeverity::Info
+    }
+
+    fn check(&self, ctx: &RuleContext, params: RuleParams) -> Vec<Finding> {
+        let markers = params
+            .strings("markers")
+            .unwrap_or_else(|| vec!["TODO", "TBD"]);
+        let mut findings = Vec::new();
+        for (index, line) in body_lines(&ctx.spec.content_md) {
+            for marker in &markers {
+                for start in find_word(&line, marker) {
+                    let column = char_column(&line, start);
+                    let width = marker.chars().count();
+                    findings.push(
+                        Finding::new(format!("Leftover {} marker", marker))
+                            .at(Some(SourceSpan::on_line(index, column, column + width))),
+                    );
+                }
+            }
+        }
+        findings.sort_by_key(|f| f.span.map(|s| (s.line, s.column)));
+        findings
+    }
+}
+
+/// Content rules, in reporting order
+pub(super) fn content_rules() -> Vec<Box<dyn ValidationRule>> {
+    vec![
+        Box::new(BrokenLink),
+        Box::new(HeadingStructure),
+        Box::new(EmptySection),
+        Box::new(PlaceholderMarker),
+        Box::new(IncompleteTask),
+    ]
+}
+
+/// A relative link or image in the spec body
+#[derive(Debug, Clone)]
+pub struct RelativeLink {
+    /// Path without fragment or query
+    pub target: String,
+    pub image: bool,
+    pub span: SourceSpan,
+}
+
+/// Relative links, images and reference definitions in a spec
+pub fn relative_links(content: &str) -> Vec<RelativeLink> {
+    let mut links = Vec::new();
+    for (index, line) in body_lines(content) {
+        // Reference definition: `[label]: path`
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('[') {
+            if let Some(close) = trimmed.find("]:") {
+                let offset = line.len() - trimmed.len() + close + 2;
+                if let Some(link) = parse_target(&line, index, offset, line.len(), false) {
+                    links.push(link);
+                }
+                continue;
+            }
+        }
+
+        // Inline: `[text](path)` and `![alt](path)`
+        let mut search = 0;
+        while let Some(found) = line[search..].find("](") {
+            let open = search + found + 2;
+            let Some(len) = line[open..].find(')') else {
+                break;
+            };
+            let image = line[..search + found]
+                .rfind('[')
+                .is_some_and(|bracket| line[..bracket].ends_with('!'));
+            if let Some(link) = parse_target(&line, index, open, open + len, image) {
+                links.push(link);
+            }
+            search = open + len;
+        }
+    }
+    links
+}
+
+/// Spec directory a link resolves into, when it leaves the linking spec
+///
+/// `spec_dir` is the linking spec's directory relative to the specs root
+/// (`012-auth` or `archived/012-auth`).
+pub fn linked_spec(spec_dir: &Path, target: &str) -> Option<String> {
+    let mut parts: Vec<String> = spec_dir
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    for component in Path::new(target).components() {
+        match component {
+            Component::ParentDir => {
+                // Leaves the specs directory
+                parts.pop()?;
+            }
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+
+    let mut parts = parts.into_iter();
+    let first = parts.next()?;
+    let name = if first == "archived" {
+        parts.next()?
+    } else {
+        first
+    };
+    let own = spec_dir.file_name()?.to_string_lossy();
+    let is_spec_dir = name.chars().next().is_some_and(|c| c.is_ascii_digit());
+    (is_spec_dir && name != own).then_some(name)
+}
+
+fn parse_target(
+    line: &str,
+    index: usize,
+    start: usize,
+    end: usize,
+    image: bool,
+) -> Option<RelativeLink> {
+    let raw = &line[start..end];
+    let leading = raw.len() - raw.trim_start().len();
+    let raw = raw.trim();
+    // Drop an optional title: `path "Title"`
+    let raw = raw.split_whitespace().next().unwrap_or("");
+    let raw = raw
+        .strip_prefix('<')
+        .and_then(|r| r.strip_suffix('>'))
+        .unwrap_or(raw);
+
+    let is_external = raw.contains("://")
+        || ["mailto:", "tel:", "data:"]
+            .iter()
+            .any(|scheme| raw.starts_with(scheme));
+    if raw.is_empty() || raw.starts_with('#') || raw.starts_with('/') || is_external {
+        return None;
+    }
+
+    let path = raw.split(['#', '?']).next().unwrap_or(raw);
+    let column = char_column(line, start + leading);
+    Some(RelativeLink {
+        target: path.replace("%20", " "),
+        image,
+        span: SourceSpan::on_line(index, column, column + raw.chars().count()),
+    })
+}
+
+/// Body lines outside frontmatter and fenced code, with inline code blanked
+/// so columns are kept
+fn body_lines(content: &str) -> Vec<(usize, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    if lines.first().is_some_and(|l| l.trim_end() == "---") {
+        if let Some(close) = lines.iter().skip(1).position(|l| l.trim_end() == "---") {
+            start = close + 2;
+        }
+    }
+
+    let mut fence: Option<&str> = None;
+    let mut body = Vec::new();
+    for (index, line) in lines.iter().enumerate().skip(start) {
+        let trimmed = line.trim_start();
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            match fence {
+                Some(open) if open == marker => fence = None,
+                None => fence = Some(marker),
+                _ => {}
+            }
+            continue;
+        }
+        if fence.is_none() {
+            body.push((index, blank_inline_code(line)));
+        }
+    }
+    body
+}
+
+fn blank_inline_code(line: &str) -> String {
+    let mut in_code = false;
+    line.chars()
+        .map(|c| {
+            if c == '`' {
+                in_code = !in_code;
+                ' '
+            } else if in_code {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Byte offsets of `word` not surrounded by other word characters
+fn find_word(line: &str, word: &str) -> Vec<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    line.match_indices(word)
+        .map(|(start, _)| start)
+        .filter(|&start| {
+            let before = line[..start].chars().next_back();
+            let after = line[start + word.len()..].chars().next();
+            !before.is_some_and(is_word) && !after.is_some_and(is_word)
+        })
+        .collect()
+}
+
+/// Text of an unchecked `- [ ]` / `1. [ ]` task item
+fn unchecked_task(line: &str) -> Option<&str> {
+    task_item(line)
+        .filter(|(done, _)| !done)
+        .map(|(_, text)| text)
+}
+
+fn char_column(line: &str, byte: usize) -> usize {
+    line[..byte].chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::frontmatter::{parse_frontmatter, parse_source_map};
+    use crate::specs::reader::Spec;
+    use crate::specs::reader::SpecBuilder;
+    use crate::specs::validation::ValidationOptions;
+
+    fn spec(status: &str, content: &str) -> Spec {
+        SpecBuilder::new("001-test")
+            .title("Test")
+            .status(status)
+            .content(content)
+            .build()
+    }
+
+    fn check(rule: &dyn ValidationRule, spec: &Spec, options: &ValidationOptions) -> Vec<String> {
+        let (frontmatter, _) = parse_frontmatter(&spec.content_md);
+        let source = parse_source_map(&spec.content_md);
+        let ctx = RuleContext::new(spec, &frontmatter, &source, options);
+        rule.check(&ctx, RuleParams::default())
+            .into_iter()
+            .map(|f| {
+                let span = f.span.unwrap();
+                format!("{}:{} {}", span.line, span.column, f.message)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_structure_rules() {
+        let content = "---\nstatus: complete\n---\n\n# Test\n\n## Overview\n\n#### Deep\n\nTODO: write `TODO` this\n\n## Empty\n\n## Plan\n\n- [x] Done\n- [ ] Ship it\n\n```\n- [ ] TBD\n```\n\n# Second\n";
+        let spec = spec("complete", content);
+        let options = ValidationOptions::default();
+
+        assert_eq!(
+            check(&HeadingStructure, &spec, &options),
+            vec![
+                "9:1 Heading 'Deep' skips from H2 to H4",
+                "24:1 Multiple H1 headings: 'Second'"
+            ]
+        );
+        assert_eq!(
+            check(&EmptySection, &spec, &options),
+            vec!["13:1 Section 'Empty' is empty"]
+        );
+        assert_eq!(
+            check(&PlaceholderMarker, &spec, &options),
+            vec!["11:1 Leftover TODO marker"]
+        );
+        assert_eq!(
+            check(&IncompleteTask, &spec, &options),
+            vec!["18:1 Spec is complete but task 'Ship it' is unchecked"]
+        );
+    }
+
+    #[test]
+    fn test_broken_links() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let spec_dir = temp.path().join("001-test");
+        std::fs::create_dir_all(&spec_dir).unwrap();
+        std::fs::write(spec_dir.join("design.md"), "").unwrap();
+
+        let content = "# Test\n\nSee [design](./design.md#api), [notes](notes.md \"Notes\"), \
+            ![diagram](img/flow.png), [web](https://example.com) and [other](../002-other/README.md).\n\n\
+            [ref]: missing.md\n";
+        let spec = spec("planned", content);
+        let options = ValidationOptions {
+            specs_dir: Some(temp.path().to_path_buf()),
+            ..ValidationOptions::default()
+        };
+
+        assert_eq!(
+            check(&BrokenLink, &spec, &options),
+            vec![
+                "3:40 Link target 'notes.md' does not exist",
+                "3:70 Image target 'img/flow.png' does not exist",
+                "5:8 Link target 'missing.md' does not exist",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_linked_spec() {
+        let dir = Path::new("001-test");
+        assert_eq!(
+            linked_spec(dir, "../002-other/README.md"),
+            Some("002-other".to_string())
+        );
+        assert_eq!(
+            linked_spec(Path::new("archived/001-test"), "../../003-x"),
+            Some("003-x".to_string())
+        );
+        assert_eq!(linked_spec(dir, "./design.md"), None);
+        assert_eq!(linked_spec(dir, "../../docs/guide.md"), None);
+    }
+}
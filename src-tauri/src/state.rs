@@ -1,4 +1,7 @@
 use crate::projects::ProjectStore;
+use crate::specs::code_activity::GitActivityCache;
+use crate::specs::dependencies::ExternalReferenceCache;
+use crate::specs::portfolio::PortfolioCache;
 use leanspec_core::storage::chat_config::ChatConfigStore;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -6,6 +9,9 @@ use tokio::sync::RwLock;
 pub struct DesktopState {
     pub project_store: ProjectStore,
     pub chat_config: Arc<RwLock<ChatConfigStore>>,
+    pub portfolio_cache: PortfolioCache,
+    pub external_reference_cache: ExternalReferenceCache,
+    pub git_activity_cache: GitActivityCache,
 }
 
 impl DesktopState {
@@ -14,6 +20,9 @@ impl DesktopState {
         Self {
             project_store: ProjectStore::load(),
             chat_config: Arc::new(RwLock::new(chat_config)),
+            portfolio_cache: PortfolioCache::default(),
+            external_reference_cache: ExternalReferenceCache::default(),
+            git_activity_cache: GitActivityCache::default(),
         }
     }
 }
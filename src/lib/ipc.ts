@@ -4,9 +4,27 @@ import type {
   Spec,
   LightweightSpec,
   StatsResult,
+  FlowMetrics,
+  CompletionForecast,
+  PortfolioStats,
+  StatsSnapshot,
   DependencyGraph,
   SpecDependencies,
+  SpecImpact,
+  ImpactDirection,
+  GraphExportFormat,
+  LayoutGrouping,
   ValidationResult,
+  StalenessReport,
+  AssigneeReport,
+  TagAnalytics,
+  SpecTokenCount,
+  ValidationRuleInfo,
+  FixReport,
+  LoadDiagnostic,
+  ValidationReportFormat,
+  TraceabilityReport,
+  CodeActivity,
 } from '../types';
 
 // ============================================================================
@@ -77,12 +95,75 @@ export async function getProjectStats(projectId: string): Promise<StatsResult> {
   return invoke<StatsResult>('get_project_stats', { projectId });
 }
 
+/**
+ * Record today's stats snapshot for trend charts
+ * Only the first snapshot of a day is kept; resolves to whether one was written
+ */
+export async function recordStatsSnapshot(projectId: string): Promise<boolean> {
+  return invoke<boolean>('record_stats_snapshot', { projectId });
+}
+
+/**
+ * Forecast when the specs matching a query and/or tag will all be complete
+ * (Monte Carlo over recent weekly throughput)
+ */
+export async function getCompletionForecast(
+  projectId: string,
+  selection?: { query?: string; tag?: string; historyWeeks?: number; seed?: number }
+): Promise<CompletionForecast> {
+  return invoke<CompletionForecast>('get_completion_forecast', { projectId, ...selection });
+}
+
+/**
+ * Get daily stats snapshots for trend charts
+ * With `backfill`, missing days are reconstructed from git history first
+ */
+export async function getStatsHistory(
+  projectId: string,
+  options?: { from?: string; to?: string; backfill?: boolean }
+): Promise<StatsSnapshot[]> {
+  return invoke<StatsSnapshot[]>('get_stats_history', { projectId, ...options });
+}
+
+/**
+ * Get combined stats across all registered projects (or the given subset)
+ */
+export async function getPortfolioStats(projectIds?: string[]): Promise<PortfolioStats> {
+  return invoke<PortfolioStats>('get_portfolio_stats', { projectIds });
+}
+
+/**
+ * Get lead time, cycle time, weekly throughput and time in status
+ * Dates accept `YYYY-MM-DD` or RFC 3339 timestamps
+ */
+export async function getFlowMetrics(
+  projectId: string,
+  range?: { from?: string; to?: string }
+): Promise<FlowMetrics> {
+  return invoke<FlowMetrics>('get_flow_metrics', { projectId, ...range });
+}
+
 /**
  * Get dependency graph for visualization
  * Replaces: GET /api/projects/[id]/specs/[spec]/dependency-graph
+ * Pass `layout` to have the backend compute a layered layout with that grouping.
  */
-export async function getDependencyGraph(projectId: string): Promise<DependencyGraph> {
-  return invoke<DependencyGraph>('get_dependency_graph', { projectId });
+export async function getDependencyGraph(
+  projectId: string,
+  layout?: LayoutGrouping
+): Promise<DependencyGraph> {
+  return invoke<DependencyGraph>('get_dependency_graph', { projectId, layout });
+}
+
+/**
+ * Export the dependency graph as DOT, Mermaid or GraphML text
+ */
+export async function exportDependencyGraph(
+  projectId: string,
+  format: GraphExportFormat,
+  filters?: { status?: string[]; tag?: string; root?: string; withPositions?: boolean }
+): Promise<string> {
+  return invoke<string>('export_dependency_graph', { projectId, format, ...filters });
 }
 
 /**
@@ -93,6 +174,18 @@ export async function getSpecDependencies(projectId: string, specId: string): Pr
   return invoke<SpecDependencies>('get_spec_dependencies_cmd', { projectId, specId });
 }
 
+/**
+ * Get transitive upstream or downstream impact of a spec
+ */
+export async function getSpecImpact(
+  projectId: string,
+  specId: string,
+  direction?: ImpactDirection,
+  maxDepth?: number
+): Promise<SpecImpact> {
+  return invoke<SpecImpact>('get_spec_impact_cmd', { projectId, specId, direction, maxDepth });
+}
+
 /**
  * Search specs by query
  * Part of: GET /api/projects/[id]/specs with query param
@@ -117,6 +210,32 @@ export async function getAllTags(projectId: string): Promise<string[]> {
   return invoke<string[]>('get_all_tags', { projectId });
 }
 
+/**
+ * Get per-tag counts by status, co-occurring tag pairs, near-duplicate tags
+ * and tags used by a single spec
+ */
+export async function getTagAnalytics(projectId: string): Promise<TagAnalytics> {
+  return invoke<TagAnalytics>('get_tag_analytics', { projectId });
+}
+
+/**
+ * Rename a tag on every spec that uses it; returns the rewritten spec names
+ */
+export async function renameTag(projectId: string, from: string, to: string): Promise<string[]> {
+  return invoke<string[]>('rename_tag', { projectId, from, to });
+}
+
+/**
+ * Merge tags into a target tag on every affected spec; returns the rewritten spec names
+ */
+export async function mergeTags(
+  projectId: string,
+  sources: string[],
+  target: string
+): Promise<string[]> {
+  return invoke<string[]>('merge_tags', { projectId, sources, target });
+}
+
 /**
  * Validate a single spec
  * Part of: POST /api/projects/[id]/validate
@@ -133,6 +252,115 @@ export async function validateAllSpecs(projectId: string): Promise<ValidationRes
   return invoke<ValidationResult[]>('validate_all_specs_cmd', { projectId });
 }
 
+/**
+ * Export project validation results as SARIF 2.1.0 or JUnit XML.
+ * The report is also written to `outputPath` (relative to the project root) when given;
+ * paths outside the project are refused.
+ */
+export async function exportValidationReport(
+  projectId: string,
+  format: ValidationReportFormat,
+  outputPath?: string
+): Promise<string> {
+  return invoke<string>('export_validation_report', { projectId, format, outputPath });
+}
+
+/**
+ * List spec directories that failed to load (missing README, invalid YAML, no status)
+ */
+export async function getLoadDiagnostics(projectId: string): Promise<LoadDiagnostic[]> {
+  return invoke<LoadDiagnostic[]>('get_load_diagnostics', { projectId });
+}
+
+/**
+ * List validation rules with the project's overrides from .lean-spec/config.json
+ */
+export async function getValidationRules(projectId: string): Promise<ValidationRuleInfo[]> {
+  return invoke<ValidationRuleInfo[]>('get_validation_rules', { projectId });
+}
+
+/**
+ * Get the project's frontmatter JSON Schema from .lean-spec/frontmatter.schema.json, if any
+ * Rejects with the file path when the schema is invalid.
+ */
+export async function getFrontmatterSchema(projectId: string): Promise<Record<string, unknown> | null> {
+  return invoke<Record<string, unknown> | null>('get_frontmatter_schema', { projectId });
+}
+
+/**
+ * Fix validation issues that have a safe automatic fix, for one spec or all specs.
+ * Returns a diff and content hash per spec. To apply, pass the previewed hashes by
+ * spec name; only those specs are written, and nothing is written if any changed.
+ */
+export async function fixSpecIssues(
+  projectId: string,
+  specId?: string,
+  expectedHashes?: Record<string, string>
+): Promise<FixReport> {
+  return invoke<FixReport>('fix_spec_issues', {
+    projectId,
+    specId,
+    apply: expectedHashes !== undefined,
+    expectedHashes,
+  });
+}
+
+/**
+ * Find stale in-progress, neglected planned and untouched draft specs, and
+ * specs whose assignee has been inactive (thresholds from .lean-spec/config.json)
+ */
+export async function getStaleSpecs(projectId: string): Promise<StalenessReport> {
+  return invoke<StalenessReport>('get_stale_specs', { projectId });
+}
+
+/**
+ * Get open work, estimated tokens and completions per assignee, plus open
+ * high/critical specs without an assignee (aliases from .lean-spec/assignees.json)
+ */
+export async function getAssigneeStats(projectId: string): Promise<AssigneeReport> {
+  return invoke<AssigneeReport>('get_assignee_stats', { projectId });
+}
+
+/**
+ * Count a spec's tokens with the tokenizer of the project's model
+ * (pinned in .lean-spec/config.json, else the default chat model or runner)
+ */
+export async function getSpecTokens(projectId: string, specId: string): Promise<SpecTokenCount> {
+  return invoke<SpecTokenCount>('get_spec_tokens', { projectId, specId });
+}
+
+/**
+ * Find the source lines and commits referencing each spec (e.g. `spec 042`, `LS-042`).
+ * Skips .gitignore'd paths; complete specs without code references are flagged as untraced.
+ */
+export async function getSpecTraceability(
+  projectId: string,
+  specId?: string
+): Promise<TraceabilityReport> {
+  return invoke<TraceabilityReport>('get_spec_traceability', { projectId, specId });
+}
+
+/**
+ * Get the local git commits and branches mentioning a spec, newest first,
+ * with whether each branch is merged into the default branch
+ */
+export async function getSpecActivity(projectId: string, specId: string): Promise<CodeActivity> {
+  return invoke<CodeActivity>('get_spec_activity', { projectId, specId });
+}
+
+/**
+ * Check or uncheck the task list item on a 1-based line of a spec.
+ * Fails when the file no longer matches `expectedContent` (the content the UI read).
+ */
+export async function toggleSpecTask(
+  projectId: string,
+  specId: string,
+  line: number,
+  expectedContent: string
+): Promise<Spec> {
+  return invoke<Spec>('toggle_spec_task', { projectId, specId, line, expectedContent });
+}
+
 /**
  * Update spec status
  * Replaces: POST /api/projects/[id]/specs/[spec]/status
@@ -69,16 +69,26 @@ export interface DesktopBootstrapPayload {
 // ============================================================================
 
 /** A full spec with all content */
-export type Spec = UiSpec;
+export type Spec = UiSpec & TaskProgress;
 
 /** Lightweight spec without full content (for list views) */
-export type LightweightSpec = UiLightweightSpec;
+export type LightweightSpec = UiLightweightSpec &
+  TaskProgress & {
+    /** Latest commit or branch mentioning the spec */
+    lastCodeActivity?: string | null;
+  };
 
 /** Statistics result for a project */
-export type StatsResult = UiStatsResult;
+export type StatsResult = UiStatsResult & {
+  /** Task list items across all specs */
+  tasksTotal: number;
+  tasksDone: number;
+  /** Percentage of task list items checked off */
+  taskCompletionRate: number;
+};
 
-/** Complete dependency graph */
-export type DependencyGraph = UiDependencyGraph;
+/** Complete dependency graph, with the backend layered layout when requested */
+export type DependencyGraph = UiDependencyGraph & { layout?: GraphLayout };
 
 /** Dependencies for a specific spec */
 export type SpecDependencies = UiSpecDependencies;
@@ -87,7 +97,354 @@ export type SpecDependencies = UiSpecDependencies;
 export type DependencyInfo = UiDependencyInfo;
 
 /** Validation result */
-export type ValidationResult = UiValidationResult;
+export type ValidationResult = Omit<UiValidationResult, 'issues'> & { issues: ValidationIssue[] };
 
-/** Validation issue */
-export type ValidationIssue = UiValidationIssue;
+/** 1-based position range in a spec's README.md; the end is exclusive */
+export interface SourceSpan {
+  line: number;
+  column: number;
+  endLine: number;
+  endColumn: number;
+}
+
+/** Validation issue, with the range it applies to when known */
+export type ValidationIssue = UiValidationIssue & { span?: SourceSpan };
+
+/** Direction to walk the dependency graph */
+export type ImpactDirection = 'upstream' | 'downstream';
+
+/** A spec reached while walking the dependency graph */
+export interface ImpactedSpec {
+  specName: string;
+  title?: string;
+  status: string;
+  distance: number;
+  path: string[];
+}
+
+/** Transitive impact of a spec in one direction */
+export interface SpecImpact {
+  specName: string;
+  direction: ImpactDirection;
+  maxDepth?: number;
+  specs: ImpactedSpec[];
+  summary: {
+    total: number;
+    open: number;
+    complete: number;
+    archived: number;
+  };
+}
+
+/** Dependency graph export format */
+export type GraphExportFormat = 'dot' | 'mermaid' | 'graphml';
+
+/** How dependency graph nodes are grouped into clusters */
+export type LayoutGrouping = 'none' | 'tag' | 'status';
+
+/** Layered layout of the dependency graph (node coordinates are centers) */
+export interface GraphLayout {
+  nodes: Array<{ id: string; x: number; y: number; layer: number; order: number }>;
+  edges: Array<{
+    source: string;
+    target: string;
+    points: Array<{ x: number; y: number }>;
+    reversed: boolean;
+  }>;
+  clusters: Array<{
+    key: string;
+    nodeIds: string[];
+    x: number;
+    y: number;
+    width: number;
+    height: number;
+  }>;
+  width: number;
+  height: number;
+}
+
+/** Percentile summary of durations, in days */
+export interface DurationSummary {
+  samples: number;
+  meanDays: number;
+  p50Days: number;
+  p85Days: number;
+  p95Days: number;
+}
+
+/** Lead/cycle time, throughput and time-in-status metrics */
+export interface FlowMetrics {
+  from?: string;
+  to?: string;
+  specs: Array<{
+    specName: string;
+    title?: string;
+    status: string;
+    completedAt?: string;
+    leadTimeDays?: number;
+    cycleTimeDays?: number;
+    source: 'transitions' | 'git' | 'none';
+  }>;
+  leadTime: DurationSummary;
+  cycleTime: DurationSummary;
+  throughput: Array<{ weekStart: string; completed: number }>;
+  timeInStatus: Array<DurationSummary & { status: string }>;
+}
+
+/** A project ranked by a spec count */
+export interface ProjectRanking {
+  projectId: string;
+  projectName: string;
+  count: number;
+}
+
+/** Combined stats across registered projects */
+export interface PortfolioStats {
+  totalProjects: number;
+  totalSpecs: number;
+  specsByStatus: StatsResult['specsByStatus'];
+  specsByPriority: StatsResult['specsByPriority'];
+  completionRate: number;
+  activeSpecs: number;
+  projects: Array<{
+    projectId: string;
+    projectName: string;
+    stats: StatsResult;
+    inProgressSpecs: number;
+    staleSpecs: number;
+  }>;
+  mostInProgress: ProjectRanking[];
+  mostStale: ProjectRanking[];
+}
+
+/** Project stats at the end of a day */
+export interface StatsSnapshot {
+  date: string;
+  totalSpecs: number;
+  specsByStatus: StatsResult['specsByStatus'];
+  specsByPriority: StatsResult['specsByPriority'];
+  completionRate: number;
+  activeSpecs: number;
+  backfilled: boolean;
+}
+
+/** Forecast completion after a number of weeks */
+export interface ForecastPoint {
+  weeks: number;
+  date: string;
+}
+
+/** Monte Carlo completion forecast for a set of specs */
+export interface CompletionForecast {
+  totalSpecs: number;
+  remainingSpecs: number;
+  historyWeeks: number;
+  weeklyThroughput: number[];
+  averageWeeklyThroughput: number;
+  trials: number;
+  p50?: ForecastPoint;
+  p85?: ForecastPoint;
+  p95?: ForecastPoint;
+  warning?: string;
+}
+
+/** Per-project staleness thresholds, in days */
+export interface StalenessSettings {
+  inProgressDays: number;
+  plannedDays: number;
+  draftDays: number;
+  assigneeInactiveDays: number;
+}
+
+/** Why a spec was flagged as stale */
+export type StaleReason =
+  | 'stale-in-progress'
+  | 'neglected-planned'
+  | 'untouched-draft'
+  | 'inactive-assignee';
+
+/** Stale and neglected specs */
+export interface StalenessReport {
+  thresholds: StalenessSettings;
+  specs: Array<{
+    specName: string;
+    title?: string;
+    status: string;
+    assignee?: string;
+    reason: StaleReason;
+    days: number;
+    message: string;
+  }>;
+}
+
+/** Workload for one assignee */
+export interface AssigneeStats {
+  assignee: string;
+  aliases: string[];
+  openSpecs: number;
+  openByStatus: StatsResult['specsByStatus'];
+  openByPriority: StatsResult['specsByPriority'];
+  estimatedTokens: number;
+  completedSpecs: number;
+  completedByMonth: Array<{ month: string; completed: number }>;
+}
+
+/** Assignee analytics for a project */
+export interface AssigneeReport {
+  assignees: AssigneeStats[];
+  unassignedHighPriority: Array<{
+    specName: string;
+    title?: string;
+    status: string;
+    priority: string;
+  }>;
+}
+
+/** Why two tags look like duplicates */
+export type DuplicateTagKind = 'spelling' | 'contained' | 'similar';
+
+/** Tag taxonomy analytics for a project */
+export interface TagAnalytics {
+  tags: Array<{
+    tag: string;
+    count: number;
+    byStatus: StatsResult['specsByStatus'];
+  }>;
+  coOccurrence: Array<{ first: string; second: string; count: number }>;
+  nearDuplicates: Array<{
+    first: string;
+    second: string;
+    kind: DuplicateTagKind;
+    similarity: number;
+  }>;
+  orphans: Array<{ tag: string; specName: string }>;
+}
+
+/** Tokenizer used to count spec tokens */
+export type TokenizerKind = 'heuristic' | 'cl100k' | 'o200k';
+
+/** Token count of a spec and the project's thresholds */
+export interface SpecTokenCount {
+  specName: string;
+  tokens: number;
+  tokenizer: TokenizerKind;
+  model?: string;
+  /** The tokenizer isn't the model's own vocabulary, so the count is an estimate */
+  approximate: boolean;
+  warningTokens: number;
+  maxTokens: number;
+}
+
+/** Severity of a validation issue */
+export type IssueSeverity = 'error' | 'warning' | 'info';
+
+/** A validation rule and its effective project configuration */
+export interface ValidationRuleInfo {
+  code: string;
+  description: string;
+  defaultSeverity: IssueSeverity;
+  severity: IssueSeverity;
+  enabled: boolean;
+  params: Record<string, unknown>;
+}
+
+/** Automatic fixes for a spec, with a unified diff of README.md */
+export interface SpecFix {
+  specName: string;
+  fixes: Array<{ code: string; description: string }>;
+  diff: string;
+  /** Pass back to fixSpecIssues to apply exactly this change */
+  contentHash: string;
+}
+
+/** Fixes previewed or applied for a spec or project */
+export interface FixReport {
+  applied: boolean;
+  specs: SpecFix[];
+}
+
+/** Why a spec directory did not load */
+export type LoadFailure =
+  | 'missing-readme'
+  | 'unreadable-readme'
+  | 'invalid-frontmatter'
+  | 'missing-status';
+
+/** A spec directory that was skipped while loading */
+export interface LoadDiagnostic {
+  specName: string;
+  filePath: string;
+  reason: LoadFailure;
+  message: string;
+  line?: number | null;
+  column?: number | null;
+}
+
+/** Validation report export formats */
+export type ValidationReportFormat = 'sarif' | 'junit';
+
+/** A source line referencing a spec */
+export interface CodeReference {
+  /** Path relative to the project root */
+  filePath: string;
+  line: number;
+  text: string;
+}
+
+/** A commit whose message references a spec */
+export interface CommitReference {
+  hash: string;
+  at: string;
+  subject: string;
+}
+
+/** Where a spec is referenced in code and commit messages */
+export interface SpecTraceability {
+  specName: string;
+  status: string;
+  references: CodeReference[];
+  commits: CommitReference[];
+  /** Complete, but no source file references it */
+  untraced: boolean;
+}
+
+export interface TraceabilityReport {
+  specs: SpecTraceability[];
+  filesScanned: number;
+}
+
+export type ActivityKind = 'commit' | 'branch';
+
+/** A commit or branch mentioning a spec */
+export interface ActivityEvent {
+  kind: ActivityKind;
+  /** Commit date, or the date of the branch's tip */
+  at: string;
+  hash: string;
+  /** Commit subject, or branch name */
+  summary: string;
+  /** Whether a branch is merged into the default branch; null for commits */
+  merged?: boolean | null;
+}
+
+/** Commits and branches mentioning a spec, newest first */
+export interface CodeActivity {
+  specName: string;
+  events: ActivityEvent[];
+  lastCodeActivity?: string | null;
+}
+
+/** Task progress of a section, named after its nearest heading */
+export interface SectionTasks {
+  /** Heading text; empty for tasks before the first heading */
+  section: string;
+  tasksTotal: number;
+  tasksDone: number;
+}
+
+/** Markdown task list (`- [ ]` / `- [x]`) progress of a spec */
+export interface TaskProgress {
+  tasksTotal: number;
+  tasksDone: number;
+  taskSections: SectionTasks[];
+}